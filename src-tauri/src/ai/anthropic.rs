@@ -0,0 +1,91 @@
+// Anthropic Messages API (https://docs.anthropic.com/en/api/messages)
+
+use super::{AiCompletion, AiFuture, AiProvider, CompletionOptions, HttpClient};
+use crate::error::JuliError;
+
+const DEFAULT_BASE_URL: &str = "https://api.anthropic.com";
+const DEFAULT_MODEL: &str = "claude-sonnet-4-20250514";
+const API_VERSION: &str = "2023-06-01";
+
+pub struct AnthropicProvider {
+    http: HttpClient,
+    api_key: String,
+    model: String,
+    base_url: String,
+}
+
+impl AnthropicProvider {
+    pub fn new(api_key: String, model: Option<String>, base_url: Option<String>) -> Self {
+        AnthropicProvider {
+            http: HttpClient::default(),
+            api_key,
+            model: model.unwrap_or_else(|| DEFAULT_MODEL.to_string()),
+            base_url: base_url.unwrap_or_else(|| DEFAULT_BASE_URL.to_string()),
+        }
+    }
+
+    // Use other timeouts and retries than RetryPolicy::default()
+    pub fn with_http(mut self, http: HttpClient) -> Self {
+        self.http = http;
+        self
+    }
+}
+
+impl AiProvider for AnthropicProvider {
+    fn name(&self) -> &str {
+        "anthropic"
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn complete<'a>(&'a self, prompt: &'a str, options: &'a CompletionOptions) -> AiFuture<'a> {
+        Box::pin(async move {
+            // Anthropic has no JSON mode, so options.json is left to the prompt itself
+            let mut body = serde_json::json!({
+                "model": self.model,
+                "max_tokens": options.max_tokens,
+                "messages": [
+                    {"role": "user", "content": prompt}
+                ]
+            });
+            // With a schema, the model has to answer by calling a tool that takes exactly that JSON
+            if let Some(schema) = &options.schema {
+                body["tools"] = serde_json::json!([{
+                    "name": schema.name,
+                    "description": schema.description,
+                    "input_schema": schema.schema,
+                }]);
+                body["tool_choice"] = serde_json::json!({"type": "tool", "name": schema.name});
+            }
+
+            let request = self
+                .http
+                .post(format!("{}/v1/messages", self.base_url.trim_end_matches('/')))
+                .header("x-api-key", &self.api_key)
+                .header("anthropic-version", API_VERSION)
+                .header("content-type", "application/json")
+                .json(&body);
+
+            let response_json = self.http.send_json(request, self.name(), &options.attempts).await?;
+
+            // The answer is a list of content blocks
+            // A tool call's input is the structured answer; otherwise join the text blocks together
+            let blocks = response_json["content"].as_array().cloned().unwrap_or_default();
+            let tool_input = blocks
+                .iter()
+                .find(|block| block["type"] == "tool_use")
+                .map(|block| block["input"].to_string());
+            let text = match tool_input {
+                Some(input) => input,
+                None => blocks.iter().filter_map(|block| block["text"].as_str()).collect::<Vec<_>>().join(""),
+            };
+            if text.is_empty() {
+                return Err(JuliError::ai_parse("No text in response").with_details(response_json.to_string()));
+            }
+
+            Ok(AiCompletion { text, raw: response_json })
+        })
+    }
+}
@@ -0,0 +1,58 @@
+// Digging the JSON out of a model's text answer
+// Providers without tool use (or a model that ignores its JSON mode) may wrap the
+// object in ```json fences, or add a sentence before or after it. Instead of
+// deleting backticks - which also mangles any backticks inside the JSON, like in
+// the reasoning - we look for the first complete JSON object in the text
+
+// The first balanced {...} in `text` that is valid JSON
+// Falls back to the trimmed text, so a parse error still shows what the model sent
+//
+// Example:
+// extract_json("Sure! ```json\n{\"reminders\": []}\n```") == "{\"reminders\": []}"
+pub fn extract_json(text: &str) -> &str {
+    for (start, _) in text.match_indices('{') {
+        let candidate = &text[start..];
+        if let Some(end) = balanced_object_end(candidate) {
+            let object = &candidate[..end];
+            // Prose like "use {name} here" is balanced too, but isn't JSON
+            if serde_json::from_str::<serde_json::Value>(object).is_ok() {
+                return object;
+            }
+        }
+    }
+    text.trim()
+}
+
+// `text` starts with '{'; returns the byte length up to and including its matching '}'
+// Braces inside JSON strings don't count, and neither do escaped quotes
+fn balanced_object_end(text: &str) -> Option<usize> {
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for (i, c) in text.char_indices() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+
+        match c {
+            '"' => in_string = true,
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i + 1);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    None
+}
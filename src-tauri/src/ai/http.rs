@@ -0,0 +1,252 @@
+// The HTTP client every provider sends its requests through
+// - connect and request timeouts, so a hung server can't block an analysis forever
+// - retries for failures that are worth retrying (timeouts, 429 rate limits, 5xx, Anthropic's 529 overloaded)
+// - exponential backoff between attempts, or exactly as long as a retry-after header asks for
+// - a maximum number of attempts
+// Every attempt is written to the AttemptLog passed in CompletionOptions, which the
+// analyzer stores next to the AI log entry
+
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use reqwest::{header::HeaderMap, StatusCode};
+use serde::{Deserialize, Serialize};
+
+use crate::error::JuliError;
+
+// The "retry" part of the ai section in config.json, e.g.
+// { "ai": { "retry": { "max_attempts": 5, "request_timeout_ms": 120000 } } }
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct RetryPolicy {
+    // Including the first one; 1 means never retry
+    pub max_attempts: u32,
+    pub connect_timeout_ms: u64,
+    // The whole request, from sending it to reading the last byte of the answer
+    pub request_timeout_ms: u64,
+    // The wait before the second attempt; it doubles after every further failure
+    pub initial_backoff_ms: u64,
+    // The longest we wait between attempts. A retry-after asking for more than
+    // this ends the retries instead
+    pub max_backoff_ms: u64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            connect_timeout_ms: 10_000,
+            request_timeout_ms: 60_000,
+            initial_backoff_ms: 1_000,
+            max_backoff_ms: 30_000,
+        }
+    }
+}
+
+// One try at a request
+// In TypeScript: interface RequestAttempt { attempt: number; status: number | null; error: string | null;
+//   duration_ms: number; retry_in_ms: number | null }
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RequestAttempt {
+    // 1 for the first try
+    pub attempt: u32,
+    // The HTTP status, None when no response came back (timeout, connection refused, ...)
+    pub status: Option<u16>,
+    // None when the attempt succeeded
+    pub error: Option<String>,
+    pub duration_ms: u64,
+    // How long we waited before the next attempt; None when there wasn't one
+    pub retry_in_ms: Option<u64>,
+}
+
+// Collects the attempts of one completion
+// Cloning shares the same list, so the caller keeps a clone and reads it afterwards
+#[derive(Debug, Clone, Default)]
+pub struct AttemptLog(Arc<Mutex<Vec<RequestAttempt>>>);
+
+impl AttemptLog {
+    pub fn record(&self, attempt: RequestAttempt) {
+        self.0.lock().unwrap().push(attempt);
+    }
+
+    pub fn attempts(&self) -> Vec<RequestAttempt> {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HttpClient {
+    // reqwest::Client is a handle to a connection pool, so clones share connections
+    client: reqwest::Client,
+    policy: RetryPolicy,
+}
+
+impl HttpClient {
+    pub fn new(policy: RetryPolicy) -> Self {
+        let client = reqwest::Client::builder()
+            .connect_timeout(Duration::from_millis(policy.connect_timeout_ms))
+            .timeout(Duration::from_millis(policy.request_timeout_ms))
+            .build()
+            .unwrap_or_default();
+        HttpClient { client, policy }
+    }
+
+    pub fn post(&self, url: impl reqwest::IntoUrl) -> reqwest::RequestBuilder {
+        self.client.post(url)
+    }
+
+    // Send a request and return the JSON body, turning every failure into a typed error
+    // Used by all three providers so their error messages look the same
+    pub async fn send_json(
+        &self,
+        request: reqwest::RequestBuilder,
+        provider: &str,
+        log: &AttemptLog,
+    ) -> Result<serde_json::Value, JuliError> {
+        let max_attempts = self.policy.max_attempts.max(1);
+
+        for attempt in 1..=max_attempts {
+            // A JSON body can always be cloned; only streaming bodies can't
+            let this_try = request
+                .try_clone()
+                .ok_or_else(|| JuliError::ai_transport(format!("{} request can't be retried", provider)))?;
+
+            let started = Instant::now();
+            let result = send_once(this_try, provider).await;
+            let duration_ms = started.elapsed().as_millis() as u64;
+
+            let failure = match result {
+                Ok((status, body)) => {
+                    log.record(RequestAttempt { attempt, status: Some(status), error: None, duration_ms, retry_in_ms: None });
+                    return Ok(body);
+                }
+                Err(failure) => failure,
+            };
+
+            let wait = if failure.retryable && attempt < max_attempts {
+                self.wait_before_retry(attempt, failure.retry_after)
+            } else {
+                None
+            };
+            log.record(RequestAttempt {
+                attempt,
+                status: failure.status,
+                error: Some(failure.error.to_string()),
+                duration_ms,
+                retry_in_ms: wait.map(|w| w.as_millis() as u64),
+            });
+
+            match wait {
+                Some(wait) => {
+                    println!(
+                        "🔁 {} attempt {}/{} failed ({}), retrying in {}ms",
+                        provider,
+                        attempt,
+                        max_attempts,
+                        failure.error,
+                        wait.as_millis()
+                    );
+                    tokio::time::sleep(wait).await;
+                }
+                None => {
+                    let error = if attempt > 1 {
+                        failure.error.with_details(format!("gave up after {} attempts", attempt))
+                    } else {
+                        failure.error
+                    };
+                    return Err(error);
+                }
+            }
+        }
+
+        unreachable!("the last attempt always returns")
+    }
+
+    // Exponential backoff, unless the server said how long to wait
+    // None when the server wants us to wait longer than max_backoff_ms
+    fn wait_before_retry(&self, attempt: u32, retry_after: Option<Duration>) -> Option<Duration> {
+        let max = Duration::from_millis(self.policy.max_backoff_ms);
+        match retry_after {
+            Some(retry_after) if retry_after > max => None,
+            Some(retry_after) => Some(retry_after),
+            None => {
+                let backoff = self.policy.initial_backoff_ms.saturating_mul(1 << (attempt - 1).min(20));
+                Some(Duration::from_millis(backoff).min(max))
+            }
+        }
+    }
+}
+
+impl Default for HttpClient {
+    fn default() -> Self {
+        HttpClient::new(RetryPolicy::default())
+    }
+}
+
+// Why one attempt failed, and whether another one could succeed
+struct Failure {
+    error: JuliError,
+    status: Option<u16>,
+    retryable: bool,
+    retry_after: Option<Duration>,
+}
+
+// The status and JSON body of a successful response
+async fn send_once(request: reqwest::RequestBuilder, provider: &str) -> Result<(u16, serde_json::Value), Failure> {
+    let response = request.send().await.map_err(|e| Failure {
+        // No response at all: timeouts, refused or dropped connections are all worth another try
+        retryable: !e.is_builder(),
+        error: JuliError::ai_transport(format!("{} request failed: {}", provider, e)),
+        status: None,
+        retry_after: None,
+    })?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let retry_after = retry_after(response.headers());
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(Failure {
+            error: JuliError::ai_transport(format!("{} API error: {}", provider, status)).with_details(error_text),
+            status: Some(status.as_u16()),
+            retryable: is_retryable(status),
+            retry_after,
+        });
+    }
+
+    let body = response.json().await.map_err(|e| Failure {
+        // The body timed out halfway: retry. It arrived but isn't JSON: the next one won't be either
+        retryable: e.is_timeout(),
+        error: JuliError::ai_parse(format!("Failed to parse {} response: {}", provider, e)),
+        status: Some(status.as_u16()),
+        retry_after: None,
+    })?;
+    Ok((status.as_u16(), body))
+}
+
+// Rate limits, timeouts and server-side trouble; 529 is Anthropic's "overloaded"
+// Other 4xx (bad API key, invalid request, ...) fail the same way every time
+fn is_retryable(status: StatusCode) -> bool {
+    matches!(status.as_u16(), 408 | 429 | 500 | 502 | 503 | 504 | 529)
+}
+
+// retry-after is either a number of seconds or an HTTP date
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(reqwest::header::RETRY_AFTER)?.to_str().ok()?.trim();
+
+    // The server picks this, and "inf" or "1e30" parse as well: too long for a Duration is
+    // simply longer than max_backoff_ms, so wait_before_retry gives up instead of panicking
+    if let Ok(seconds) = value.parse::<f64>() {
+        return match Duration::try_from_secs_f64(seconds) {
+            Ok(wait) => Some(wait),
+            Err(_) if seconds > 0.0 => Some(Duration::MAX),
+            // Negative or NaN
+            Err(_) => None,
+        };
+    }
+
+    let at = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let wait = at.signed_duration_since(chrono::Utc::now());
+    Some(wait.to_std().unwrap_or(Duration::ZERO))
+}
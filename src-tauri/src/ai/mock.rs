@@ -0,0 +1,83 @@
+// A scripted provider that never touches the network
+// Used by the integration tests in tests/, and handy for working on the UI offline
+//
+// Usage:
+// let provider = MockProvider::new()
+//     .respond_when("Call John", r#"{"reminders": [...], "reasoning": "..."}"#)
+//     .respond_otherwise(r#"{"reminders": [], "reasoning": "nothing to do"}"#);
+
+use std::sync::Mutex;
+
+use super::{AiCompletion, AiFuture, AiProvider, CompletionOptions};
+use crate::error::JuliError;
+
+#[derive(Default)]
+pub struct MockProvider {
+    // (text the prompt must contain, canned response) - first match wins
+    rules: Vec<(String, String)>,
+    // Returned when no rule matches; without one the call fails like a network error
+    fallback: Option<String>,
+    // Every prompt we were asked to complete, in order
+    prompts: Mutex<Vec<String>>,
+    // Reported by model(); "mock" unless set with with_model()
+    model: Option<String>,
+}
+
+impl MockProvider {
+    pub fn new() -> Self {
+        MockProvider::default()
+    }
+
+    pub fn respond_when(mut self, prompt_contains: impl Into<String>, response: impl Into<String>) -> Self {
+        self.rules.push((prompt_contains.into(), response.into()));
+        self
+    }
+
+    pub fn respond_otherwise(mut self, response: impl Into<String>) -> Self {
+        self.fallback = Some(response.into());
+        self
+    }
+
+    // Pretend to be a different model, e.g. to test that switching models invalidates cached analyses
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.model = Some(model.into());
+        self
+    }
+
+    pub fn prompts(&self) -> Vec<String> {
+        self.prompts.lock().unwrap().clone()
+    }
+
+    pub fn call_count(&self) -> usize {
+        self.prompts.lock().unwrap().len()
+    }
+}
+
+impl AiProvider for MockProvider {
+    fn name(&self) -> &str {
+        "mock"
+    }
+
+    fn model(&self) -> &str {
+        self.model.as_deref().unwrap_or("mock")
+    }
+
+    fn complete<'a>(&'a self, prompt: &'a str, _options: &'a CompletionOptions) -> AiFuture<'a> {
+        Box::pin(async move {
+            self.prompts.lock().unwrap().push(prompt.to_string());
+
+            let response = self
+                .rules
+                .iter()
+                .find(|(needle, _)| prompt.contains(needle.as_str()))
+                .map(|(_, response)| response.clone())
+                .or_else(|| self.fallback.clone())
+                .ok_or_else(|| JuliError::ai_transport("mock request failed: no scripted response for this prompt"))?;
+
+            Ok(AiCompletion {
+                raw: serde_json::json!({ "text": response }),
+                text: response,
+            })
+        })
+    }
+}
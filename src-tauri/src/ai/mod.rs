@@ -0,0 +1,159 @@
+// ============================================================================
+// AI PROVIDERS
+// ============================================================================
+
+// Everything that talks to a language model goes through the AiProvider trait,
+// so the analysis code doesn't care whether the note goes to Anthropic,
+// an OpenAI-compatible server, or a local Ollama model.
+//
+// In TypeScript this would be:
+// interface AiProvider { complete(prompt: string, options: CompletionOptions): Promise<AiCompletion> }
+
+use std::{future::Future, pin::Pin};
+
+use serde::Deserialize;
+
+use crate::error::JuliError;
+
+mod anthropic;
+mod extract;
+mod http;
+mod mock;
+mod ollama;
+mod openai;
+
+pub use anthropic::AnthropicProvider;
+pub use extract::extract_json;
+pub use http::{AttemptLog, HttpClient, RequestAttempt, RetryPolicy};
+pub use mock::MockProvider;
+pub use ollama::OllamaProvider;
+pub use openai::OpenAiProvider;
+
+// Trait methods can't be `async fn` if we want to pick the provider at runtime
+// (Box<dyn AiProvider>), so each provider returns a boxed future instead
+// Think of it as the Rust spelling of `Promise<Result<AiCompletion, JuliError>>`
+pub type AiFuture<'a> = Pin<Box<dyn Future<Output = Result<AiCompletion, JuliError>> + Send + 'a>>;
+
+pub trait AiProvider: Send + Sync {
+    // Short identifier used in logs, e.g. "anthropic"
+    fn name(&self) -> &str;
+
+    // The model every request is sent to
+    fn model(&self) -> &str;
+
+    fn complete<'a>(&'a self, prompt: &'a str, options: &'a CompletionOptions) -> AiFuture<'a>;
+}
+
+#[derive(Debug, Clone)]
+pub struct CompletionOptions {
+    pub max_tokens: u32,
+    // Ask the provider for a JSON-only answer when it supports a JSON mode
+    pub json: bool,
+    // The shape the JSON answer must have, for providers that can enforce it
+    pub schema: Option<OutputSchema>,
+    // Every HTTP attempt (including retries) is recorded here; keep a clone to read them back
+    pub attempts: AttemptLog,
+}
+
+impl Default for CompletionOptions {
+    fn default() -> Self {
+        CompletionOptions { max_tokens: 1024, json: false, schema: None, attempts: AttemptLog::default() }
+    }
+}
+
+// A JSON schema for the answer
+// Anthropic gets it as a tool the model is forced to call, and the tool call's input
+// (already parsed JSON) becomes the completion text. Providers without tool use only get
+// their JSON mode, so callers should still run the text through extract_json()
+#[derive(Debug, Clone)]
+pub struct OutputSchema {
+    // Tool name - letters, digits and underscores only
+    pub name: &'static str,
+    pub description: &'static str,
+    pub schema: serde_json::Value,
+}
+
+#[derive(Debug, Clone)]
+pub struct AiCompletion {
+    // The model's answer as plain text
+    pub text: String,
+    // The provider's full JSON response, for logging and debugging
+    pub raw: serde_json::Value,
+}
+
+// ============================================================================
+// PROVIDER SELECTION
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProviderKind {
+    #[default]
+    Anthropic,
+    // Any server that speaks the OpenAI /chat/completions API (OpenAI, LM Studio, vLLM, ...)
+    #[serde(alias = "openai-compatible")]
+    OpenAi,
+    Ollama,
+}
+
+impl ProviderKind {
+    pub fn parse(value: &str) -> Option<ProviderKind> {
+        match value.trim().to_lowercase().as_str() {
+            "anthropic" | "claude" => Some(ProviderKind::Anthropic),
+            "openai" | "openai-compatible" => Some(ProviderKind::OpenAi),
+            "ollama" => Some(ProviderKind::Ollama),
+            _ => None,
+        }
+    }
+}
+
+// The "ai" section of config.json, e.g.
+// { "ai": { "provider": "ollama", "model": "llama3.1", "base_url": "http://localhost:11434" } }
+// Every field is optional; the defaults keep the original Anthropic setup working
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct AiSettings {
+    #[serde(default)]
+    pub provider: ProviderKind,
+    pub model: Option<String>,
+    pub base_url: Option<String>,
+    pub api_key: Option<String>,
+    // Keep the AI's reminder changes as proposals to accept or reject, instead of applying them
+    #[serde(default)]
+    pub review_changes: bool,
+    // Timeouts and retries for the provider's HTTP requests
+    #[serde(default)]
+    pub retry: RetryPolicy,
+}
+
+// Build the provider described by the settings
+// `claude_api_key` is the legacy top-level key, used when the ai section has none
+pub fn build_provider(settings: &AiSettings, claude_api_key: Option<String>) -> Result<Box<dyn AiProvider>, JuliError> {
+    let api_key = settings.api_key.clone().filter(|k| !k.is_empty());
+    let http = HttpClient::new(settings.retry.clone());
+
+    match settings.provider {
+        ProviderKind::Anthropic => {
+            let api_key = api_key.or(claude_api_key).filter(|k| !k.is_empty()).ok_or_else(|| {
+                JuliError::config("No Anthropic API key configured. Set CLAUDE_API_KEY or add \"claude_api_key\" to config.json")
+            })?;
+            Ok(Box::new(
+                AnthropicProvider::new(api_key, settings.model.clone(), settings.base_url.clone()).with_http(http),
+            ))
+        }
+        ProviderKind::OpenAi => Ok(Box::new(
+            OpenAiProvider::new(api_key, settings.model.clone(), settings.base_url.clone()).with_http(http),
+        )),
+        ProviderKind::Ollama => Ok(Box::new(
+            OllamaProvider::new(settings.model.clone(), settings.base_url.clone()).with_http(http),
+        )),
+    }
+}
+
+// ============================================================================
+// SHARED HELPERS
+// ============================================================================
+
+// Remove ```json ... ``` fences that models like to wrap their JSON in
+pub fn strip_code_fences(text: &str) -> String {
+    text.replace("```json", "").replace("```", "").trim().to_string()
+}
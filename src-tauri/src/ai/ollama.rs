@@ -0,0 +1,71 @@
+// Local Ollama server (https://github.com/ollama/ollama/blob/main/docs/api.md)
+// Notes never leave the machine, so no API key is involved
+
+use super::{AiCompletion, AiFuture, AiProvider, CompletionOptions, HttpClient};
+use crate::error::JuliError;
+
+const DEFAULT_BASE_URL: &str = "http://localhost:11434";
+const DEFAULT_MODEL: &str = "llama3.1";
+
+pub struct OllamaProvider {
+    http: HttpClient,
+    model: String,
+    base_url: String,
+}
+
+impl OllamaProvider {
+    pub fn new(model: Option<String>, base_url: Option<String>) -> Self {
+        OllamaProvider {
+            http: HttpClient::default(),
+            model: model.unwrap_or_else(|| DEFAULT_MODEL.to_string()),
+            base_url: base_url.unwrap_or_else(|| DEFAULT_BASE_URL.to_string()),
+        }
+    }
+
+    // Use other timeouts and retries than RetryPolicy::default()
+    pub fn with_http(mut self, http: HttpClient) -> Self {
+        self.http = http;
+        self
+    }
+}
+
+impl AiProvider for OllamaProvider {
+    fn name(&self) -> &str {
+        "ollama"
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn complete<'a>(&'a self, prompt: &'a str, options: &'a CompletionOptions) -> AiFuture<'a> {
+        Box::pin(async move {
+            // stream: false makes Ollama return one JSON object instead of a line per token
+            let mut body = serde_json::json!({
+                "model": self.model,
+                "stream": false,
+                "messages": [
+                    {"role": "user", "content": prompt}
+                ],
+                "options": {"num_predict": options.max_tokens}
+            });
+            if options.json {
+                body["format"] = serde_json::json!("json");
+            }
+
+            let request = self
+                .http
+                .post(format!("{}/api/chat", self.base_url.trim_end_matches('/')))
+                .json(&body);
+
+            let response_json = self.http.send_json(request, self.name(), &options.attempts).await?;
+
+            let text = response_json["message"]["content"]
+                .as_str()
+                .map(|text| text.to_string())
+                .ok_or_else(|| JuliError::ai_parse("No text in response").with_details(response_json.to_string()))?;
+
+            Ok(AiCompletion { text, raw: response_json })
+        })
+    }
+}
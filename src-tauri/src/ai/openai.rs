@@ -0,0 +1,75 @@
+// OpenAI-compatible Chat Completions API
+// Works with OpenAI itself and with servers that copy its API (LM Studio, vLLM, llama.cpp, ...)
+
+use super::{AiCompletion, AiFuture, AiProvider, CompletionOptions, HttpClient};
+use crate::error::JuliError;
+
+const DEFAULT_BASE_URL: &str = "https://api.openai.com/v1";
+const DEFAULT_MODEL: &str = "gpt-4o-mini";
+
+pub struct OpenAiProvider {
+    http: HttpClient,
+    // Optional because many self-hosted servers don't check it
+    api_key: Option<String>,
+    model: String,
+    base_url: String,
+}
+
+impl OpenAiProvider {
+    pub fn new(api_key: Option<String>, model: Option<String>, base_url: Option<String>) -> Self {
+        OpenAiProvider {
+            http: HttpClient::default(),
+            api_key,
+            model: model.unwrap_or_else(|| DEFAULT_MODEL.to_string()),
+            base_url: base_url.unwrap_or_else(|| DEFAULT_BASE_URL.to_string()),
+        }
+    }
+
+    // Use other timeouts and retries than RetryPolicy::default()
+    pub fn with_http(mut self, http: HttpClient) -> Self {
+        self.http = http;
+        self
+    }
+}
+
+impl AiProvider for OpenAiProvider {
+    fn name(&self) -> &str {
+        "openai"
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn complete<'a>(&'a self, prompt: &'a str, options: &'a CompletionOptions) -> AiFuture<'a> {
+        Box::pin(async move {
+            let mut body = serde_json::json!({
+                "model": self.model,
+                "max_tokens": options.max_tokens,
+                "messages": [
+                    {"role": "user", "content": prompt}
+                ]
+            });
+            if options.json {
+                body["response_format"] = serde_json::json!({"type": "json_object"});
+            }
+
+            let mut request = self
+                .http
+                .post(format!("{}/chat/completions", self.base_url.trim_end_matches('/')))
+                .json(&body);
+            if let Some(api_key) = &self.api_key {
+                request = request.bearer_auth(api_key);
+            }
+
+            let response_json = self.http.send_json(request, self.name(), &options.attempts).await?;
+
+            let text = response_json["choices"][0]["message"]["content"]
+                .as_str()
+                .map(|text| text.to_string())
+                .ok_or_else(|| JuliError::ai_parse("No text in response").with_details(response_json.to_string()))?;
+
+            Ok(AiCompletion { text, raw: response_json })
+        })
+    }
+}
@@ -0,0 +1,667 @@
+// ============================================================================
+// JULI COMMAND LINE
+// ============================================================================
+
+// A terminal frontend for the same app.sqlite the desktop app uses
+// All the real work happens in juli_lib::core, so a note added here is saved
+// and analyzed exactly like one typed into the app
+//
+// Usage:
+//   juli-cli note add --date 2026-10-16 "Call John about the budget"
+//   juli-cli note add --no-ai "Just a thought"
+//   juli-cli note show
+//   juli-cli note history --date 2026-10-16
+//   juli-cli note restore 17
+//   juli-cli reminders list --unresolved --tag work
+//   juli-cli reminders resolve 42
+//   juli-cli reminders list --sort priority
+//   juli-cli reminders priority 42 urgent
+//   juli-cli reminders add "Renew passport" --due 2026-11-01 --tags admin
+//   juli-cli reminders edit 42 --due ""
+//   juli-cli reminders add "Pay rent" --due 2026-11-01 --repeat "FREQ=MONTHLY;BYMONTHDAY=1"
+//   juli-cli reminders add "Dentist" --due 2026-10-20 --time 15:30
+//   juli-cli proposals list
+//   juli-cli proposals accept 3 --text "Call John about the budget"
+//   juli-cli logs tail -n 5
+//   juli-cli search john budget --from 2026-10-01 --tag work
+
+use std::{path::PathBuf, process::ExitCode, sync::{Mutex, OnceLock}, thread, time::Duration};
+
+use chrono::NaiveDate;
+use chrono_tz::Tz;
+use clap::{Args, Parser, Subcommand};
+use rusqlite::Connection;
+
+use juli_lib::core::{
+    database_path, default_config_dir, default_data_dir, load_config, lock_connection, note_date_key, open_database, today_in,
+    AiLogRow, AiLogStore, AnalysisOutcome, AnalysisQueue, Analyzer, NewAiLog, NoteStore, ProposalRow, ProposalStore, Recurrence, ReminderDraft,
+    ReminderPatch, ReminderRow, ReminderSort, ReminderStore, RevisionPolicy, RevisionStore, SearchHitKind, SearchIndex, SearchQuery, SearchScope,
+};
+use juli_lib::error::JuliError;
+
+// clap turns these structs into the argument parser
+// In TypeScript this is roughly what yargs/commander build from a schema
+#[derive(Parser)]
+#[command(name = "juli-cli", version, about = "Capture notes and manage juli reminders from the terminal")]
+struct Cli {
+    /// Use another database file instead of the desktop app's app.sqlite
+    #[arg(long, global = true, value_name = "PATH")]
+    db: Option<PathBuf>,
+
+    /// Save notes without sending them to the AI provider
+    #[arg(long, global = true)]
+    no_ai: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Add to or show a day's note
+    #[command(subcommand)]
+    Note(NoteCommand),
+    /// List, add, edit and resolve reminders
+    #[command(subcommand)]
+    Reminders(RemindersCommand),
+    /// Review the reminder changes the AI proposed (when review_changes is on)
+    #[command(subcommand)]
+    Proposals(ProposalsCommand),
+    /// Inspect the AI interaction log
+    #[command(subcommand)]
+    Logs(LogsCommand),
+    /// Full-text search over notes and reminders
+    Search(SearchArgs),
+}
+
+#[derive(Subcommand)]
+enum NoteCommand {
+    /// Append a line to a day's note and extract reminders from it
+    Add {
+        /// Day the note belongs to, as YYYY-MM-DD (defaults to today)
+        #[arg(long)]
+        date: Option<NaiveDate>,
+        text: String,
+    },
+    /// Print a day's note
+    Show {
+        /// Day to show, as YYYY-MM-DD (defaults to today)
+        #[arg(long)]
+        date: Option<NaiveDate>,
+    },
+    /// List the saved versions of a day's note, newest first
+    History {
+        /// Day to show, as YYYY-MM-DD (defaults to today)
+        #[arg(long)]
+        date: Option<NaiveDate>,
+    },
+    /// Show what changed between two revisions
+    Diff { from: i64, to: i64 },
+    /// Make an earlier revision the note's text again
+    Restore { revision: i64 },
+}
+
+#[derive(Subcommand)]
+enum RemindersCommand {
+    /// List reminders, ordered by due date
+    List(ListArgs),
+    /// Mark a reminder as done (a recurring one gets its next occurrence)
+    Resolve { id: i64 },
+    /// Mark a resolved reminder as open again
+    Unresolve { id: i64 },
+    /// Set a reminder's priority yourself (low, normal, high or urgent); the AI keeps it from then on
+    Priority { id: i64, priority: String },
+    /// Push a reminder out: a date (YYYY-MM-DD), "1d", "2w", "tomorrow", "next monday", "next week" or "eow"
+    Snooze { id: i64, until: String },
+    /// Add a reminder by hand, without a note or the AI
+    Add {
+        text: String,
+        /// Comma-separated tags
+        #[arg(long)]
+        tags: Option<String>,
+        /// Due date, as YYYY-MM-DD
+        #[arg(long)]
+        due: Option<String>,
+        /// Due time on that date, as 24-hour HH:MM in your timezone
+        #[arg(long)]
+        time: Option<String>,
+        /// Repeat it, as an RRULE like "FREQ=WEEKLY;BYDAY=MO"
+        #[arg(long)]
+        repeat: Option<String>,
+        /// low, normal, high or urgent
+        #[arg(long)]
+        priority: Option<String>,
+    },
+    /// Change a reminder's text, tags, due date and time, or recurrence
+    Edit {
+        id: i64,
+        /// New text
+        #[arg(long)]
+        text: Option<String>,
+        /// New comma-separated tags ("" for none)
+        #[arg(long)]
+        tags: Option<String>,
+        /// New due date, as YYYY-MM-DD ("" for none)
+        #[arg(long)]
+        due: Option<String>,
+        /// New due time, as 24-hour HH:MM in your timezone ("" for none)
+        #[arg(long)]
+        time: Option<String>,
+        /// New recurrence, as an RRULE ("" to stop repeating)
+        #[arg(long)]
+        repeat: Option<String>,
+        /// New priority: low, normal, high or urgent
+        #[arg(long)]
+        priority: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ProposalsCommand {
+    /// List the proposals waiting for a decision
+    List,
+    /// Apply a proposal, optionally changing it first
+    Accept {
+        id: i64,
+        /// Use this text instead of the proposed one
+        #[arg(long)]
+        text: Option<String>,
+        /// Use these comma-separated tags instead ("" for none)
+        #[arg(long)]
+        tags: Option<String>,
+        /// Use this due date instead, as YYYY-MM-DD ("" for none)
+        #[arg(long)]
+        due: Option<String>,
+        /// Use this due time instead, as 24-hour HH:MM in your timezone ("" for none)
+        #[arg(long)]
+        time: Option<String>,
+        /// Use this recurrence instead, as an RRULE ("" for none)
+        #[arg(long)]
+        repeat: Option<String>,
+    },
+    /// Throw a proposal away without changing any reminder
+    Reject { id: i64 },
+}
+
+#[derive(Args)]
+struct ListArgs {
+    /// Only open reminders
+    #[arg(long, conflicts_with = "resolved")]
+    unresolved: bool,
+    /// Only resolved reminders
+    #[arg(long)]
+    resolved: bool,
+    /// Only reminders with this tag
+    #[arg(long)]
+    tag: Option<String>,
+    /// Order by due (default), priority or created (newest first)
+    #[arg(long, default_value = "due")]
+    sort: ReminderSort,
+}
+
+#[derive(Args)]
+struct SearchArgs {
+    /// Words to look for; the last one also matches as a prefix
+    #[arg(required = true)]
+    words: Vec<String>,
+    /// Only search notes
+    #[arg(long, conflicts_with = "reminders")]
+    notes: bool,
+    /// Only search reminders
+    #[arg(long)]
+    reminders: bool,
+    /// Earliest note date, as YYYY-MM-DD
+    #[arg(long)]
+    from: Option<NaiveDate>,
+    /// Latest note date, as YYYY-MM-DD
+    #[arg(long)]
+    to: Option<NaiveDate>,
+    /// Only reminders with this tag (repeat for several)
+    #[arg(long)]
+    tag: Vec<String>,
+    /// Only open reminders
+    #[arg(long, conflicts_with = "resolved")]
+    unresolved: bool,
+    /// Only resolved reminders
+    #[arg(long)]
+    resolved: bool,
+    /// Maximum number of hits
+    #[arg(short = 'n', long, default_value_t = 20)]
+    limit: usize,
+}
+
+#[derive(Subcommand)]
+enum LogsCommand {
+    /// Print the most recent AI interactions
+    Tail {
+        /// How many entries to print
+        #[arg(short = 'n', long, default_value_t = 10)]
+        lines: usize,
+        /// Keep running and print new entries as they are written
+        #[arg(short, long)]
+        follow: bool,
+    },
+}
+
+fn main() -> ExitCode {
+    // Same as the desktop app: .env overrides config.json during development
+    dotenvy::dotenv().ok();
+
+    let cli = Cli::parse();
+    match run(cli) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(cli: Cli) -> Result<(), JuliError> {
+    let path = match cli.db {
+        Some(path) => path,
+        None => database_path(&default_data_dir()?),
+    };
+    let conn = open_database(&path)?;
+
+    match cli.command {
+        Command::Note(NoteCommand::Add { date, text }) => add_note(conn, date, &text, cli.no_ai),
+        Command::Note(NoteCommand::Show { date }) => {
+            let note = NoteStore::new(&conn).for_date(&date_key(date))?;
+            println!("{}", note.for_date);
+            println!();
+            println!("{}", note.text);
+            Ok(())
+        }
+        Command::Note(NoteCommand::History { date }) => {
+            let note = NoteStore::new(&conn).for_date(&date_key(date))?;
+            for revision in RevisionStore::new(&conn).for_note(note.id)? {
+                println!(
+                    "{:>4}  {} - {}  {} chars",
+                    revision.id, revision.created_at, revision.updated_at, revision.size
+                );
+            }
+            Ok(())
+        }
+        Command::Note(NoteCommand::Diff { from, to }) => {
+            print!("{}", RevisionStore::new(&conn).diff(from, to)?.unified);
+            Ok(())
+        }
+        Command::Note(NoteCommand::Restore { revision }) => {
+            let note = NoteStore::new(&conn)
+                .with_revision_policy(revision_policy())
+                .restore_revision(revision)?;
+            println!("✓ Restored revision {} of {}", revision, note.for_date);
+            Ok(())
+        }
+        Command::Reminders(RemindersCommand::List(args)) => list_reminders(&conn, &args),
+        Command::Reminders(RemindersCommand::Resolve { id }) => {
+            let reminders = ReminderStore::new(&conn).with_timezone(timezone());
+            let next = reminders.resolve(id)?;
+            println!("✓ Resolved reminder {}", id);
+            if let Some(next) = next {
+                println!("↻ Next occurrence: {}", format_reminder(&reminders.get(next)?).trim_start());
+            }
+            Ok(())
+        }
+        Command::Reminders(RemindersCommand::Unresolve { id }) => {
+            ReminderStore::new(&conn).unresolve(id)?;
+            println!("✓ Reopened reminder {}", id);
+            Ok(())
+        }
+        Command::Reminders(RemindersCommand::Priority { id, priority }) => {
+            println!("{}", format_reminder(&ReminderStore::new(&conn).set_priority(id, &priority)?));
+            Ok(())
+        }
+        Command::Reminders(RemindersCommand::Snooze { id, until }) => {
+            let reminder = ReminderStore::new(&conn).with_timezone(timezone()).snooze(id, &until)?;
+            println!("💤 {}", format_reminder(&reminder).trim_start());
+            Ok(())
+        }
+        Command::Reminders(RemindersCommand::Add { text, tags, due, time, repeat, priority }) => {
+            let reminders = ReminderStore::new(&conn).with_timezone(timezone());
+            let draft = ReminderDraft { text, tags, due_date: due, due_time: time, timezone: None, recurrence: repeat, priority };
+            let reminder_id = reminders.create_manual(draft)?;
+            println!("{}", format_reminder(&reminders.get(reminder_id)?));
+            Ok(())
+        }
+        Command::Reminders(RemindersCommand::Edit { id, text, tags, due, time, repeat, priority }) => {
+            // Anything not given on the command line stays as it is
+            let patch = ReminderPatch {
+                text,
+                tags: tags.map(|t| Some(t).filter(|t| !t.is_empty())),
+                due_date: due.map(|d| Some(d).filter(|d| !d.is_empty())),
+                due_time: time.map(|t| Some(t).filter(|t| !t.is_empty())),
+                recurrence: repeat.map(|r| Some(r).filter(|r| !r.is_empty())),
+                priority,
+            };
+            println!("{}", format_reminder(&ReminderStore::new(&conn).with_timezone(timezone()).edit(id, patch)?));
+            Ok(())
+        }
+        Command::Proposals(ProposalsCommand::List) => {
+            for proposal in ProposalStore::new(&conn).pending()? {
+                print_proposal(&proposal);
+            }
+            Ok(())
+        }
+        Command::Proposals(ProposalsCommand::Accept { id, text, tags, due, time, repeat }) => {
+            let store = ProposalStore::new(&conn).with_timezone(timezone());
+            let edits = if text.is_none() && tags.is_none() && due.is_none() && time.is_none() && repeat.is_none() {
+                None
+            } else {
+                // Anything not given on the command line keeps the proposed value
+                let proposed = store.get(id)?.proposed;
+                Some(ReminderDraft {
+                    text: text.unwrap_or(proposed.text),
+                    tags: tags.map(|t| Some(t).filter(|t| !t.is_empty())).unwrap_or(proposed.tags),
+                    due_date: due.map(|d| Some(d).filter(|d| !d.is_empty())).unwrap_or(proposed.due_date),
+                    due_time: time.map(|t| Some(t).filter(|t| !t.is_empty())).unwrap_or(proposed.due_time),
+                    timezone: proposed.timezone,
+                    recurrence: repeat.map(|r| Some(r).filter(|r| !r.is_empty())).unwrap_or(proposed.recurrence),
+                    priority: proposed.priority,
+                })
+            };
+            let reminder_id = store.accept(id, edits)?;
+            println!("✓ Accepted proposal {}", id);
+            println!("{}", format_reminder(&ReminderStore::new(&conn).get(reminder_id)?));
+            Ok(())
+        }
+        Command::Proposals(ProposalsCommand::Reject { id }) => {
+            ProposalStore::new(&conn).reject(id)?;
+            println!("✓ Rejected proposal {}", id);
+            Ok(())
+        }
+        Command::Logs(LogsCommand::Tail { lines, follow }) => tail_logs(&conn, lines, follow),
+        Command::Search(args) => search(&conn, args),
+    }
+}
+
+// The desktop app's revision settings, or the defaults when config.json is missing or broken
+fn revision_policy() -> RevisionPolicy {
+    default_config_dir()
+        .and_then(|dir| load_config(&dir.join("config.json")))
+        .map(|config| config.revisions)
+        .unwrap_or_default()
+}
+
+// The desktop app's timezone, or the system's when config.json is missing or broken
+// Read once: every reminder line compares its timezone against it
+fn timezone() -> Tz {
+    static TIMEZONE: OnceLock<Tz> = OnceLock::new();
+    *TIMEZONE.get_or_init(|| {
+        default_config_dir()
+            .and_then(|dir| load_config(&dir.join("config.json")))
+            .unwrap_or_default()
+            .timezone()
+    })
+}
+
+fn date_key(date: Option<NaiveDate>) -> String {
+    date.map(note_date_key).unwrap_or_else(|| note_date_key(today_in(timezone())))
+}
+
+// ============================================================================
+// NOTES
+// ============================================================================
+
+// The CLI equivalent of the add_note command: save first, then analyze the whole note
+fn add_note(conn: Connection, date: Option<NaiveDate>, text: &str, no_ai: bool) -> Result<(), JuliError> {
+    let for_date = date_key(date);
+    let config_path = default_config_dir()?.join("config.json");
+    let config = load_config(&config_path);
+
+    let policy = config.as_ref().map(|c| c.revisions.clone()).unwrap_or_default();
+    let (note_id, full_text) = NoteStore::new(&conn).with_revision_policy(policy).append(text, &for_date)?;
+    println!("✓ Saved note for {}", for_date);
+
+    if no_ai {
+        return Ok(());
+    }
+
+    let db = Mutex::new(conn);
+
+    // A missing API key or broken config.json is logged like any other failed AI call
+    let review = config.as_ref().is_ok_and(|c| c.ai.review_changes);
+    let provider = match config.and_then(|config| config.provider()) {
+        Ok(provider) => provider,
+        Err(e) => {
+            let conn = lock_connection(&db);
+            AiLogStore::new(&conn).insert(&NewAiLog::failure(note_id, "", &format!("AI API call failed: {}", e)))?;
+            return Err(e.with_details(format!("config.json location: {} (use --no-ai to skip analysis)", config_path.display())));
+        }
+    };
+
+    // The note's own date is "today" for the prompt, so "tomorrow" in a
+    // back-dated note means the day after that note, just like in the app
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| JuliError::ai_transport(format!("Could not start the async runtime: {}", e)))?;
+    let analyzer = Analyzer::new(provider.as_ref()).with_review(review).with_timezone(timezone());
+    let outcome = match runtime.block_on(analyzer.analyze(&db, note_id, &full_text, &for_date)) {
+        Ok(outcome) => outcome,
+        // Offline: leave it to the desktop app's queue, which retries until the provider is reachable
+        Err(e @ JuliError::AiTransport { .. }) => {
+            AnalysisQueue::new(&lock_connection(&db)).enqueue(note_id, &for_date)?;
+            println!("📥 Couldn't reach the AI provider ({}), queued the note for the app to analyze later", e);
+            return Ok(());
+        }
+        Err(e) => return Err(e),
+    };
+
+    let conn = lock_connection(&db);
+    let reminders = ReminderStore::new(&conn);
+    match outcome {
+        AnalysisOutcome::Skipped => println!("Note unchanged since the last analysis"),
+        AnalysisOutcome::Applied { created, updated } => {
+            for id in created {
+                println!("+ {}", format_reminder(&reminders.get(id)?));
+            }
+            for id in updated {
+                println!("~ {}", format_reminder(&reminders.get(id)?));
+            }
+        }
+        AnalysisOutcome::Proposed { proposals } => {
+            let store = ProposalStore::new(&conn);
+            for id in proposals {
+                print_proposal(&store.get(id)?);
+            }
+            println!("Review them with: juli-cli proposals accept <id> / reject <id>");
+        }
+    }
+    Ok(())
+}
+
+// ============================================================================
+// REMINDERS
+// ============================================================================
+
+fn list_reminders(conn: &Connection, args: &ListArgs) -> Result<(), JuliError> {
+    let store = ReminderStore::new(conn).with_sort(args.sort);
+    let reminders = if args.unresolved {
+        store.unresolved()?
+    } else if args.resolved {
+        store.resolved()?
+    } else {
+        store.all()?
+    };
+
+    // Tags are stored comma separated ("work,finance"), so match whole tags
+    let wanted = args.tag.as_deref().map(|tag| tag.trim().to_lowercase());
+    for reminder in reminders.iter().filter(|r| match &wanted {
+        Some(tag) => r
+            .tags
+            .as_deref()
+            .unwrap_or("")
+            .split(',')
+            .any(|t| t.trim().to_lowercase() == *tag),
+        None => true,
+    }) {
+        println!("{}", format_reminder(reminder));
+    }
+    Ok(())
+}
+
+// One line per reminder, e.g. "  42 [ ] Call John (due 2026-10-17 15:00) #work #finance"
+fn format_reminder(reminder: &ReminderRow) -> String {
+    let due = due_label(reminder.due_date.as_deref(), reminder.due_time.as_deref(), reminder.timezone.as_deref());
+    let mut line = format!(
+        "{:>4} [{}] {}",
+        reminder.id,
+        if reminder.resolved { "x" } else { " " },
+        with_details(&reminder.text, due.as_deref(), reminder.recurrence.as_deref(), Some(&reminder.priority), reminder.tags.as_deref())
+    );
+    // "(snoozed 2x, was due 2026-10-16)"
+    if reminder.snooze_count > 0 {
+        line.push_str(&format!(" (snoozed {}x", reminder.snooze_count));
+        if let Some(original) = &reminder.original_due_date {
+            line.push_str(&format!(", was due {}", original));
+        }
+        line.push(')');
+    }
+    line
+}
+
+// "2026-10-17", "2026-10-17 15:00", or "2026-10-17 15:00 Europe/Paris" when it's not in your timezone
+fn due_label(due_date: Option<&str>, due_time: Option<&str>, timezone: Option<&str>) -> Option<String> {
+    let mut label = due_date?.to_string();
+    if let Some(time) = due_time {
+        label.push_str(&format!(" {}", time));
+        if let Some(tz) = timezone.filter(|tz| *tz != self::timezone().name()) {
+            label.push_str(&format!(" {}", tz));
+        }
+    }
+    Some(label)
+}
+
+// "Call John (due 2026-10-17 15:00) (high priority) (repeats every week on Mon) #work #finance"
+// Normal priority isn't mentioned
+fn with_details(text: &str, due: Option<&str>, recurrence: Option<&str>, priority: Option<&str>, tags: Option<&str>) -> String {
+    let mut line = text.to_string();
+    if let Some(due) = due {
+        line.push_str(&format!(" (due {})", due));
+    }
+    if let Some(priority) = priority.filter(|p| *p != "normal") {
+        line.push_str(&format!(" ({} priority)", priority));
+    }
+    if let Some(rule) = recurrence {
+        let repeats = Recurrence::parse(rule).map(|r| r.describe()).unwrap_or_else(|_| rule.to_string());
+        line.push_str(&format!(" (repeats {})", repeats));
+    }
+    for tag in tags.unwrap_or("").split(',').map(str::trim).filter(|t| !t.is_empty()) {
+        line.push_str(&format!(" #{}", tag));
+    }
+    line
+}
+
+// An UPDATE shows the reminder as it was (-) above what it would become (+)
+fn print_proposal(proposal: &ProposalRow) {
+    let target = proposal.reminder_id.map(|id| format!(" of reminder {}", id)).unwrap_or_default();
+    println!("{:>4} {}{}", proposal.id, proposal.action, target);
+    let format_draft = |draft: &ReminderDraft| {
+        let due = due_label(draft.due_date.as_deref(), draft.due_time.as_deref(), draft.timezone.as_deref());
+        with_details(&draft.text, due.as_deref(), draft.recurrence.as_deref(), draft.priority.as_deref(), draft.tags.as_deref())
+    };
+    if let Some(before) = &proposal.before {
+        println!("     - {}", format_draft(before));
+    }
+    println!("     + {}", format_draft(&proposal.proposed));
+}
+
+// ============================================================================
+// SEARCH
+// ============================================================================
+
+fn search(conn: &Connection, args: SearchArgs) -> Result<(), JuliError> {
+    let query = SearchQuery {
+        query: args.words.join(" "),
+        scope: if args.notes {
+            SearchScope::Notes
+        } else if args.reminders {
+            SearchScope::Reminders
+        } else {
+            SearchScope::All
+        },
+        from_date: args.from.map(|d| d.to_string()),
+        to_date: args.to.map(|d| d.to_string()),
+        tags: args.tag,
+        resolved: if args.unresolved {
+            Some(false)
+        } else if args.resolved {
+            Some(true)
+        } else {
+            None
+        },
+        limit: Some(args.limit),
+    };
+
+    for hit in SearchIndex::new(conn).search(&query)? {
+        let label = match hit.kind {
+            SearchHitKind::Note => format!("note {}", hit.for_date.as_deref().unwrap_or("?")),
+            SearchHitKind::Reminder => format!("reminder {}", hit.id),
+        };
+        println!("{}: {}", label, emphasize(&hit.snippet, &hit.highlights));
+    }
+    Ok(())
+}
+
+// Wrap each highlighted range in [brackets]
+// Offsets are UTF-16 units (for the web frontend), so walk the string the same way
+fn emphasize(snippet: &str, highlights: &[(usize, usize)]) -> String {
+    let mut out = String::with_capacity(snippet.len() + highlights.len() * 2);
+    let mut offset = 0;
+    for c in snippet.chars() {
+        if highlights.iter().any(|&(start, _)| start == offset) {
+            out.push('[');
+        }
+        out.push(c);
+        offset += c.len_utf16();
+        if highlights.iter().any(|&(_, end)| end == offset) {
+            out.push(']');
+        }
+    }
+    out.replace('\n', " ")
+}
+
+// ============================================================================
+// AI LOGS
+// ============================================================================
+
+fn tail_logs(conn: &Connection, lines: usize, follow: bool) -> Result<(), JuliError> {
+    // all() is newest first; print the last few oldest first, like tail
+    let logs = AiLogStore::new(conn).all()?;
+    let mut last_id = logs.first().map(|log| log.id).unwrap_or(0);
+    for log in logs.iter().take(lines).rev() {
+        print_log(log);
+    }
+
+    if !follow {
+        return Ok(());
+    }
+
+    // Poll for rows written by the desktop app (or another juli-cli) until Ctrl+C
+    loop {
+        thread::sleep(Duration::from_secs(1));
+        let logs = AiLogStore::new(conn).all()?;
+        let seen = last_id;
+        for log in logs.iter().rev().filter(|log| log.id > seen) {
+            print_log(log);
+            last_id = log.id;
+        }
+    }
+}
+
+fn print_log(log: &AiLogRow) {
+    let status = if log.success { "ok" } else { "FAILED" };
+    println!(
+        "#{} {} note {} {} - {} reminder(s)",
+        log.id, log.created_at, log.note_id, status, log.reminders_count
+    );
+    let detail = if log.success { &log.reasoning } else { &log.response };
+    if !detail.is_empty() {
+        println!("    {}", detail.replace('\n', "\n    "));
+    }
+    for rejection in &log.rejections {
+        println!("    rejected: {} - {}", rejection.reason, rejection.item);
+    }
+}
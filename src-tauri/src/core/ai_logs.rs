@@ -0,0 +1,179 @@
+// ============================================================================
+// AI INTERACTION LOGS
+// ============================================================================
+
+use std::collections::HashMap;
+
+use rusqlite::{Connection, Row};
+use serde::Serialize;
+
+use crate::ai::RequestAttempt;
+use crate::error::JuliError;
+
+#[derive(Debug, Serialize)]
+pub struct AiLogRow {
+    pub id: i64,
+    pub note_id: i64,
+    pub prompt: String,
+    pub response: String,
+    pub success: bool,
+    pub reasoning: String,
+    // How many reminders passed validation and were applied (or proposed)
+    pub reminders_count: i64,
+    pub created_at: String,
+    // The reminders from this answer that failed validation
+    pub rejections: Vec<AiLogRejection>,
+    // Every HTTP request it took, retries included (empty for entries from before retries were logged)
+    pub attempts: Vec<RequestAttempt>,
+}
+
+// In TypeScript: interface AiLogRejection { item: string; reason: string }
+#[derive(Debug, Clone, Serialize)]
+pub struct AiLogRejection {
+    // The reminder exactly as the AI sent it, as JSON
+    pub item: String,
+    pub reason: String,
+}
+
+impl AiLogRow {
+    fn from_row(row: &Row) -> rusqlite::Result<AiLogRow> {
+        Ok(AiLogRow {
+            id: row.get("id")?,
+            note_id: row.get("note_id")?,
+            prompt: row.get("prompt")?,
+            response: row.get("response")?,
+            success: row.get("success")?,
+            reasoning: row.get("reasoning")?,
+            reminders_count: row.get("reminders_count")?,
+            created_at: row.get("created_at")?,
+            rejections: Vec::new(),
+            attempts: Vec::new(),
+        })
+    }
+}
+
+// One entry to write - every analysis attempt, successful or not, gets one
+pub struct NewAiLog<'a> {
+    pub note_id: i64,
+    pub prompt: &'a str,
+    pub response: &'a str,
+    pub success: bool,
+    pub reasoning: &'a str,
+    pub reminders_count: i64,
+    pub attempts: &'a [RequestAttempt],
+}
+
+impl<'a> NewAiLog<'a> {
+    // A failed attempt: no reasoning, no reminders
+    pub fn failure(note_id: i64, prompt: &'a str, response: &'a str) -> Self {
+        NewAiLog { note_id, prompt, response, success: false, reasoning: "", reminders_count: 0, attempts: &[] }
+    }
+
+    pub fn with_attempts(mut self, attempts: &'a [RequestAttempt]) -> Self {
+        self.attempts = attempts;
+        self
+    }
+}
+
+pub struct AiLogStore<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> AiLogStore<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        AiLogStore { conn }
+    }
+
+    // Newest first
+    pub fn all(&self) -> Result<Vec<AiLogRow>, JuliError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, note_id, prompt, response, success, reasoning, reminders_count, created_at
+             FROM ai_interaction_logs ORDER BY id DESC",
+        )?;
+        let mut logs = stmt
+            .query_map([], AiLogRow::from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // One query for every log's rejections, rather than one per log
+        let mut stmt = self
+            .conn
+            .prepare("SELECT ai_log_id, item, reason FROM ai_log_rejections ORDER BY id")?;
+        let mut rejections: HashMap<i64, Vec<AiLogRejection>> = HashMap::new();
+        for row in stmt.query_map([], |row| {
+            Ok((row.get::<_, i64>(0)?, AiLogRejection { item: row.get(1)?, reason: row.get(2)? }))
+        })? {
+            let (log_id, rejection) = row?;
+            rejections.entry(log_id).or_default().push(rejection);
+        }
+        let mut stmt = self.conn.prepare(
+            "SELECT ai_log_id, attempt, status, error, duration_ms, retry_in_ms FROM ai_request_attempts ORDER BY id",
+        )?;
+        let mut attempts: HashMap<i64, Vec<RequestAttempt>> = HashMap::new();
+        for row in stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                RequestAttempt {
+                    attempt: row.get(1)?,
+                    status: row.get(2)?,
+                    error: row.get(3)?,
+                    duration_ms: row.get(4)?,
+                    retry_in_ms: row.get(5)?,
+                },
+            ))
+        })? {
+            let (log_id, attempt) = row?;
+            attempts.entry(log_id).or_default().push(attempt);
+        }
+
+        for log in &mut logs {
+            log.rejections = rejections.remove(&log.id).unwrap_or_default();
+            log.attempts = attempts.remove(&log.id).unwrap_or_default();
+        }
+
+        Ok(logs)
+    }
+
+    pub fn insert(&self, log: &NewAiLog) -> Result<i64, JuliError> {
+        self.conn.execute(
+            "INSERT INTO ai_interaction_logs (note_id, prompt, response, success, reasoning, reminders_count) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            (log.note_id, log.prompt, log.response, log.success, log.reasoning, log.reminders_count),
+        )?;
+        let log_id = self.conn.last_insert_rowid();
+
+        for attempt in log.attempts {
+            self.conn.execute(
+                "INSERT INTO ai_request_attempts (ai_log_id, attempt, status, error, duration_ms, retry_in_ms)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                (
+                    log_id,
+                    attempt.attempt,
+                    attempt.status,
+                    &attempt.error,
+                    attempt.duration_ms as i64,
+                    attempt.retry_in_ms.map(|ms| ms as i64),
+                ),
+            )?;
+        }
+
+        Ok(log_id)
+    }
+
+    // Remember a reminder from the log's answer that we refused to write
+    pub fn insert_rejection(&self, log_id: i64, item: &str, reason: &str) -> Result<(), JuliError> {
+        self.conn.execute(
+            "INSERT INTO ai_log_rejections (ai_log_id, item, reason) VALUES (?1, ?2, ?3)",
+            (log_id, item, reason),
+        )?;
+        Ok(())
+    }
+
+    pub fn delete(&self, log_id: i64) -> Result<(), JuliError> {
+        self.conn.execute("DELETE FROM ai_interaction_logs WHERE id = ?1", (log_id,))?;
+        Ok(())
+    }
+
+    pub fn delete_all(&self) -> Result<(), JuliError> {
+        self.conn.execute("DELETE FROM ai_interaction_logs", ())?;
+        Ok(())
+    }
+}
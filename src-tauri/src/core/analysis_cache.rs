@@ -0,0 +1,68 @@
+// ============================================================================
+// ANALYSIS CACHE
+// ============================================================================
+
+// Remembers, per note, a hash of the text that was last analyzed - separately for
+// every prompt version and provider/model - so an analysis is skipped only when
+// that exact text has already been through the current prompt and model
+//
+// Changing the model in config.json, or shipping a new prompt, gives a new key,
+// so every note is analyzed again the next time it's saved
+
+use rusqlite::{Connection, OptionalExtension};
+use sha2::{Digest, Sha256};
+
+use crate::error::JuliError;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheKey {
+    // A fingerprint of the prompt template (see analyzer::prompt_version)
+    pub prompt_version: String,
+    pub provider: String,
+    pub model: String,
+}
+
+// SHA-256 of the note text, as hex
+pub fn content_hash(text: &str) -> String {
+    Sha256::digest(text.as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+pub struct AnalysisCache<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> AnalysisCache<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        AnalysisCache { conn }
+    }
+
+    // The hash of the text this note was last analyzed with under this key,
+    // None if the note has never been analyzed with this prompt and model
+    pub fn lookup(&self, note_id: i64, key: &CacheKey) -> Result<Option<String>, JuliError> {
+        let hash = self
+            .conn
+            .query_row(
+                "SELECT content_hash FROM analysis_cache
+                 WHERE note_id = ?1 AND prompt_version = ?2 AND provider = ?3 AND model = ?4",
+                (note_id, &key.prompt_version, &key.provider, &key.model),
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(hash)
+    }
+
+    pub fn store(&self, note_id: i64, key: &CacheKey, content_hash: &str) -> Result<(), JuliError> {
+        self.conn.execute(
+            "INSERT INTO analysis_cache (note_id, prompt_version, provider, model, content_hash)
+             VALUES (?1, ?2, ?3, ?4, ?5)
+             ON CONFLICT(note_id, prompt_version, provider, model)
+             DO UPDATE SET content_hash = excluded.content_hash,
+                           analyzed_at = strftime('%Y-%m-%dT%H:%M:%SZ', 'now')",
+            (note_id, &key.prompt_version, &key.provider, &key.model, content_hash),
+        )?;
+        Ok(())
+    }
+}
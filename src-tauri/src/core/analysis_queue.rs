@@ -0,0 +1,269 @@
+// ============================================================================
+// PENDING ANALYSES (OFFLINE QUEUE)
+// ============================================================================
+
+// Saving a note never waits for the AI. The note is written, a job is added to
+// pending_analyses, and a background worker works through the queue:
+// - a job that succeeds is removed
+// - a job that fails because the provider can't be reached (offline, timeouts, 5xx,
+//   no API key yet) stays queued and is tried again later, with a growing wait
+// - a job that fails for any other reason (the AI's answer couldn't be used, the
+//   note was deleted) is removed - the AI log already has the details
+// As soon as one job gets through, everything else that was waiting is tried again
+// right away, so a backlog of notes written offline drains once the network is back
+//
+// Usage:
+// AnalysisQueue::new(&conn).enqueue(note_id, &today_note_date())?;
+// while let Some(result) = run_next_analysis(&db, || load_provider()).await? { ... }
+//
+// A job's id is the pending_analyses row id. Saving a note that is already queued
+// keeps its job (and id), so the UI can follow one job per note
+
+use rusqlite::{Connection, OptionalExtension, Row};
+use serde::Serialize;
+
+use super::{AiLogStore, AnalysisOutcome, Analyzer, AppConfig, Database, NewAiLog, NoteStore};
+use crate::ai::AiProvider;
+use crate::error::JuliError;
+
+// The first retry waits this long; every further failure doubles it, up to MAX_RETRY_DELAY_SECS
+const FIRST_RETRY_DELAY_SECS: i64 = 30;
+const MAX_RETRY_DELAY_SECS: i64 = 30 * 60;
+
+// In TypeScript:
+// interface PendingAnalysis { id: number; note_id: number; saved_on: string; attempts: number;
+//   last_error: string | null; last_error_kind: string | null; next_attempt_at: string; created_at: string }
+#[derive(Debug, Clone, Serialize)]
+pub struct PendingAnalysis {
+    pub id: i64,
+    pub note_id: i64,
+    // The day the note was saved, used as "today" in the prompt
+    pub saved_on: String,
+    // Failed tries so far
+    pub attempts: i64,
+    pub last_error: Option<String>,
+    // JuliError::kind() of last_error
+    pub last_error_kind: Option<String>,
+    pub next_attempt_at: String,
+    pub created_at: String,
+    // Bumped on every save of the note while the job waits, so a job that finishes
+    // isn't removed if the note was saved again in the meantime
+    #[serde(skip)]
+    saves: i64,
+}
+
+const PENDING_COLUMNS: &str =
+    "id, note_id, saved_on, attempts, last_error, last_error_kind, next_attempt_at, created_at, saves";
+
+impl PendingAnalysis {
+    fn from_row(row: &Row) -> rusqlite::Result<PendingAnalysis> {
+        Ok(PendingAnalysis {
+            id: row.get("id")?,
+            note_id: row.get("note_id")?,
+            saved_on: row.get("saved_on")?,
+            attempts: row.get("attempts")?,
+            last_error: row.get("last_error")?,
+            last_error_kind: row.get("last_error_kind")?,
+            next_attempt_at: row.get("next_attempt_at")?,
+            created_at: row.get("created_at")?,
+            saves: row.get("saves")?,
+        })
+    }
+}
+
+pub struct AnalysisQueue<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> AnalysisQueue<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        AnalysisQueue { conn }
+    }
+
+    // Queue the note for analysis, due right away; returns the job id
+    // A note that is already waiting keeps its job, which starts over with the new save
+    pub fn enqueue(&self, note_id: i64, saved_on: &str) -> Result<i64, JuliError> {
+        let job_id = self.conn.query_row(
+            "INSERT INTO pending_analyses (note_id, saved_on) VALUES (?1, ?2)
+             ON CONFLICT(note_id) DO UPDATE SET
+               saved_on = excluded.saved_on,
+               attempts = 0,
+               next_attempt_at = strftime('%Y-%m-%dT%H:%M:%SZ', 'now'),
+               saves = saves + 1
+             RETURNING id",
+            (note_id, saved_on),
+            |row| row.get(0),
+        )?;
+        Ok(job_id)
+    }
+
+    // Every waiting job, the next one to run first
+    pub fn all(&self) -> Result<Vec<PendingAnalysis>, JuliError> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} FROM pending_analyses ORDER BY next_attempt_at, id",
+            PENDING_COLUMNS
+        ))?;
+        let jobs = stmt
+            .query_map([], PendingAnalysis::from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(jobs)
+    }
+
+    // The job to run now, if any is due
+    pub fn next_due(&self) -> Result<Option<PendingAnalysis>, JuliError> {
+        let job = self
+            .conn
+            .query_row(
+                &format!(
+                    "SELECT {} FROM pending_analyses WHERE next_attempt_at <= strftime('%Y-%m-%dT%H:%M:%SZ', 'now')
+                     ORDER BY next_attempt_at, id LIMIT 1",
+                    PENDING_COLUMNS
+                ),
+                [],
+                PendingAnalysis::from_row,
+            )
+            .optional()?;
+        Ok(job)
+    }
+
+    // How many seconds until the next job is due (0 if one is due now), None when the queue is empty
+    pub fn seconds_until_next(&self) -> Result<Option<i64>, JuliError> {
+        let seconds = self.conn.query_row(
+            "SELECT MAX(0, CAST(strftime('%s', MIN(next_attempt_at)) AS INTEGER) - CAST(strftime('%s', 'now') AS INTEGER))
+             FROM pending_analyses",
+            [],
+            |row| row.get(0),
+        )?;
+        Ok(seconds)
+    }
+
+    // The job ran; remove it unless the note was saved again while it was running
+    pub fn complete(&self, job: &PendingAnalysis) -> Result<(), JuliError> {
+        self.conn.execute(
+            "DELETE FROM pending_analyses WHERE id = ?1 AND saves = ?2",
+            (job.id, job.saves),
+        )?;
+        Ok(())
+    }
+
+    // The job failed but may work later: record why and push it back
+    // Returns when it will be tried again (None if a newer save already made it due)
+    pub fn postpone(&self, job: &PendingAnalysis, error: &JuliError) -> Result<Option<String>, JuliError> {
+        let delay = retry_delay_secs(job.attempts + 1);
+        let next_attempt_at = self
+            .conn
+            .query_row(
+                "UPDATE pending_analyses
+                 SET attempts = attempts + 1, last_error = ?1, last_error_kind = ?2,
+                     next_attempt_at = strftime('%Y-%m-%dT%H:%M:%SZ', 'now', ?3)
+                 WHERE id = ?4 AND saves = ?5
+                 RETURNING next_attempt_at",
+                (error.to_string(), error.kind(), format!("+{} seconds", delay), job.id, job.saves),
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(next_attempt_at)
+    }
+
+    // The provider is reachable again: make every waiting job due now
+    pub fn retry_all_now(&self) -> Result<(), JuliError> {
+        self.conn.execute(
+            "UPDATE pending_analyses SET next_attempt_at = strftime('%Y-%m-%dT%H:%M:%SZ', 'now')
+             WHERE next_attempt_at > strftime('%Y-%m-%dT%H:%M:%SZ', 'now')",
+            [],
+        )?;
+        Ok(())
+    }
+}
+
+// 30s, 1m, 2m, 4m, ... up to 30 minutes
+fn retry_delay_secs(attempts: i64) -> i64 {
+    let doublings = (attempts - 1).clamp(0, 16) as u32;
+    (FIRST_RETRY_DELAY_SECS << doublings).min(MAX_RETRY_DELAY_SECS)
+}
+
+// Failures that say nothing about the note itself: another try may well work
+fn worth_retrying(error: &JuliError) -> bool {
+    matches!(error, JuliError::AiTransport { .. } | JuliError::Config { .. } | JuliError::Database { .. })
+}
+
+// ============================================================================
+// RUNNING THE QUEUE
+// ============================================================================
+
+// What happened to the job run_next_analysis picked up
+#[derive(Debug)]
+pub enum AnalysisJobResult {
+    Completed { job: PendingAnalysis, outcome: AnalysisOutcome },
+    // Still queued, tried again at next_attempt_at (None: right away, the note was saved again)
+    Postponed { job: PendingAnalysis, error: JuliError, next_attempt_at: Option<String> },
+    // Removed from the queue, another try wouldn't help
+    Failed { job: PendingAnalysis, error: JuliError },
+}
+
+// Run the next due job, if there is one
+// Only fails if the queue itself can't be read or updated
+pub async fn run_next_analysis<F>(db: &impl Database, load_provider: F) -> Result<Option<AnalysisJobResult>, JuliError>
+where
+    F: FnOnce() -> Result<(Box<dyn AiProvider>, AppConfig), JuliError>,
+{
+    let job = db.with_reader(|conn| AnalysisQueue::new(conn).next_due())?;
+    match job {
+        Some(job) => run_analysis_job(db, job, load_provider).await.map(Some),
+        None => Ok(None),
+    }
+}
+
+// Run one job from the queue and update the queue with the result
+// load_provider is called here rather than once at startup, so config.json is read
+// fresh for each job - an API key added while offline is picked up by the next retry,
+// and so is a change of timezone
+// Only fails if the queue itself can't be updated
+pub async fn run_analysis_job<F>(db: &impl Database, job: PendingAnalysis, load_provider: F) -> Result<AnalysisJobResult, JuliError>
+where
+    F: FnOnce() -> Result<(Box<dyn AiProvider>, AppConfig), JuliError>,
+{
+    // The note is read when the job runs, so it's analyzed as it is now, not as it was when queued
+    let note = db.with_reader(|conn| NoteStore::new(conn).get(job.note_id));
+
+    let result = match note {
+        Ok(note) => match load_provider() {
+            Ok((provider, config)) => {
+                Analyzer::new(provider.as_ref())
+                    .with_review(config.ai.review_changes)
+                    .with_timezone(config.timezone())
+                    .analyze(db, job.note_id, &note.text, &job.saved_on)
+                    .await
+            }
+            Err(e) => {
+                // A missing API key or broken config.json is logged like any other failed AI call,
+                // but only once per job rather than on every retry
+                if job.attempts == 0 {
+                    let message = format!("AI API call failed: {}", e);
+                    db.with_writer(|conn| AiLogStore::new(conn).insert(&NewAiLog::failure(job.note_id, "", &message)))?;
+                }
+                Err(e)
+            }
+        },
+        Err(e) => Err(e),
+    };
+
+    db.with_writer(|conn| {
+        let queue = AnalysisQueue::new(conn);
+        Ok(match result {
+            Ok(outcome) => {
+                queue.complete(&job)?;
+                queue.retry_all_now()?;
+                AnalysisJobResult::Completed { job, outcome }
+            }
+            Err(error) if worth_retrying(&error) => {
+                let next_attempt_at = queue.postpone(&job, &error)?;
+                AnalysisJobResult::Postponed { job, error, next_attempt_at }
+            }
+            Err(error) => {
+                queue.complete(&job)?;
+                AnalysisJobResult::Failed { job, error }
+            }
+        })
+    })
+}
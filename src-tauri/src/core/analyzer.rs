@@ -0,0 +1,638 @@
+// ============================================================================
+// AI ANALYSIS
+// ============================================================================
+
+// Turns a note into reminders: build the prompt, ask the provider, apply the answer
+// The caller is responsible for making sure only one analysis runs at a time
+// (the desktop app runs every analysis on its one queue worker, see core/analysis_queue.rs)
+
+use std::sync::OnceLock;
+
+use chrono::Utc;
+use chrono_tz::Tz;
+use rusqlite::{Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+use super::analysis_cache::{content_hash, AnalysisCache, CacheKey};
+use super::note_diff::{NoteChanges, NoteSection};
+use super::proposals::{NewProposal, ProposalStore};
+use super::sources::{NewReminderSource, ReminderSourceStore};
+use super::time::system_timezone;
+use super::validation::{validate_reminders, ReminderAction, ValidReminder};
+use super::{AiLogStore, Database, NewAiLog, ReminderDraft, ReminderRow, ReminderStore};
+use crate::ai::{self, AiProvider, AttemptLog, CompletionOptions, OutputSchema, RequestAttempt};
+use crate::error::JuliError;
+
+// What the AI returns when analyzing a note
+// We use this to parse the AI's JSON response
+// Nothing here is trusted yet - see core/validation.rs. text and action default to ""
+// so one malformed reminder is rejected on its own instead of failing the whole answer
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AiExtractedReminder {
+    #[serde(default)]
+    pub text: String,                    // The reminder text (without due date info)
+    #[serde(default)]
+    pub action: String,                  // "CREATE" or "UPDATE"
+    pub update_id: Option<i64>,
+    pub tags: Option<String>,            // Comma-separated tags
+    pub due_date: Option<String>,        // "2025-12-20" or null
+    #[serde(default)]
+    pub due_time: Option<String>,        // "15:00" (24-hour, the user's timezone) or null
+    #[serde(default)]
+    pub recurrence: Option<String>,      // "FREQ=MONTHLY;BYMONTHDAY=1" or null (see core/recurrence.rs)
+    #[serde(default)]
+    pub priority: Option<String>,        // "low", "normal", "high", "urgent" or null
+    // Which [CHANGED n] section of an edited note this came from (see NoteChanges)
+    #[serde(default)]
+    pub span: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AiAnalysisResponse {
+    pub reminders: Vec<AiExtractedReminder>,
+    pub reasoning: String,
+}
+
+// What a finished analysis did
+#[derive(Debug, PartialEq, Eq)]
+pub enum AnalysisOutcome {
+    // Nothing was added or edited since the note was last analyzed, so the AI wasn't called
+    Skipped,
+    // The AI answered and its reminders were applied
+    Applied { created: Vec<i64>, updated: Vec<i64> },
+    // Review mode: the AI answered and its changes are waiting for the user (see core/proposals.rs)
+    Proposed { proposals: Vec<i64> },
+}
+
+pub struct Analyzer<'a> {
+    provider: &'a dyn AiProvider,
+    // Store the AI's changes as proposals instead of applying them
+    review: bool,
+    // The user's timezone: the prompt's local time and what due times mean (system's if None)
+    timezone: Option<Tz>,
+}
+
+impl<'a> Analyzer<'a> {
+    pub fn new(provider: &'a dyn AiProvider) -> Self {
+        Analyzer { provider, review: false, timezone: None }
+    }
+
+    // Usage: Analyzer::new(&provider).with_timezone(config.timezone())
+    pub fn with_timezone(mut self, timezone: Tz) -> Self {
+        self.timezone = Some(timezone);
+        self
+    }
+
+    // Turned on by "review_changes" in the ai section of config.json
+    pub fn with_review(mut self, review: bool) -> Self {
+        self.review = review;
+        self
+    }
+
+    // Analyses are only reused for the same prompt, provider and model
+    pub fn cache_key(&self) -> CacheKey {
+        CacheKey {
+            prompt_version: prompt_version().to_string(),
+            provider: self.provider.name().to_string(),
+            model: self.provider.model().to_string(),
+        }
+    }
+
+    // Send the note to the AI and apply the reminders it extracts
+    //
+    // Takes the Database rather than a locked Connection because no connection may be
+    // held while we wait for the AI - other commands need the database in the meantime
+    pub async fn analyze(
+        &self,
+        db: &impl Database,
+        note_id: i64,
+        note_text: &str,
+        current_date: &str,
+    ) -> Result<AnalysisOutcome, JuliError> {
+        let key = self.cache_key();
+        let timezone = self.timezone.unwrap_or_else(system_timezone);
+        let clock = local_clock(timezone);
+        let (prompt, changes) = match db.with_writer(|conn| prepare(conn, &key, note_id, note_text, current_date, &clock))? {
+            Some(prepared) => prepared,
+            None => {
+                println!("⏭️  Skipping AI analysis - nothing new in the note since it was last analyzed");
+                return Ok(AnalysisOutcome::Skipped);
+            }
+        };
+
+        // schema makes Anthropic answer through a tool call with exactly this JSON;
+        // json: true asks providers with a JSON mode (OpenAI, Ollama) to return nothing but JSON
+        // Whatever comes back, extract_json finds the object even if there's prose around it
+        // attempts collects every HTTP try (retries included) for the AI log
+        let attempts = AttemptLog::default();
+        let options = CompletionOptions {
+            max_tokens: 1024,
+            json: true,
+            schema: Some(analysis_schema()),
+            attempts: attempts.clone(),
+        };
+        let api_result = self
+            .provider
+            .complete(&prompt, &options)
+            .await
+            .map(|completion| ai::extract_json(&completion.text).to_string());
+
+        let attempts = attempts.attempts();
+        let request = AnalysisRequest {
+            key: &key,
+            note_id,
+            note_text,
+            changes: &changes,
+            prompt: &prompt,
+            attempts: &attempts,
+            review: self.review,
+            timezone,
+        };
+        db.with_writer(|conn| apply(conn, &request, api_result))
+    }
+}
+
+// Everything apply() needs to know about the analysis the AI just answered
+struct AnalysisRequest<'r> {
+    key: &'r CacheKey,
+    note_id: i64,
+    note_text: &'r str,
+    changes: &'r NoteChanges,
+    prompt: &'r str,
+    // The HTTP requests it took to get the answer, stored with the AI log entry
+    attempts: &'r [RequestAttempt],
+    // Store the changes as proposals instead of applying them
+    review: bool,
+    // What the answer's due times mean
+    timezone: Tz,
+}
+
+// Work out what changed and build the prompt for it
+// Returns None when there is nothing new to analyze
+fn prepare(
+    conn: &Connection,
+    key: &CacheKey,
+    note_id: i64,
+    note_text: &str,
+    current_date: &str,
+    clock: &str,
+) -> Result<Option<(String, NoteChanges)>, JuliError> {
+    // If this exact text was already analyzed with this prompt and model, skip AI analysis
+    let analyzed_text: Option<String> = match AnalysisCache::new(conn).lookup(note_id, key)? {
+        Some(hash) if hash == content_hash(note_text) => return Ok(None),
+        // Analyzed before with this prompt and model: only look at what changed since
+        // The baseline is whatever was analyzed last, so it only counts when that was this
+        // prompt and model too - after another one, the changes it saw were never shown to this one
+        Some(hash) => conn
+            .query_row("SELECT analyzed_text FROM note_analysis_state WHERE note_id = ?1", [note_id], |row| row.get(0))
+            .optional()?
+            .filter(|text: &String| content_hash(text) == hash),
+        // Never analyzed with this prompt and model (or at all), or the baseline is another's: the whole note counts
+        None => None,
+    };
+
+    let changes = NoteChanges::between(analyzed_text.as_deref(), note_text);
+    if changes.is_empty() {
+        // Only deletions: move the baseline forward so they aren't compared against again
+        mark_analyzed(conn, key, note_id, note_text)?;
+        return Ok(None);
+    }
+
+    let reminders = ReminderStore::new(conn).all()?;
+    Ok(Some((build_analysis_prompt(&changes, current_date, clock, &reminders), changes)))
+}
+
+// "The user's timezone is America/New_York and their local time is 2026-10-16 15:04 (UTC-04:00)."
+// It's a separate sentence so prompt_version() can leave it out: it changes every minute
+fn local_clock(timezone: Tz) -> String {
+    let now = Utc::now().with_timezone(&timezone);
+    format!(
+        "The user's timezone is {} and their local time is {} (UTC{}).",
+        timezone.name(),
+        now.format("%Y-%m-%d %H:%M"),
+        now.format("%:z")
+    )
+}
+
+// Remember the text as analyzed: the baseline for the next diff, and the cache entry
+fn mark_analyzed(conn: &Connection, key: &CacheKey, note_id: i64, note_text: &str) -> Result<(), JuliError> {
+    conn.execute(
+        "INSERT INTO note_analysis_state (note_id, analyzed_text) VALUES (?1, ?2)
+         ON CONFLICT(note_id) DO UPDATE SET
+           analyzed_text = excluded.analyzed_text,
+           analyzed_at = strftime('%Y-%m-%dT%H:%M:%SZ', 'now')",
+        (note_id, note_text),
+    )?;
+    AnalysisCache::new(conn).store(note_id, key, &content_hash(note_text))
+}
+
+// Write the AI's answer (or its failure) to the database
+fn apply(
+    conn: &Connection,
+    request: &AnalysisRequest,
+    api_result: Result<String, JuliError>,
+) -> Result<AnalysisOutcome, JuliError> {
+    let (note_id, prompt) = (request.note_id, request.prompt);
+    let logs = AiLogStore::new(conn);
+
+    let response = match api_result {
+        Ok(response) => response,
+        Err(e) => {
+            // AI API call failed
+            // The log gets a readable message, but the caller gets the original error
+            // so the frontend still sees what kind of failure it was
+            let error_msg = format!("AI API call failed: {}", e);
+            logs.insert(&NewAiLog::failure(note_id, prompt, &error_msg).with_attempts(request.attempts))?;
+            return Err(e);
+        }
+    };
+
+    // Try to parse the AI response
+    let analysis = match serde_json::from_str::<AiAnalysisResponse>(&response) {
+        Ok(analysis) => analysis,
+        Err(e) => {
+            let error_msg = format!("Failed to parse AI response as JSON: {}. Response was: {}", e, response);
+            logs.insert(&NewAiLog::failure(note_id, prompt, &error_msg).with_attempts(request.attempts))?;
+            return Err(JuliError::ai_parse(format!("Failed to parse AI response as JSON: {}", e)).with_details(response));
+        }
+    };
+
+    // The log entry, reminders, sources and cache entry for this answer are written in
+    // one transaction. If anything fails halfway nothing is kept - not even the cache
+    // entry - so the next save analyzes the note again without creating duplicates
+    let written = conn.unchecked_transaction().map_err(JuliError::from).and_then(|tx| {
+        let outcome = write_analysis(&tx, request, &response, &analysis)?;
+        tx.commit()?;
+        Ok(outcome)
+    });
+
+    written.or_else(|e| {
+        // The transaction was rolled back when tx was dropped, so this is the only trace of the attempt
+        let error_msg = format!("Failed to save AI response: {}. Response was: {}", e, response);
+        logs.insert(&NewAiLog::failure(note_id, prompt, &error_msg).with_attempts(request.attempts))?;
+        Err(e)
+    })
+}
+
+// Everything a successful answer changes
+// In review mode the reminders are left alone and the changes become proposals
+fn write_analysis(
+    conn: &Connection,
+    request: &AnalysisRequest,
+    response: &str,
+    analysis: &AiAnalysisResponse,
+) -> Result<AnalysisOutcome, JuliError> {
+    let AnalysisRequest { key, note_id, note_text, changes, prompt, attempts, review, timezone } = *request;
+    let logs = AiLogStore::new(conn);
+
+    // Check every reminder before anything is written
+    let (valid, rejected) = validate_reminders(&analysis.reminders, &ReminderStore::new(conn))?;
+
+    // Log successful AI interaction
+    // (first, so every reminder source and rejection can point at this log entry)
+    let log_id = logs.insert(&NewAiLog {
+        note_id,
+        prompt,
+        response,
+        success: true,
+        reasoning: &analysis.reasoning,
+        reminders_count: valid.len() as i64,
+        attempts,
+    })?;
+
+    // The ones we refused are kept with the log, so it's clear why they never showed up
+    for rejection in &rejected {
+        println!("⚠️ Rejected a reminder from the AI: {}", rejection.reason);
+        let item = serde_json::to_string(rejection.item).unwrap_or_default();
+        logs.insert_rejection(log_id, &item, &rejection.reason)?;
+    }
+
+    let changed: Vec<&NoteSection> = changes.changed_sections().collect();
+
+    if review {
+        let proposals = propose(conn, note_id, log_id, &changed, &valid, timezone)?;
+        mark_analyzed(conn, key, note_id, note_text)?;
+        return Ok(AnalysisOutcome::Proposed { proposals });
+    }
+
+    // Success! Insert reminders
+    let reminders = ReminderStore::new(conn).with_timezone(timezone);
+    let sources = ReminderSourceStore::new(conn);
+    let mut created = Vec::new();
+    let mut updated = Vec::new();
+
+    for reminder in &valid {
+        let reminder_id = match reminder.action {
+            ReminderAction::Create => {
+                let id = reminders.create(note_id, &reminder.draft())?;
+                created.push(id);
+                id
+            }
+            ReminderAction::Update { reminder_id } => {
+                reminders.update(reminder_id, &update_draft(reminder, &reminders.get(reminder_id)?))?;
+                updated.push(reminder_id);
+                reminder_id
+            }
+        };
+
+        // Remember which lines of the note this reminder came from
+        for section in source_sections(&changed, reminder.span) {
+            sources.insert(&NewReminderSource {
+                reminder_id,
+                note_id,
+                ai_log_id: Some(log_id),
+                action: reminder.action.name(),
+                start_line: section.start_line,
+                end_line: section.end_line,
+                excerpt: &section.text,
+            })?;
+        }
+    }
+
+    // The next analysis of this note only looks at what changes after this
+    mark_analyzed(conn, key, note_id, note_text)?;
+
+    Ok(AnalysisOutcome::Applied { created, updated })
+}
+
+// Store every valid CREATE and UPDATE as a pending proposal, with a snapshot of what an UPDATE would replace
+fn propose(
+    conn: &Connection,
+    note_id: i64,
+    log_id: i64,
+    changed: &[&NoteSection],
+    valid: &[ValidReminder],
+    timezone: Tz,
+) -> Result<Vec<i64>, JuliError> {
+    let reminders = ReminderStore::new(conn).with_timezone(timezone);
+    let proposals = ProposalStore::new(conn);
+    let mut ids = Vec::new();
+
+    for reminder in valid {
+        let (reminder_id, proposed, before) = match reminder.action {
+            ReminderAction::Create => (None, reminder.draft(), None),
+            ReminderAction::Update { reminder_id } => {
+                let existing = reminders.get(reminder_id)?;
+                let proposed = update_draft(reminder, &existing);
+                let before = ReminderDraft {
+                    text: existing.text,
+                    tags: existing.tags,
+                    due_date: existing.due_date,
+                    due_time: existing.due_time,
+                    timezone: existing.timezone,
+                    recurrence: existing.recurrence,
+                    priority: Some(existing.priority),
+                };
+                // Nothing to review if it wouldn't change anything
+                // (the AI's due times are in the user's timezone, so an unchanged time keeps the old one)
+                if before == (ReminderDraft { timezone: before.timezone.clone(), ..proposed.clone() }) {
+                    continue;
+                }
+                (Some(reminder_id), proposed, Some(before))
+            }
+        };
+
+        ids.push(proposals.insert(&NewProposal {
+            note_id,
+            ai_log_id: Some(log_id),
+            action: reminder.action.name(),
+            reminder_id,
+            proposed,
+            before,
+            sources: source_sections(changed, reminder.span),
+        })?);
+    }
+
+    Ok(ids)
+}
+
+// What an UPDATE from the AI writes: its draft, but the priority stays when the user picked
+// it (see ReminderStore::set_priority) or when the AI didn't give one
+fn update_draft(reminder: &ValidReminder, existing: &ReminderRow) -> ReminderDraft {
+    let mut draft = reminder.draft();
+    if existing.priority_set_by_user || draft.priority.is_none() {
+        draft.priority = Some(existing.priority.clone());
+    }
+    draft
+}
+
+// The section(s) a reminder came from, going by the "span" the model reported
+// Without a usable span we can only say it came from one of the changed sections
+fn source_sections<'s>(changed: &[&'s NoteSection], span: Option<usize>) -> Vec<&'s NoteSection> {
+    match span {
+        Some(n) if n >= 1 && n <= changed.len() => vec![changed[n - 1]],
+        _ => changed.to_vec(),
+    }
+}
+
+// The end of the prompt: either the whole note, or only the parts that changed
+fn note_prompt_section(changes: &NoteChanges) -> String {
+    if changes.whole_note {
+        let text = changes.sections.first().map(|s| s.text.as_str()).unwrap_or("");
+        return format!("Note to analyze:\n{}", text);
+    }
+
+    let mut changed_count = 0;
+    let sections = changes
+        .sections
+        .iter()
+        .map(|section| {
+            let label = if section.changed {
+                changed_count += 1;
+                format!("[CHANGED {}, lines {}-{}]", changed_count, section.start_line, section.end_line)
+            } else {
+                format!("[CONTEXT, lines {}-{}]", section.start_line, section.end_line)
+            };
+            format!("{}\n{}", label, section.text)
+        })
+        .collect::<Vec<String>>()
+        .join("\n\n");
+
+    format!(
+        r#"This note was edited since it was last analyzed. Only the [CHANGED n] sections below are new or edited - extract reminders ONLY from them.
+[CONTEXT] sections are unchanged text, included so you understand what the changes refer to. Reminders from them already exist, so never create or update a reminder because of context alone.
+Add "span": n to every reminder in your response, where n is the number of the [CHANGED n] section it came from.
+
+Changed parts of the note:
+{}"#,
+        sections
+    )
+}
+
+// The answer we expect, as a JSON schema - the same shape as AiAnalysisResponse
+// Anthropic is forced to call a tool with this input (see ai::OutputSchema)
+fn analysis_schema() -> OutputSchema {
+    OutputSchema {
+        name: "save_reminders",
+        description: "Save the reminders extracted from the note, and why",
+        schema: serde_json::json!({
+            "type": "object",
+            "properties": {
+                "reminders": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "text": {"type": "string", "description": "What needs to be done, without the due date or tags"},
+                            "action": {"type": "string", "enum": ["CREATE", "UPDATE"]},
+                            "update_id": {"type": ["integer", "null"], "description": "For UPDATE: the id of the existing reminder"},
+                            "tags": {"type": ["string", "null"], "description": "Comma-separated, like \"work,urgent\""},
+                            "due_date": {"type": ["string", "null"], "description": "YYYY-MM-DD"},
+                            "due_time": {"type": ["string", "null"], "description": "HH:MM, 24-hour, in the user's timezone, if a time of day is given"},
+                            "recurrence": {"type": ["string", "null"], "description": "An RRULE like \"FREQ=WEEKLY;BYDAY=MO\" if it repeats"},
+                            "priority": {"type": ["string", "null"], "enum": ["low", "normal", "high", "urgent", null], "description": "How pressing it is"},
+                            "span": {"type": "integer", "description": "For edited notes: the n of the [CHANGED n] section it came from"}
+                        },
+                        "required": ["text", "action"]
+                    }
+                },
+                "reasoning": {"type": "string"}
+            },
+            "required": ["reminders", "reasoning"]
+        }),
+    }
+}
+
+// A short fingerprint of the prompt template, used in the analysis cache key
+// It's a hash of the prompt built for fixed sample inputs (plus the answer schema),
+// so any edit to the template, to how changed sections are presented or to the
+// schema changes it automatically
+pub fn prompt_version() -> &'static str {
+    static VERSION: OnceLock<String> = OnceLock::new();
+    VERSION.get_or_init(|| {
+        let whole = NoteChanges::between(None, "sample");
+        let edited = NoteChanges::between(Some("sample"), "sample\n\nedited");
+        let template = format!(
+            "{}{}{}",
+            build_analysis_prompt(&whole, "", "", &[]),
+            build_analysis_prompt(&edited, "", "", &[]),
+            analysis_schema().schema
+        );
+        content_hash(&template)[..16].to_string()
+    })
+}
+
+// This is the AI prompt we'll send to analyze notes
+// `clock` is local_clock()'s sentence about the user's timezone and time
+fn build_analysis_prompt(changes: &NoteChanges, current_date: &str, clock: &str, reminders: &[ReminderRow]) -> String {
+    let reminders_text = reminders.iter().map(|reminder| {
+        let due_info = match (&reminder.due_date, &reminder.due_time, &reminder.timezone) {
+            (Some(d), Some(t), Some(tz)) => format!(", due: {} {} {}", d, t, tz),
+            (Some(d), Some(t), None) => format!(", due: {} {}", d, t),
+            (Some(d), None, _) => format!(", due: {}", d),
+            (None, _, _) => String::new(),
+        };
+        let repeat_info = reminder.recurrence.as_deref().map(|r| format!(", repeats: {}", r)).unwrap_or_default();
+        let priority_info = match (reminder.priority.as_str(), reminder.priority_set_by_user) {
+            (priority, true) => format!(", priority: {} (set by the user)", priority),
+            ("normal", false) => String::new(),
+            (priority, false) => format!(", priority: {}", priority),
+        };
+        let manual_info = if reminder.source == "manual" { ", added by the user" } else { "" };
+        format!("{}: {} (tags: {}{}{}{}{})", reminder.id, reminder.text, reminder.tags.as_deref().unwrap_or("none"), due_info, repeat_info, priority_info, manual_info)
+    }).collect::<Vec<String>>().join("\n");
+    let reminders_prompt = if reminders_text.is_empty() {
+        "".to_string()
+    } else {
+        format!("These are the existing reminders. Before creating a new reminder, check if it matches an existing one: \n{}", reminders_text)
+    };
+
+    format!(r#"You are analyzing a note to extract actionable reminders. Today's date is {}. {}
+
+Analyze this note and extract any tasks, reminders, or action items. For each one, determine:
+1. The reminder text (what needs to be done) - DO NOT include the due date in the text
+2. The due date as a separate field (if mentioned or implied) in YYYY-MM-DD format
+3. Tags (if the user ends a sentence with --[comma separated list])
+4. The recurrence, if the task repeats, as an RRULE
+5. The due time, if a time of day is mentioned, as HH:MM (24-hour)
+6. The priority: "low", "normal", "high" or "urgent"
+
+
+Common patterns to recognize for due_date:
+- "before eow" / "by end of week" = Friday of current week
+- "before eom" / "by end of month" = last day of month
+- "tomorrow" = next day
+- "today" / "eod" = same day
+- "next week" = 7 days from now
+- Specific dates like "Dec 20" or "12/20"
+- No deadline mentioned = null for due_date
+
+For due_time:
+- Only when the note gives a time of day; otherwise null
+- 24-hour HH:MM in the user's timezone: "at 3pm" = "15:00", "by 9:30" = "09:30", "noon" = "12:00"
+- A time always needs a due_date: "at 3pm" with no day means today, or tomorrow if that time has already passed today
+- Existing reminders show their time after the date ("due: 2026-10-20 15:00"), followed by a timezone if it isn't the user's
+
+For recurrence:
+- Only for tasks that repeat ("every", "each", "daily", "weekly", "on weekdays", "every other", ...); otherwise null
+- Use FREQ=DAILY, WEEKLY, MONTHLY or YEARLY, optionally with INTERVAL, BYDAY (MO,TU,WE,TH,FR,SA,SU), BYMONTHDAY (MONTHLY only), UNTIL=YYYYMMDD or COUNT
+- "pay rent on the 1st every month" = "FREQ=MONTHLY;BYMONTHDAY=1"
+- "standup notes every weekday" = "FREQ=WEEKLY;BYDAY=MO,TU,WE,TH,FR"
+- "water the plants every other Friday" = "FREQ=WEEKLY;INTERVAL=2;BYDAY=FR"
+- "team lunch on the last Friday of each month" = "FREQ=MONTHLY;BYDAY=-1FR"
+- due_date is the FIRST occurrence on or after today (for the rent example: the next 1st of a month)
+- Existing reminders that repeat show "repeats: RULE" - keep that rule when updating them unless the note changes how often it happens
+
+For priority:
+- "urgent": the note says it can't wait - "urgent", "asap", "immediately", "critical", "!!!" or "!!"
+- "high": it's important or pressing - "important", "high priority", "soon", "don't forget", a single "!"
+- "low": it can wait - "low priority", "someday", "whenever", "if I have time", "nice to have"
+- "normal" for everything else; a close due date alone doesn't make something urgent
+- Priority words are not part of the reminder text: "call the bank asap!!" = text "Call the bank", priority "urgent"
+- Existing reminders show "priority: X" when it isn't normal. Keep it when updating them unless the note changes how pressing the task is
+- A priority "set by the user" is never changed - leave it as it is
+
+For tags:
+- If a sentence ends with --[tag1, tag2, tag3], extract those as tags,
+- Remove the --[tags] part from the reminder text
+- Store tags as a comma-separated string like "tag1,tag2,tag3"
+- Example: "Call John about the project --[work, urgent]" should extract tags "work,urgent" and text "Call John about the project"
+- If no tags are specified, use null
+- Note that the user may provide tags in a different format em dash or double dash or single dash, use context to understand what is a tag
+
+CRITICAL: The reminder text should be CLEAN - do NOT include due date information in the text field.
+- BAD: "Message Jon about the project (due date: 2025-12-20)"
+- GOOD: "Message Jon about the project" with due_date: "2025-12-20" as a separate field
+
+CRITICAL DUPLICATE DETECTION RULES:
+- FIRST, check if the note is referring to an EXISTING reminder about the same task/subject
+- Match reminders by semantic similarity, not just exact text match. Examples:
+  * "finish simpli rewind" matches "simpli rewind should happen before end of year" → UPDATE the existing reminder
+  * "call John" matches "call John about the project" → UPDATE the existing reminder
+  * "review PR" matches "review the pull request" → UPDATE the existing reminder
+- If a new note provides additional information (like a due date) for an existing task, ALWAYS UPDATE the existing reminder instead of creating a new one
+- If a reminder already exists with the EXACT SAME text, tags, and due_date, DO NOT include it in your response at all (no CREATE, no UPDATE)
+- ONLY use UPDATE action if:
+  * The note is about the same task/subject as an existing reminder (even if wording differs slightly), OR
+  * The tags or due_date have actually CHANGED (different from what currently exists)
+- When updating, use the most complete/clear version of the text from either the existing reminder or the new note
+- If the reminder text, tags, and due_date are identical to an existing reminder, simply omit it from your response
+- Do NOT update a reminder just to "confirm" values remain the same - that's a waste of database operations
+- When in doubt about whether something matches an existing reminder, prefer UPDATE over CREATE
+- Reminders marked "added by the user" were typed in by hand rather than taken from a note - they count as existing reminders like any other
+
+Respond ONLY with valid JSON in this exact format, just straight JSON, no template literals or anything else:
+{{
+  "reminders": [
+    {{
+      "text": "Message Jon about the project",
+      "action": "CREATE" | "UPDATE",
+      "update_id": 1,
+      "tags": "work,urgent",
+      "due_date": "2025-12-20",
+      "due_time": null,
+      "recurrence": null,
+      "priority": "normal"
+    }}
+  ],
+  "reasoning": "Explain your decision here - why you extracted these reminders, or why you found no actionable items in the note."
+}}
+
+{}
+
+If there are no actionable items, respond with:
+{{"reminders": [], "reasoning": "No actionable tasks or deadlines found in this note."}}
+
+{}
+"#, current_date, clock, reminders_prompt, note_prompt_section(changes))
+}
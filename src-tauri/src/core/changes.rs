@@ -0,0 +1,116 @@
+// ============================================================================
+// CHANGE FEED
+// ============================================================================
+
+// Triggers on reminders and notes (see create_change_feed in migrations.rs) write one
+// row to change_feed for every insert, update and delete. The app reads the feed after
+// each write and turns every row into a Tauri event, so the UI learns about exactly
+// what changed - whether the app, the AI, juli-cli or a manual SQL session did it
+//
+// The events:
+// - reminder:created, reminder:updated, reminder:resolved  payload: the reminder (ReminderRow)
+// - reminder:deleted                                      payload: { id }
+// - note:saved                                            payload: the note (NoteRow)
+// Unresolving a reminder is a reminder:updated with resolved: false
+//
+// Usage:
+// let (changes, last_id) = ChangeFeed::new(&conn).since(last_seen)?;
+// for change in changes { app.emit(&change.event, &change.payload)? }
+
+use rusqlite::Connection;
+use serde::Serialize;
+
+use super::{NoteRow, NoteStore, ReminderRow, ReminderStore};
+use crate::error::JuliError;
+
+#[derive(Debug)]
+pub struct Change {
+    // The change_feed row id; they only go up
+    pub id: i64,
+    // The Tauri event name, e.g. "reminder:created"
+    pub event: String,
+    pub payload: ChangePayload,
+}
+
+// untagged: each variant serializes as just its contents, so the payload of
+// reminder:created is a plain ReminderRow and reminder:deleted is { "id": 3 }
+// (the row is boxed because it's much bigger than the other payloads)
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum ChangePayload {
+    Reminder(Box<ReminderRow>),
+    Note(NoteRow),
+    Deleted { id: i64 },
+}
+
+// A running app reads the feed every few seconds, and one that starts skips what happened
+// while it was closed, so nobody sends changes older than this any more
+const KEEP_CHANGES_HOURS: i64 = 1;
+
+pub struct ChangeFeed<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> ChangeFeed<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        ChangeFeed { conn }
+    }
+
+    // The newest change so far (0 for an empty feed); start reading after this one
+    // to skip everything that happened before
+    pub fn latest_id(&self) -> Result<i64, JuliError> {
+        let id = self.conn.query_row("SELECT COALESCE(MAX(id), 0) FROM change_feed", [], |row| row.get(0))?;
+        Ok(id)
+    }
+
+    // Every change after `after_id`, oldest first, with the row's current state as payload
+    // Also returns the id to pass next time. A reminder or note that no longer exists is
+    // left out - its reminder:deleted follows later in the same feed
+    pub fn since(&self, after_id: i64) -> Result<(Vec<Change>, i64), JuliError> {
+        let mut stmt = self.conn.prepare("SELECT id, event, entity_id FROM change_feed WHERE id > ?1 ORDER BY id")?;
+        let rows = stmt
+            .query_map([after_id], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?, row.get::<_, i64>(2)?)))?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let last_id = rows.last().map(|(id, _, _)| *id).unwrap_or(after_id);
+        let mut changes = Vec::new();
+        for (id, event, entity_id) in rows {
+            let payload = match event.as_str() {
+                "reminder:deleted" => Some(ChangePayload::Deleted { id: entity_id }),
+                "note:saved" => still_there(NoteStore::new(self.conn).get(entity_id))?.map(ChangePayload::Note),
+                // reminder:created, reminder:updated and reminder:resolved
+                _ => still_there(ReminderStore::new(self.conn).get(entity_id))?.map(Box::new).map(ChangePayload::Reminder),
+            };
+            if let Some(payload) = payload {
+                changes.push(Change { id, event, payload });
+            }
+        }
+
+        Ok((changes, last_id))
+    }
+
+    // Forget changes that were already sent
+    pub fn prune(&self, up_to_id: i64) -> Result<(), JuliError> {
+        self.conn.execute("DELETE FROM change_feed WHERE id <= ?1", [up_to_id])?;
+        Ok(())
+    }
+
+    // Forget changes older than KEEP_CHANGES_HOURS, sent or not
+    // open_database does this, so writes from juli-cli alone don't grow the feed forever
+    pub fn prune_stale(&self) -> Result<usize, JuliError> {
+        let pruned = self.conn.execute(
+            "DELETE FROM change_feed WHERE created_at < strftime('%Y-%m-%dT%H:%M:%SZ', 'now', ?1)",
+            [format!("-{} hours", KEEP_CHANGES_HOURS)],
+        )?;
+        Ok(pruned)
+    }
+}
+
+// NotFound becomes None, every other error stays an error
+fn still_there<T>(result: Result<T, JuliError>) -> Result<Option<T>, JuliError> {
+    match result {
+        Ok(row) => Ok(Some(row)),
+        Err(JuliError::NotFound { .. }) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
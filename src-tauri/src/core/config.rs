@@ -0,0 +1,110 @@
+// ============================================================================
+// CONFIG FILE
+// ============================================================================
+
+use std::{env, fs, path::Path};
+
+use chrono_tz::Tz;
+use serde::Deserialize;
+
+use super::notifications::{NotificationSettings, MAX_LEAD_MINUTES};
+use super::time::{parse_timezone, system_timezone};
+use super::validation::parse_due_time;
+use crate::ai::{self, AiProvider, AiSettings, ProviderKind};
+use crate::core::RevisionPolicy;
+use crate::error::JuliError;
+
+// Configuration loaded from config.json in the app's config directory
+// This is used for production builds where .env files aren't loaded
+// Every field is optional: Ollama needs no key, the ai section defaults to Anthropic,
+// revisions falls back to RevisionPolicy::default(), notifications to
+// NotificationSettings::default() and timezone to the system's
+#[derive(Debug, Deserialize, Default)]
+pub struct AppConfig {
+    #[serde(default)]
+    pub claude_api_key: Option<String>,
+    #[serde(default)]
+    pub ai: AiSettings,
+    #[serde(default)]
+    pub revisions: RevisionPolicy,
+    #[serde(default)]
+    pub notifications: NotificationSettings,
+    // IANA name like "America/New_York": what "today" and "at 3pm" mean (see core/time.rs)
+    #[serde(default)]
+    pub timezone: Option<String>,
+}
+
+impl AppConfig {
+    // Build whichever AI provider the config selects (Anthropic by default)
+    pub fn provider(&self) -> Result<Box<dyn AiProvider>, JuliError> {
+        ai::build_provider(&self.ai, self.claude_api_key.clone())
+    }
+
+    // The configured timezone, or the system's
+    // load_config already refused names that aren't real timezones
+    pub fn timezone(&self) -> Tz {
+        self.timezone
+            .as_deref()
+            .and_then(|name| parse_timezone(name).ok())
+            .unwrap_or_else(system_timezone)
+    }
+}
+
+// Read config.json (if there is one) and apply environment overrides on top
+// This ensures the app works both during development (.env) and in packaged builds (config.json)
+pub fn load_config(config_path: &Path) -> Result<AppConfig, JuliError> {
+    // config.json is optional - a dev setup or a local Ollama model may not need one
+    let mut config = match fs::read_to_string(config_path) {
+        Ok(contents) => {
+            let config: AppConfig = serde_json::from_str(&contents)
+                .map_err(|e| JuliError::config(format!("Invalid config.json: {}", e)).with_details(config_path.display().to_string()))?;
+            println!("✓ Using config.json at: {}", config_path.display());
+            config
+        }
+        Err(_) => AppConfig::default(),
+    };
+
+    // Environment variables (.env in development) win over config.json
+    if let Ok(key) = env::var("CLAUDE_API_KEY") {
+        println!("✓ Using API key from .env file");
+        config.claude_api_key = Some(key);
+    }
+    if let Ok(provider) = env::var("JULI_AI_PROVIDER") {
+        config.ai.provider = ProviderKind::parse(&provider)
+            .ok_or_else(|| JuliError::config(format!("Unknown JULI_AI_PROVIDER \"{}\" (expected anthropic, openai or ollama)", provider)))?;
+    }
+    if let Ok(model) = env::var("JULI_AI_MODEL") {
+        config.ai.model = Some(model);
+    }
+    if let Ok(base_url) = env::var("JULI_AI_BASE_URL") {
+        config.ai.base_url = Some(base_url);
+    }
+    if let Ok(api_key) = env::var("JULI_AI_API_KEY") {
+        config.ai.api_key = Some(api_key);
+    }
+    if let Ok(timezone) = env::var("JULI_TIMEZONE") {
+        config.timezone = Some(timezone);
+    }
+
+    if let Some(timezone) = config.timezone.as_deref() {
+        parse_timezone(timezone).map_err(|e| JuliError::config(format!("Invalid timezone in config.json or JULI_TIMEZONE: {}", e)))?;
+    }
+    if config.notifications.lead_minutes.iter().any(|lead| *lead < 0) {
+        return Err(JuliError::config("notifications.lead_minutes in config.json can't be negative"));
+    }
+    if config.notifications.lead_minutes.iter().any(|lead| *lead > MAX_LEAD_MINUTES) {
+        return Err(JuliError::config(format!(
+            "notifications.lead_minutes in config.json can be at most {} (a week)",
+            MAX_LEAD_MINUTES
+        )));
+    }
+    // "8:00" -> "08:00", and null, "" or "null" turn the digest off
+    config.notifications.digest_time = parse_due_time(config.notifications.digest_time.as_deref()).map_err(|_| {
+        JuliError::config(format!(
+            "notifications.digest_time in config.json must be a 24-hour HH:MM time, not \"{}\"",
+            config.notifications.digest_time.as_deref().unwrap_or("")
+        ))
+    })?;
+
+    Ok(config)
+}
@@ -0,0 +1,248 @@
+// ============================================================================
+// DATABASE LOCATION AND SETUP
+// ============================================================================
+
+// The desktop app and juli-cli must open the very same app.sqlite, with the same
+// pragmas and migrations, or the two frontends would drift apart
+
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use rusqlite::{Connection, OpenFlags};
+
+use super::ChangeFeed;
+use crate::error::JuliError;
+use crate::migrations;
+
+// Must match "identifier" in tauri.conf.json - Tauri names the app's directories after it
+pub const APP_IDENTIFIER: &str = "com.hassanahmed.juli";
+
+// Where Tauri's app_data_dir() points, resolved without a running app:
+// - macOS: ~/Library/Application Support/com.hassanahmed.juli
+// - Windows: C:\Users\<user>\AppData\Roaming\com.hassanahmed.juli
+// - Linux: ~/.local/share/com.hassanahmed.juli
+pub fn default_data_dir() -> Result<PathBuf, JuliError> {
+    dirs::data_dir()
+        .map(|dir| dir.join(APP_IDENTIFIER))
+        .ok_or_else(|| JuliError::config("Could not determine the app data directory"))
+}
+
+// Same for app_config_dir(), which is where config.json lives
+// (on Linux that's ~/.config/com.hassanahmed.juli)
+pub fn default_config_dir() -> Result<PathBuf, JuliError> {
+    dirs::config_dir()
+        .map(|dir| dir.join(APP_IDENTIFIER))
+        .ok_or_else(|| JuliError::config("Could not determine the app config directory"))
+}
+
+// How long a connection waits for another one's write lock before "database is locked"
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub fn database_path(data_dir: &Path) -> PathBuf {
+    data_dir.join("app.sqlite")
+}
+
+// Open (or create) the database file and bring its schema up to date
+// In TypeScript: const db = new Database(dbPath); db.pragma('journal_mode = WAL'); migrate(db)
+pub fn open_database(path: &Path) -> Result<Connection, JuliError> {
+    // Create the directory if it doesn't exist (like mkdir -p)
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| JuliError::database(format!("Could not create {}: {}", parent.display(), e)))?;
+    }
+
+    let mut conn = Connection::open(path)?;
+
+    // WAL lets reads continue while a write is in progress, which also lets
+    // the CLI read while the desktop app is writing (and vice versa)
+    // It has to be set outside of a transaction, so it can't be a migration
+    conn.execute_batch("PRAGMA journal_mode = WAL;")?;
+
+    // Both processes may write at the same time; wait for the other one's
+    // transaction instead of failing straight away with "database is locked"
+    conn.busy_timeout(BUSY_TIMEOUT)?;
+
+    // Bring the schema up to date before anything can touch the database
+    // If a migration fails we refuse to continue instead of running on a half-upgraded schema
+    migrations::run(&mut conn)?;
+
+    // Only the desktop app reads the change feed; without this, using juli-cli alone would
+    // make it grow forever
+    ChangeFeed::new(&conn).prune_stale()?;
+
+    Ok(conn)
+}
+
+// ============================================================================
+// CONNECTION POOL
+// ============================================================================
+
+// The desktop app's connections: one writer and a few readers on the same file
+// - SQLite allows one writer at a time anyway, so all writes share one connection
+// - under WAL, readers see the last committed state and never wait for the writer,
+//   so listing reminders doesn't stall while an analysis is saving its results
+// - read()/write() run the work on Tokio's blocking thread pool (spawn_blocking),
+//   so a slow query never blocks the async runtime the commands run on
+// - a panic while a connection is in use doesn't take the database down with it
+//   (see lock_connection)
+//
+// Usage:
+// let pool = DbPool::open(&database_path(&data_dir))?;
+// let reminders = pool.read(|conn| ReminderStore::new(conn).unresolved()).await?;
+// pool.write(move |conn| ReminderStore::new(conn).resolve(reminder_id)).await?;
+//
+// Cloning is cheap and shares the same connections (it's an Arc inside)
+#[derive(Clone)]
+pub struct DbPool {
+    inner: Arc<PoolInner>,
+}
+
+struct PoolInner {
+    writer: Mutex<Connection>,
+    // Readers not in use right now; more are opened when they're all busy
+    idle_readers: Mutex<Vec<Connection>>,
+    // None for a pool around a single connection, which then does the reading too
+    path: Option<PathBuf>,
+}
+
+// Readers kept open between queries; any extra ones are closed after use
+const MAX_IDLE_READERS: usize = 4;
+
+impl DbPool {
+    // Open the database like open_database (migrations included) and pool it
+    pub fn open(path: &Path) -> Result<DbPool, JuliError> {
+        let writer = open_database(path)?;
+        Ok(DbPool::new(writer, Some(path.to_path_buf())))
+    }
+
+    // A pool where one connection does everything, e.g. an in-memory database in tests
+    pub fn from_connection(conn: Connection) -> DbPool {
+        DbPool::new(conn, None)
+    }
+
+    fn new(writer: Connection, path: Option<PathBuf>) -> DbPool {
+        DbPool {
+            inner: Arc::new(PoolInner {
+                writer: Mutex::new(writer),
+                idle_readers: Mutex::new(Vec::new()),
+                path,
+            }),
+        }
+    }
+
+    // Run a read on a reader connection, off the async runtime
+    // The closure must own what it uses ('static), since it runs on another thread:
+    // pool.read(move |conn| NoteStore::new(conn).for_date(&for_date)).await
+    pub async fn read<T, F>(&self, read: F) -> Result<T, JuliError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Connection) -> Result<T, JuliError> + Send + 'static,
+    {
+        let pool = self.clone();
+        blocking(move || pool.with_reader(read)).await
+    }
+
+    // Run a write on the writer connection, off the async runtime
+    pub async fn write<T, F>(&self, write: F) -> Result<T, JuliError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Connection) -> Result<T, JuliError> + Send + 'static,
+    {
+        let pool = self.clone();
+        blocking(move || pool.with_writer(write)).await
+    }
+
+    // Bring the schema up to date (migrations need the connection to themselves)
+    pub async fn migrate(&self) -> Result<i64, JuliError> {
+        let pool = self.clone();
+        blocking(move || Ok(migrations::run(&mut lock_connection(&pool.inner.writer))?)).await
+    }
+
+    // A reader from the idle list, or a new one when they're all busy
+    fn take_reader(&self, path: &Path) -> Result<Connection, JuliError> {
+        if let Some(reader) = self.inner.idle_readers.lock().unwrap_or_else(|e| e.into_inner()).pop() {
+            return Ok(reader);
+        }
+
+        // Read-only: a bug in a read can't write by accident
+        let reader = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX)?;
+        reader.busy_timeout(BUSY_TIMEOUT)?;
+        Ok(reader)
+    }
+
+    fn return_reader(&self, reader: Connection) {
+        let mut idle = self.inner.idle_readers.lock().unwrap_or_else(|e| e.into_inner());
+        if idle.len() < MAX_IDLE_READERS {
+            idle.push(reader);
+        }
+    }
+}
+
+// Synchronous access to a database, for code that already runs off the async runtime
+// (the analysis worker, juli-cli) or only needs it for a moment
+// Implemented by DbPool and by a plain Mutex<Connection>, so the Analyzer and the
+// queue work the same in the app, in juli-cli and in the tests
+pub trait Database {
+    fn with_reader<T>(&self, read: impl FnOnce(&Connection) -> Result<T, JuliError>) -> Result<T, JuliError>;
+    fn with_writer<T>(&self, write: impl FnOnce(&Connection) -> Result<T, JuliError>) -> Result<T, JuliError>;
+}
+
+impl Database for DbPool {
+    fn with_reader<T>(&self, read: impl FnOnce(&Connection) -> Result<T, JuliError>) -> Result<T, JuliError> {
+        let Some(path) = &self.inner.path else {
+            return self.with_writer(read);
+        };
+        let reader = self.take_reader(path)?;
+        let result = read(&reader);
+        self.return_reader(reader);
+        result
+    }
+
+    fn with_writer<T>(&self, write: impl FnOnce(&Connection) -> Result<T, JuliError>) -> Result<T, JuliError> {
+        write(&lock_connection(&self.inner.writer))
+    }
+}
+
+impl Database for Mutex<Connection> {
+    fn with_reader<T>(&self, read: impl FnOnce(&Connection) -> Result<T, JuliError>) -> Result<T, JuliError> {
+        read(&lock_connection(self))
+    }
+
+    fn with_writer<T>(&self, write: impl FnOnce(&Connection) -> Result<T, JuliError>) -> Result<T, JuliError> {
+        write(&lock_connection(self))
+    }
+}
+
+// Lock a connection, even if a thread panicked while holding it
+// A panic "poisons" a Mutex, and .lock().unwrap() would then panic in every later
+// command. The connection itself is fine though: at worst the panic left a
+// transaction open, which we roll back before handing the connection out again
+pub fn lock_connection(mutex: &Mutex<Connection>) -> MutexGuard<'_, Connection> {
+    match mutex.lock() {
+        Ok(conn) => conn,
+        Err(poisoned) => {
+            println!("⚠️ Recovering the database connection after a panic");
+            let conn = poisoned.into_inner();
+            if !conn.is_autocommit() {
+                if let Err(e) = conn.execute_batch("ROLLBACK") {
+                    println!("⚠️ Failed to roll back the interrupted transaction: {}", e);
+                }
+            }
+            mutex.clear_poison();
+            conn
+        }
+    }
+}
+
+// spawn_blocking, with a panic in the closure turned into an error instead of crashing the command
+// In TypeScript there's no equivalent: JS can't run a synchronous query on another thread
+pub async fn blocking<T, F>(work: F) -> Result<T, JuliError>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, JuliError> + Send + 'static,
+{
+    tokio::task::spawn_blocking(work)
+        .await
+        .map_err(|e| JuliError::database("A database task failed").with_details(e.to_string()))?
+}
@@ -0,0 +1,56 @@
+// ============================================================================
+// JULI CORE
+// ============================================================================
+
+// Everything juli does, without Tauri
+// The stores work on a plain rusqlite::Connection and the Analyzer on any AiProvider,
+// so the same code runs behind the desktop app's #[tauri::command] functions,
+// in the integration tests, and in the juli-cli binary
+//
+// Usage:
+// let note_id = NoteStore::new(&conn).save("Call John tomorrow", &today_note_date())?;
+// let unresolved = ReminderStore::new(&conn).unresolved()?;
+
+mod ai_logs;
+mod analysis_cache;
+mod analysis_queue;
+mod analyzer;
+mod changes;
+mod config;
+mod database;
+mod note_diff;
+mod notes;
+mod notifications;
+mod proposals;
+mod recurrence;
+mod reminders;
+mod revisions;
+mod search;
+mod snooze;
+mod sources;
+mod time;
+mod validation;
+
+pub use ai_logs::{AiLogRejection, AiLogRow, AiLogStore, NewAiLog};
+pub use analysis_cache::{content_hash, AnalysisCache, CacheKey};
+pub use analysis_queue::{run_analysis_job, run_next_analysis, AnalysisJobResult, AnalysisQueue, PendingAnalysis};
+pub use analyzer::{prompt_version, AiAnalysisResponse, AiExtractedReminder, AnalysisOutcome, Analyzer};
+pub use changes::{Change, ChangeFeed, ChangePayload};
+pub use config::{load_config, AppConfig};
+pub use database::{
+    blocking, database_path, default_config_dir, default_data_dir, lock_connection, open_database, Database, DbPool, APP_IDENTIFIER,
+};
+pub use note_diff::{NoteChanges, NoteSection};
+pub use notes::{note_date_key, parse_note_date, today_note_date, NoteRow, NoteStore};
+pub use notifications::{prune_delivered_notifications, Notification, NotificationSettings, Notifier};
+pub use proposals::{NewProposal, ProposalRow, ProposalSource, ProposalStore};
+pub use recurrence::{Frequency, Recurrence};
+pub use reminders::{ReminderDraft, ReminderPatch, ReminderRow, ReminderSort, ReminderStore};
+pub use revisions::{DiffLine, NoteRevisionRow, RevisionDiff, RevisionPolicy, RevisionStore};
+pub use search::{SearchHit, SearchHitKind, SearchIndex, SearchQuery, SearchScope};
+pub use snooze::snooze_date;
+pub use sources::{NewReminderSource, ReminderSourceRow, ReminderSourceStore};
+pub use time::{due_at, format_due_at, now_timestamp, parse_timezone, system_timezone, today_in};
+pub use validation::{
+    checked_draft, normalize_tags, parse_due_date, parse_due_time, parse_priority, parse_recurrence, validate_reminders, RejectedReminder, ReminderAction, ValidReminder,
+};
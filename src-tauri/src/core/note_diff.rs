@@ -0,0 +1,121 @@
+// ============================================================================
+// NOTE CHANGES SINCE THE LAST ANALYSIS
+// ============================================================================
+
+// A day's note grows all day long. Re-sending all of it after every edit costs
+// tokens and tempts the model to re-create or re-update reminders it already
+// handled, so we compare the note with the text we analyzed last time and only
+// send the paragraphs that gained or changed lines, plus their neighbours as context
+//
+// A paragraph is a run of non-blank lines; blank lines separate paragraphs
+
+use similar::{capture_diff_slices, Algorithm, DiffOp};
+
+// A block of lines from the current note
+// Line numbers are 1-based and inclusive, like an editor shows them
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NoteSection {
+    pub start_line: usize,
+    pub end_line: usize,
+    pub text: String,
+    // false for context sections, which are only there to help the model understand the change
+    pub changed: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NoteChanges {
+    // True when there was nothing to compare against, so the whole note is new
+    pub whole_note: bool,
+    // In note order
+    pub sections: Vec<NoteSection>,
+}
+
+impl NoteChanges {
+    // What changed going from `previous` (the last analyzed text, if any) to `current`
+    pub fn between(previous: Option<&str>, current: &str) -> NoteChanges {
+        let lines: Vec<&str> = current.lines().collect();
+
+        // A blank note has nothing to extract, whatever was there before
+        if current.trim().is_empty() {
+            return NoteChanges { whole_note: previous.is_none(), sections: Vec::new() };
+        }
+
+        let Some(previous) = previous else {
+            return NoteChanges {
+                whole_note: true,
+                sections: vec![NoteSection {
+                    start_line: 1,
+                    end_line: lines.len(),
+                    text: current.to_string(),
+                    changed: true,
+                }],
+            };
+        };
+
+        // Which lines of the current text are new (0-based)
+        // Deleted lines don't matter here - there is nothing to extract from text that's gone
+        // Comparing line slices (rather than the raw text) means a last line that
+        // just gained a trailing newline doesn't count as changed
+        let previous_lines: Vec<&str> = previous.lines().collect();
+        let mut inserted = vec![false; lines.len()];
+        for op in capture_diff_slices(Algorithm::Myers, &previous_lines, &lines) {
+            if let DiffOp::Insert { new_index, new_len, .. } | DiffOp::Replace { new_index, new_len, .. } = op {
+                inserted[new_index..new_index + new_len].fill(true);
+            }
+        }
+
+        let paragraphs = paragraphs(&lines);
+        let changed: Vec<bool> = paragraphs
+            .iter()
+            .map(|&(start, end)| inserted[start..=end].iter().any(|&new| new))
+            .collect();
+
+        // Keep every changed paragraph and the paragraph on each side of it
+        let sections = paragraphs
+            .iter()
+            .enumerate()
+            .filter(|&(i, _)| {
+                changed[i] || (i > 0 && changed[i - 1]) || (i + 1 < changed.len() && changed[i + 1])
+            })
+            .map(|(i, &(start, end))| NoteSection {
+                start_line: start + 1,
+                end_line: end + 1,
+                text: lines[start..=end].join("\n"),
+                changed: changed[i],
+            })
+            .collect();
+
+        NoteChanges { whole_note: false, sections }
+    }
+
+    pub fn changed_sections(&self) -> impl Iterator<Item = &NoteSection> {
+        self.sections.iter().filter(|section| section.changed)
+    }
+
+    // Nothing was added or edited (the same text, or only deletions)
+    pub fn is_empty(&self) -> bool {
+        self.changed_sections().next().is_none()
+    }
+}
+
+// (first line, last line) of every paragraph, 0-based
+fn paragraphs(lines: &[&str]) -> Vec<(usize, usize)> {
+    let mut paragraphs = Vec::new();
+    let mut start = None;
+
+    for (i, line) in lines.iter().enumerate() {
+        match (line.trim().is_empty(), start) {
+            (false, None) => start = Some(i),
+            (true, Some(s)) => {
+                paragraphs.push((s, i - 1));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        paragraphs.push((s, lines.len() - 1));
+    }
+
+    paragraphs
+}
@@ -0,0 +1,182 @@
+// ============================================================================
+// NOTES
+// ============================================================================
+
+use chrono::{Local, NaiveDate};
+use rusqlite::{Connection, Row};
+use serde::Serialize;
+
+use crate::core::revisions::{RevisionPolicy, RevisionStore};
+use crate::error::JuliError;
+
+// This defines the shape of data we'll send to the frontend
+// Think of it like a TypeScript interface:
+// interface NoteRow {
+//   id: number;
+//   text: string;
+//   for_date: string;
+// }
+//
+// The attributes above the struct are "derive macros":
+// - #[derive(Debug)] - Lets you print the struct for debugging (like console.log)
+// - #[derive(Serialize)] - Converts this struct to JSON automatically
+//   In TypeScript, objects are already JSON-compatible
+//   In Rust, we need explicit serialization via the serde crate
+#[derive(Debug, Serialize)]
+pub struct NoteRow {
+    pub id: i64,         // i64 is a 64-bit integer (TypeScript's number type)
+    pub text: String,    // String is Rust's owned string type (like TypeScript's string)
+    pub for_date: String, // We store dates as strings for simplicity
+}
+
+impl NoteRow {
+    fn from_row(row: &Row) -> rusqlite::Result<NoteRow> {
+        Ok(NoteRow {
+            id: row.get("id")?,
+            text: row.get("text")?,
+            for_date: row.get("for_date")?,
+        })
+    }
+}
+
+// A borrowed view of the notes table
+// In TypeScript: class NoteStore { constructor(private conn: Database) {} }
+pub struct NoteStore<'a> {
+    conn: &'a Connection,
+    revisions: RevisionPolicy,
+}
+
+impl<'a> NoteStore<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        NoteStore { conn, revisions: RevisionPolicy::default() }
+    }
+
+    // Use the "revisions" settings from config.json instead of the defaults
+    // Usage: NoteStore::new(&conn).with_revision_policy(config.revisions.clone())
+    pub fn with_revision_policy(mut self, policy: RevisionPolicy) -> Self {
+        self.revisions = policy;
+        self
+    }
+
+    // Insert the note for a date, or replace its text if one already exists
+    // Returns the note's id either way
+    pub fn save(&self, text: &str, for_date: &str) -> Result<i64, JuliError> {
+        // Parameterized queries (?1, ?2):
+        // - ?1 refers to the first parameter (text)
+        // - ?2 refers to the second parameter (for_date)
+        // - This prevents SQL injection, just like prepared statements in TypeScript
+        // - In TypeScript: db.query('INSERT INTO notes VALUES ($1, $2)', [text, for_date])
+        //
+        // ON CONFLICT(for_date) DO UPDATE:
+        // - If a note for this date already exists, update it instead of failing
+        // - excluded.text refers to the value we tried to insert
+        // - Like: if (exists) { update() } else { insert() }
+        //
+        // day is the same date as YYYY-MM-DD, which search uses for date ranges
+        let day = parse_note_date(for_date).map(|d| d.to_string());
+        self.conn.execute(
+            "INSERT INTO notes (text, for_date, day) VALUES (?1, ?2, ?3)
+             ON CONFLICT(for_date) DO UPDATE SET text = excluded.text",
+            (text, for_date, day), // Tuple of parameters that match ?1, ?2 and ?3
+        )?;
+
+        // Fetch the actual note ID by querying for the note with this date
+        // This works whether we inserted or updated
+        let note_id: i64 = self
+            .conn
+            .query_row("SELECT id FROM notes WHERE for_date = ?1", [for_date], |row| row.get(0))?;
+
+        // The upsert above just overwrote the old text - keep it in the history
+        RevisionStore::new(self.conn).record(note_id, text, &self.revisions)?;
+
+        Ok(note_id)
+    }
+
+    // Put an earlier revision's text back as the note's text
+    // The text being replaced stays in the history, so a restore can be undone too
+    pub fn restore_revision(&self, revision_id: i64) -> Result<NoteRow, JuliError> {
+        let revision = RevisionStore::new(self.conn).get(revision_id)?;
+        let for_date: String = self
+            .conn
+            .query_row("SELECT for_date FROM notes WHERE id = ?1", [revision.note_id], |row| row.get(0))?;
+
+        // Never fold the restored text into the revision holding the current text
+        let policy = RevisionPolicy { coalesce_seconds: 0, ..self.revisions.clone() };
+        NoteStore { conn: self.conn, revisions: policy }.save(&revision.text, &for_date)?;
+
+        self.for_date(&for_date)
+    }
+
+    // Add a line to the end of a date's note (creating the note if needed)
+    // Returns the note's id and its full text, which is what gets analyzed
+    pub fn append(&self, text: &str, for_date: &str) -> Result<(i64, String), JuliError> {
+        let full_text = match self.for_date(for_date) {
+            Ok(note) if note.text.trim().is_empty() => text.to_string(),
+            Ok(note) => format!("{}\n{}", note.text.trim_end(), text),
+            Err(JuliError::NotFound { .. }) => text.to_string(),
+            Err(e) => return Err(e),
+        };
+        let note_id = self.save(&full_text, for_date)?;
+        Ok((note_id, full_text))
+    }
+
+    pub fn all(&self) -> Result<Vec<NoteRow>, JuliError> {
+        let mut stmt = self.conn.prepare("SELECT id, text, for_date FROM notes ORDER BY id")?;
+
+        // query_map runs the closure for each row in the result set
+        // In TypeScript: rows.map(row => ({ id: row.id, text: row.text, ... }))
+        let notes = stmt
+            .query_map([], NoteRow::from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(notes)
+    }
+
+    pub fn for_date(&self, for_date: &str) -> Result<NoteRow, JuliError> {
+        self.conn
+            .query_row("SELECT id, text, for_date FROM notes WHERE for_date = ?1", [for_date], NoteRow::from_row)
+            // No row isn't a database failure - the note simply hasn't been written yet
+            .map_err(|e| match e {
+                rusqlite::Error::QueryReturnedNoRows => JuliError::not_found(format!("No note for {}", for_date)),
+                other => other.into(),
+            })
+    }
+
+    pub fn get(&self, note_id: i64) -> Result<NoteRow, JuliError> {
+        self.conn
+            .query_row("SELECT id, text, for_date FROM notes WHERE id = ?1", [note_id], NoteRow::from_row)
+            .map_err(|e| match e {
+                rusqlite::Error::QueryReturnedNoRows => JuliError::not_found(format!("Note {} not found", note_id)),
+                other => other.into(),
+            })
+    }
+}
+
+// ============================================================================
+// NOTE DATES
+// ============================================================================
+
+// Notes are keyed by a human readable date like "Friday, October 16, 2026"
+// (that's what the frontend shows and sends back as for_date)
+//
+// strftime-style format string:
+//   %A = Full weekday name (e.g., "Monday")
+//   %B = Full month name (e.g., "December")
+//   %d = Day of month (e.g., "14")
+//   %Y = Full year (e.g., "2025")
+const NOTE_DATE_FORMAT: &str = "%A, %B %d, %Y";
+
+pub fn note_date_key(date: NaiveDate) -> String {
+    date.format(NOTE_DATE_FORMAT).to_string()
+}
+
+// The reverse of note_date_key, None for anything that isn't in that format
+pub fn parse_note_date(key: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(key, NOTE_DATE_FORMAT).ok()
+}
+
+// Today's key, in the system's local timezone
+// In TypeScript: new Date().toLocaleDateString('en-US', options)
+pub fn today_note_date() -> String {
+    note_date_key(Local::now().date_naive())
+}
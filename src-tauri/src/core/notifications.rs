@@ -0,0 +1,296 @@
+// ============================================================================
+// REMINDER NOTIFICATIONS
+// ============================================================================
+
+// Works out which desktop notifications are due; the app's scheduler (see lib.rs) shows them
+// - a reminder with a due time gets a notification at each lead time in config.json
+//   ("lead_minutes": [15] = 15 minutes before it's due, 0 = when it's due)
+// - every morning at digest_time one digest lists what's due today or overdue, which is
+//   also where reminders with only a due date show up
+// A snoozed reminder stays quiet until the day it was snoozed to, even when a lead time
+// would otherwise fall before that day
+// Every notification that was shown is recorded in delivered_notifications, so a restart
+// doesn't show it again. A reminder that is moved gets new keys and so new notifications
+// Records that can't matter any more are pruned (see prune_delivered_notifications)
+//
+// Usage:
+// let notifier = Notifier::new(&conn, &config.notifications, config.timezone());
+// for notification in notifier.due(Utc::now())? {
+//     show(&notification);
+//     notifier.mark_delivered(&notification)?;
+// }
+
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, Utc};
+use chrono_tz::Tz;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+
+use super::time::{due_at, parse_timezone};
+use super::{ReminderRow, ReminderStore};
+use crate::error::JuliError;
+
+// The "notifications" section of config.json, e.g.
+// { "notifications": { "enabled": true, "lead_minutes": [60, 15], "digest_time": "08:00" } }
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct NotificationSettings {
+    pub enabled: bool,
+    // Minutes before a reminder's due time to notify; 0 is at the due time itself
+    pub lead_minutes: Vec<i64>,
+    // When the morning digest goes out, "08:00" in the user's timezone; null turns it off
+    pub digest_time: Option<String>,
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        NotificationSettings {
+            enabled: true,
+            lead_minutes: vec![15],
+            digest_time: Some("08:00".to_string()),
+        }
+    }
+}
+
+// The longest lead time load_config accepts: a week ahead is plenty, and much larger
+// values would overflow the date arithmetic
+pub const MAX_LEAD_MINUTES: i64 = 7 * 24 * 60;
+
+// A notification for a reminder is still shown this long after it's due,
+// in case the app was closed or the computer asleep when it was time
+const LATE_MINUTES: i64 = 60;
+
+// The digest is still shown when the app starts up to this long after digest_time
+const DIGEST_LATE_HOURS: i64 = 4;
+
+// At most this many reminders are named in the digest, the rest are counted
+const DIGEST_MAX_LINES: usize = 5;
+
+// A notification is shown at most MAX_LEAD_MINUTES before its reminder is due and
+// LATE_MINUTES after, so after this long its record can't stop anything being shown twice
+const KEEP_DELIVERED_DAYS: i64 = 8;
+
+// In TypeScript:
+// interface Notification { key: string; reminder_id: number | null; title: string; body: string }
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Notification {
+    // What it's about, see delivered_notifications in migrations.rs
+    pub key: String,
+    // None for the digest
+    pub reminder_id: Option<i64>,
+    pub title: String,
+    pub body: String,
+}
+
+pub struct Notifier<'a> {
+    conn: &'a Connection,
+    settings: &'a NotificationSettings,
+    // What "today" and digest_time mean
+    timezone: Tz,
+}
+
+impl<'a> Notifier<'a> {
+    pub fn new(conn: &'a Connection, settings: &'a NotificationSettings, timezone: Tz) -> Self {
+        Notifier { conn, settings, timezone }
+    }
+
+    // The notifications that should be showing at `now` and haven't been shown yet
+    pub fn due(&self, now: DateTime<Utc>) -> Result<Vec<Notification>, JuliError> {
+        if !self.settings.enabled {
+            return Ok(Vec::new());
+        }
+
+        let reminders = ReminderStore::new(self.conn).unresolved()?;
+        let mut due = Vec::new();
+        for reminder in &reminders {
+            if let Some(notification) = self.reminder_notification(reminder, now)? {
+                due.push(notification);
+            }
+        }
+        if let Some(digest) = self.digest(&reminders, now)? {
+            due.push(digest);
+        }
+        Ok(due)
+    }
+
+    // Remember that a notification was shown; showing it twice is harmless to record
+    pub fn mark_delivered(&self, notification: &Notification) -> Result<(), JuliError> {
+        self.conn.execute(
+            "INSERT OR IGNORE INTO delivered_notifications (key, reminder_id, title, body) VALUES (?1, ?2, ?3, ?4)",
+            (&notification.key, notification.reminder_id, &notification.title, &notification.body),
+        )?;
+        Ok(())
+    }
+
+    // Seconds until the next notification is due (0 if one is due now), None when nothing is coming up
+    // The scheduler sleeps this long, so notifications go out on time without polling every second
+    pub fn seconds_until_next(&self, now: DateTime<Utc>) -> Result<Option<i64>, JuliError> {
+        if !self.settings.enabled {
+            return Ok(None);
+        }
+
+        let mut times = Vec::new();
+        for reminder in ReminderStore::new(self.conn).unresolved()? {
+            if let Some(due) = parse_due_at(&reminder) {
+                let quiet_until = self.snoozed_until(&reminder);
+                times.extend(self.lead_minutes().filter_map(|lead| notify_at(due, lead)).map(|at| at.max(quiet_until.unwrap_or(now))));
+            }
+        }
+        if let Some(time) = self.digest_time() {
+            let today = now.with_timezone(&self.timezone).date_naive();
+            times.extend(today.succ_opt().map(|tomorrow| due_at(tomorrow, time, self.timezone).with_timezone(&Utc)));
+            times.push(due_at(today, time, self.timezone).with_timezone(&Utc));
+        }
+
+        Ok(times
+            .into_iter()
+            .filter(|time| *time > now)
+            .min()
+            .map(|next| (next - now).num_seconds()))
+    }
+
+    // With several lead times only the shortest one that has started counts, so after a
+    // restart five minutes before a reminder is due there is one notification, not three
+    fn reminder_notification(&self, reminder: &ReminderRow, now: DateTime<Utc>) -> Result<Option<Notification>, JuliError> {
+        let Some(due) = parse_due_at(reminder) else {
+            return Ok(None);
+        };
+        if now > due + Duration::minutes(LATE_MINUTES) {
+            return Ok(None);
+        }
+        if self.snoozed_until(reminder).is_some_and(|until| now < until) {
+            return Ok(None);
+        }
+        let Some(lead) = self.lead_minutes().filter(|lead| notify_at(due, *lead).is_some_and(|at| at <= now)).min() else {
+            return Ok(None);
+        };
+
+        let key = format!("reminder:{}:{}:{}", reminder.id, reminder.due_at.as_deref().unwrap_or(""), lead);
+        if self.delivered(&key)? {
+            return Ok(None);
+        }
+
+        let at = self.local_time(reminder);
+        let body = if now >= due {
+            format!("Due now ({})", at)
+        } else {
+            format!("Due at {}, in {}", at, minutes_label((due - now).num_minutes().max(1)))
+        };
+        Ok(Some(Notification { key, reminder_id: Some(reminder.id), title: reminder.text.clone(), body }))
+    }
+
+    // Everything unresolved that's due today or overdue, once a day at digest_time
+    fn digest(&self, reminders: &[ReminderRow], now: DateTime<Utc>) -> Result<Option<Notification>, JuliError> {
+        let Some(time) = self.digest_time() else {
+            return Ok(None);
+        };
+        let today = now.with_timezone(&self.timezone).date_naive();
+        let digest_at = due_at(today, time, self.timezone).with_timezone(&Utc);
+        if now < digest_at || now > digest_at + Duration::hours(DIGEST_LATE_HOURS) {
+            return Ok(None);
+        }
+
+        let key = format!("digest:{}", today.format("%Y-%m-%d"));
+        if self.delivered(&key)? {
+            return Ok(None);
+        }
+
+        let today = today.format("%Y-%m-%d").to_string();
+        let items: Vec<&ReminderRow> = reminders
+            .iter()
+            .filter(|r| r.due_date.as_deref().is_some_and(|d| d <= today.as_str()))
+            .collect();
+        // Nothing due: no digest today (one that shows up later would only be noise)
+        if items.is_empty() {
+            return Ok(None);
+        }
+
+        let mut lines: Vec<String> = items
+            .iter()
+            .take(DIGEST_MAX_LINES)
+            .map(|r| match (r.due_date.as_deref(), r.due_time.as_deref()) {
+                (Some(d), _) if d < today.as_str() => format!("• {} (overdue)", r.text),
+                (_, Some(_)) => format!("• {} at {}", r.text, self.local_time(r)),
+                _ => format!("• {}", r.text),
+            })
+            .collect();
+        if items.len() > DIGEST_MAX_LINES {
+            lines.push(format!("and {} more", items.len() - DIGEST_MAX_LINES));
+        }
+
+        let title = match items.len() {
+            1 => "Today: 1 reminder".to_string(),
+            n => format!("Today: {} reminders", n),
+        };
+        Ok(Some(Notification { key, reminder_id: None, title, body: lines.join("\n") }))
+    }
+
+    fn delivered(&self, key: &str) -> Result<bool, JuliError> {
+        let count: i64 =
+            self.conn
+                .query_row("SELECT COUNT(*) FROM delivered_notifications WHERE key = ?1", [key], |row| row.get(0))?;
+        Ok(count > 0)
+    }
+
+    // The start of the day a snoozed reminder was snoozed to, None when it was never snoozed
+    // That day begins in the reminder's own timezone, which may not be the one we're in
+    fn snoozed_until(&self, reminder: &ReminderRow) -> Option<DateTime<Utc>> {
+        if reminder.snooze_count == 0 {
+            return None;
+        }
+        let date = NaiveDate::parse_from_str(reminder.due_date.as_deref()?, "%Y-%m-%d").ok()?;
+        let timezone = reminder.timezone.as_deref().and_then(|name| parse_timezone(name).ok()).unwrap_or(self.timezone);
+        Some(due_at(date, NaiveTime::MIN, timezone).with_timezone(&Utc))
+    }
+
+    // Negative or huge lead times make no sense; load_config refuses them, this skips them anyway
+    fn lead_minutes(&self) -> impl Iterator<Item = i64> + '_ {
+        self.settings.lead_minutes.iter().copied().filter(|lead| (0..=MAX_LEAD_MINUTES).contains(lead))
+    }
+
+    fn digest_time(&self) -> Option<NaiveTime> {
+        self.settings
+            .digest_time
+            .as_deref()
+            .and_then(|time| NaiveTime::parse_from_str(time, "%H:%M").ok())
+    }
+
+    // "15:00", or "15:00 Europe/Paris" when the reminder was set in another timezone
+    fn local_time(&self, reminder: &ReminderRow) -> String {
+        let time = reminder.due_time.clone().unwrap_or_default();
+        match reminder.timezone.as_deref() {
+            Some(tz) if tz != self.timezone.name() => format!("{} {}", time, tz),
+            _ => time,
+        }
+    }
+}
+
+// Forget the notifications that can't come up again, so delivered_notifications doesn't grow
+// forever: everything older than KEEP_DELIVERED_DAYS, and those of resolved reminders
+// (a deleted reminder's go with it, see migrations.rs). Returns how many were removed
+pub fn prune_delivered_notifications(conn: &Connection, now: DateTime<Utc>) -> Result<usize, JuliError> {
+    let cutoff = (now - Duration::days(KEEP_DELIVERED_DAYS)).format("%Y-%m-%dT%H:%M:%SZ").to_string();
+    let pruned = conn.execute(
+        "DELETE FROM delivered_notifications
+         WHERE delivered_at < ?1 OR reminder_id IN (SELECT id FROM reminders WHERE resolved = 1)",
+        [cutoff],
+    )?;
+    Ok(pruned)
+}
+
+// `lead` minutes before `due`, None if that's before any date chrono can hold
+fn notify_at(due: DateTime<Utc>, lead: i64) -> Option<DateTime<Utc>> {
+    due.checked_sub_signed(Duration::try_minutes(lead)?)
+}
+
+fn parse_due_at(reminder: &ReminderRow) -> Option<DateTime<Utc>> {
+    let due = reminder.due_at.as_deref()?;
+    DateTime::parse_from_rfc3339(due).ok().map(|due| due.with_timezone(&Utc))
+}
+
+fn minutes_label(minutes: i64) -> String {
+    match minutes {
+        1 => "1 minute".to_string(),
+        m if m < 120 => format!("{} minutes", m),
+        m => format!("{} hours", m / 60),
+    }
+}
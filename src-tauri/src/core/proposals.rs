@@ -0,0 +1,317 @@
+// ============================================================================
+// REMINDER PROPOSALS (REVIEW MODE)
+// ============================================================================
+
+// With "review_changes" turned on in config.json the analyzer doesn't touch the
+// reminders table. Every CREATE/UPDATE the AI returns is stored here as a pending
+// proposal instead, and nothing happens until the user accepts (optionally after
+// editing it) or rejects it
+//
+// Usage:
+// let store = ProposalStore::new(&conn);
+// for proposal in store.pending()? { ... }
+// let reminder_id = store.accept(proposal_id, None)?;
+
+use chrono_tz::Tz;
+use rusqlite::{Connection, Row};
+use serde::Serialize;
+
+use super::note_diff::NoteSection;
+use super::sources::{NewReminderSource, ReminderSourceStore};
+use super::validation::checked_draft;
+use super::{ReminderDraft, ReminderStore};
+use crate::error::JuliError;
+
+// The lines of the note a proposal came from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ProposalSource {
+    pub start_line: i64,
+    pub end_line: i64,
+    pub excerpt: String,
+}
+
+// In TypeScript:
+// interface ProposalRow { id: number; note_id: number; ai_log_id: number | null; action: 'CREATE' | 'UPDATE';
+//   reminder_id: number | null; proposed: ReminderDraft; before: ReminderDraft | null;
+//   status: 'pending' | 'accepted' | 'rejected'; created_at: string; decided_at: string | null;
+//   sources: ProposalSource[] }
+#[derive(Debug, Clone, Serialize)]
+pub struct ProposalRow {
+    pub id: i64,
+    pub note_id: i64,
+    pub ai_log_id: Option<i64>,
+    pub action: String,
+    // UPDATE: the reminder it changes. CREATE: the new reminder, once accepted
+    pub reminder_id: Option<i64>,
+    // What the AI suggested - or, once accepted, what was actually written
+    pub proposed: ReminderDraft,
+    // UPDATE only: the reminder as it was when the change was proposed
+    pub before: Option<ReminderDraft>,
+    pub status: String,
+    pub created_at: String,
+    pub decided_at: Option<String>,
+    pub sources: Vec<ProposalSource>,
+}
+
+const PROPOSAL_COLUMNS: &str = "id, note_id, ai_log_id, action, reminder_id, text, tags, due_date, due_time, due_timezone, \
+     recurrence, priority, before_text, before_tags, before_due_date, before_due_time, before_due_timezone, \
+     before_recurrence, before_priority, status, created_at, decided_at";
+
+impl ProposalRow {
+    // sources are filled in separately (see ProposalStore::with_sources)
+    fn from_row(row: &Row) -> rusqlite::Result<ProposalRow> {
+        let before_text: Option<String> = row.get("before_text")?;
+        Ok(ProposalRow {
+            id: row.get("id")?,
+            note_id: row.get("note_id")?,
+            ai_log_id: row.get("ai_log_id")?,
+            action: row.get("action")?,
+            reminder_id: row.get("reminder_id")?,
+            proposed: ReminderDraft {
+                text: row.get("text")?,
+                tags: row.get("tags")?,
+                due_date: row.get("due_date")?,
+                due_time: row.get("due_time")?,
+                timezone: row.get("due_timezone")?,
+                recurrence: row.get("recurrence")?,
+                priority: row.get("priority")?,
+            },
+            before: match before_text {
+                Some(text) => Some(ReminderDraft {
+                    text,
+                    tags: row.get("before_tags")?,
+                    due_date: row.get("before_due_date")?,
+                    due_time: row.get("before_due_time")?,
+                    timezone: row.get("before_due_timezone")?,
+                    recurrence: row.get("before_recurrence")?,
+                    priority: row.get("before_priority")?,
+                }),
+                None => None,
+            },
+            status: row.get("status")?,
+            created_at: row.get("created_at")?,
+            decided_at: row.get("decided_at")?,
+            sources: Vec::new(),
+        })
+    }
+}
+
+pub struct NewProposal<'a> {
+    pub note_id: i64,
+    pub ai_log_id: Option<i64>,
+    pub action: &'a str,
+    pub reminder_id: Option<i64>,
+    pub proposed: ReminderDraft,
+    pub before: Option<ReminderDraft>,
+    pub sources: Vec<&'a NoteSection>,
+}
+
+pub struct ProposalStore<'a> {
+    conn: &'a Connection,
+    // The timezone accepted due times are in (see ReminderStore::with_timezone)
+    timezone: Option<Tz>,
+}
+
+impl<'a> ProposalStore<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        ProposalStore { conn, timezone: None }
+    }
+
+    pub fn with_timezone(mut self, timezone: Tz) -> Self {
+        self.timezone = Some(timezone);
+        self
+    }
+
+    // Oldest first, so they're reviewed in the order they were suggested
+    pub fn pending(&self) -> Result<Vec<ProposalRow>, JuliError> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} FROM reminder_proposals WHERE status = 'pending' ORDER BY id",
+            PROPOSAL_COLUMNS
+        ))?;
+        let proposals = stmt
+            .query_map([], ProposalRow::from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        proposals.into_iter().map(|proposal| self.with_sources(proposal)).collect()
+    }
+
+    pub fn get(&self, proposal_id: i64) -> Result<ProposalRow, JuliError> {
+        let proposal = self
+            .conn
+            .query_row(
+                &format!("SELECT {} FROM reminder_proposals WHERE id = ?1", PROPOSAL_COLUMNS),
+                [proposal_id],
+                ProposalRow::from_row,
+            )
+            .map_err(|e| match e {
+                rusqlite::Error::QueryReturnedNoRows => not_found(proposal_id),
+                other => other.into(),
+            })?;
+        self.with_sources(proposal)
+    }
+
+    // Returns the new proposal's id
+    pub fn insert(&self, proposal: &NewProposal) -> Result<i64, JuliError> {
+        let before = proposal.before.as_ref();
+        self.conn.execute(
+            "INSERT INTO reminder_proposals
+               (note_id, ai_log_id, action, reminder_id, text, tags, due_date, due_time, due_timezone, recurrence, priority,
+                before_text, before_tags, before_due_date, before_due_time, before_due_timezone, before_recurrence,
+                before_priority)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18)",
+            rusqlite::params![
+                proposal.note_id,
+                proposal.ai_log_id,
+                proposal.action,
+                proposal.reminder_id,
+                &proposal.proposed.text,
+                &proposal.proposed.tags,
+                &proposal.proposed.due_date,
+                &proposal.proposed.due_time,
+                &proposal.proposed.timezone,
+                &proposal.proposed.recurrence,
+                &proposal.proposed.priority,
+                before.map(|b| &b.text),
+                before.and_then(|b| b.tags.as_ref()),
+                before.and_then(|b| b.due_date.as_ref()),
+                before.and_then(|b| b.due_time.as_ref()),
+                before.and_then(|b| b.timezone.as_ref()),
+                before.and_then(|b| b.recurrence.as_ref()),
+                before.and_then(|b| b.priority.as_ref()),
+            ],
+        )?;
+        let proposal_id = self.conn.last_insert_rowid();
+
+        for section in &proposal.sources {
+            self.conn.execute(
+                "INSERT INTO proposal_sources (proposal_id, start_line, end_line, excerpt) VALUES (?1, ?2, ?3, ?4)",
+                (proposal_id, section.start_line as i64, section.end_line as i64, &section.text),
+            )?;
+        }
+
+        Ok(proposal_id)
+    }
+
+    // Apply a pending proposal, with the user's edits if there are any
+    // Returns the id of the reminder that was created or updated
+    pub fn accept(&self, proposal_id: i64, edits: Option<ReminderDraft>) -> Result<i64, JuliError> {
+        let proposal = self.pending_proposal(proposal_id)?;
+        // The user's edits get the same checks as the AI's answer did (see core/validation.rs)
+        let mut draft = match edits {
+            Some(edits) => checked_draft(edits)?,
+            None => proposal.proposed.clone(),
+        };
+        // A priority the user changed while accepting is their own, like one from
+        // ReminderStore::set_priority: it wins over the stored one and the AI keeps it from then on
+        let chosen_priority = draft.priority.clone().filter(|priority| Some(priority) != proposal.proposed.priority.as_ref());
+
+        // The reminder, its sources and the proposal's new status are saved together or not at all,
+        // so a failed accept can simply be retried
+        let tx = self.conn.unchecked_transaction()?;
+        let reminders = match self.timezone {
+            Some(timezone) => ReminderStore::new(&tx).with_timezone(timezone),
+            None => ReminderStore::new(&tx),
+        };
+
+        let reminder_id = match (proposal.action.as_str(), proposal.reminder_id) {
+            ("CREATE", _) => reminders.create(proposal.note_id, &draft)?,
+            ("UPDATE", Some(reminder_id)) => {
+                // The reminder may have been deleted while the proposal was waiting
+                let current = reminders.get(reminder_id).map_err(|e| match e {
+                    JuliError::NotFound { .. } => JuliError::not_found(format!(
+                        "Reminder {} no longer exists - reject proposal {} instead",
+                        reminder_id, proposal_id
+                    )),
+                    e => e,
+                })?;
+                // Otherwise like the analyzer's updates: a priority the user set since the proposal
+                // was made wins, and no priority keeps the one it has
+                if chosen_priority.is_none() && (current.priority_set_by_user || draft.priority.is_none()) {
+                    draft.priority = Some(current.priority);
+                }
+                reminders.update(reminder_id, &draft)?;
+                reminder_id
+            }
+            (action, _) => {
+                return Err(JuliError::validation(format!("Proposal {} has an unknown action \"{}\"", proposal_id, action)))
+            }
+        };
+        if let Some(priority) = &chosen_priority {
+            reminders.set_priority(reminder_id, priority)?;
+        }
+
+        let sources = ReminderSourceStore::new(&tx);
+        for source in &proposal.sources {
+            sources.insert(&NewReminderSource {
+                reminder_id,
+                note_id: proposal.note_id,
+                ai_log_id: proposal.ai_log_id,
+                action: &proposal.action,
+                start_line: source.start_line as usize,
+                end_line: source.end_line as usize,
+                excerpt: &source.excerpt,
+            })?;
+        }
+
+        // Keep what was actually written; the AI's original answer is still in the AI log
+        tx.execute(
+            "UPDATE reminder_proposals
+             SET status = 'accepted', decided_at = strftime('%Y-%m-%dT%H:%M:%SZ', 'now'), reminder_id = ?1,
+                 text = ?2, tags = ?3, due_date = ?4, due_time = ?5, due_timezone = ?6, recurrence = ?7, priority = ?8
+             WHERE id = ?9",
+            rusqlite::params![
+                reminder_id,
+                &draft.text,
+                &draft.tags,
+                &draft.due_date,
+                &draft.due_time,
+                &draft.timezone,
+                &draft.recurrence,
+                &draft.priority,
+                proposal_id
+            ],
+        )?;
+
+        tx.commit()?;
+        Ok(reminder_id)
+    }
+
+    pub fn reject(&self, proposal_id: i64) -> Result<(), JuliError> {
+        self.pending_proposal(proposal_id)?;
+        self.conn.execute(
+            "UPDATE reminder_proposals SET status = 'rejected', decided_at = strftime('%Y-%m-%dT%H:%M:%SZ', 'now')
+             WHERE id = ?1",
+            [proposal_id],
+        )?;
+        Ok(())
+    }
+
+    // A proposal can only be decided once
+    fn pending_proposal(&self, proposal_id: i64) -> Result<ProposalRow, JuliError> {
+        let proposal = self.get(proposal_id)?;
+        if proposal.status != "pending" {
+            return Err(JuliError::validation(format!("Proposal {} was already {}", proposal_id, proposal.status)));
+        }
+        Ok(proposal)
+    }
+
+    fn with_sources(&self, mut proposal: ProposalRow) -> Result<ProposalRow, JuliError> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT start_line, end_line, excerpt FROM proposal_sources WHERE proposal_id = ?1 ORDER BY id")?;
+        proposal.sources = stmt
+            .query_map([proposal.id], |row| {
+                Ok(ProposalSource {
+                    start_line: row.get(0)?,
+                    end_line: row.get(1)?,
+                    excerpt: row.get(2)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(proposal)
+    }
+}
+
+fn not_found(proposal_id: i64) -> JuliError {
+    JuliError::not_found(format!("Proposal {} not found", proposal_id))
+}
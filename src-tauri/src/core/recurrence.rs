@@ -0,0 +1,423 @@
+// ============================================================================
+// RECURRING REMINDERS
+// ============================================================================
+
+// A reminder that repeats stores how it repeats as an RFC 5545 RRULE, the same
+// format calendar apps use, e.g. "FREQ=MONTHLY;BYMONTHDAY=1" for "on the 1st every month"
+// Resolving it creates the next occurrence as a new reminder (see ReminderStore::resolve),
+// so every completed one stays in the resolved list
+//
+// The supported subset:
+// - FREQ=DAILY|WEEKLY|MONTHLY|YEARLY   required
+// - INTERVAL=n      every n days/weeks/months/years (default 1)
+// - BYDAY=MO,WE     only on these weekdays; in a MONTHLY rule a weekday can have a
+//                   position: 1MO is the first Monday, -1FR the last Friday
+// - BYMONTHDAY=1    MONTHLY only: these days of the month (-1 is the last day)
+// - UNTIL=20261231  no occurrences after this date
+// - COUNT=n         n occurrences in all (can't be combined with UNTIL)
+// Like in RFC 5545, a date that doesn't exist is skipped rather than moved:
+// BYMONTHDAY=31 skips the months with 30 days, and Feb 29 repeats every 4 years
+//
+// Usage:
+// let rule = Recurrence::parse("FREQ=WEEKLY;BYDAY=MO,TU,WE,TH,FR")?;
+// let next = rule.next_after(due_date, occurrence, today); // Some((date, occurrence)) or None
+
+use std::fmt;
+
+use chrono::{Datelike, Days, Months, NaiveDate, Weekday};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Recurrence {
+    pub frequency: Frequency,
+    pub interval: u32,
+    // (position, weekday): position 0 means every such weekday, 1 the first, -1 the last
+    pub by_day: Vec<(i32, Weekday)>,
+    pub by_month_day: Vec<i32>,
+    pub until: Option<NaiveDate>,
+    pub count: Option<u32>,
+}
+
+// How far ahead to look for the next occurrence before giving up
+// (a rule like BYMONTHDAY=31 with INTERVAL=2 starting in an even month never matches)
+const MAX_PERIODS: u32 = 10_000;
+
+// Every 1000 days, weeks, months or years is already more than anyone means; a larger
+// INTERVAL would only overflow the date arithmetic in next_after
+const MAX_INTERVAL: u32 = 1000;
+
+const WEEKDAYS: [(&str, Weekday); 7] = [
+    ("MO", Weekday::Mon),
+    ("TU", Weekday::Tue),
+    ("WE", Weekday::Wed),
+    ("TH", Weekday::Thu),
+    ("FR", Weekday::Fri),
+    ("SA", Weekday::Sat),
+    ("SU", Weekday::Sun),
+];
+
+impl Recurrence {
+    // "FREQ=weekly;byday=mo,fr" or "RRULE:FREQ=WEEKLY;BYDAY=MO,FR"
+    // The error is a sentence saying what's wrong, like parse_due_date's
+    pub fn parse(rule: &str) -> Result<Recurrence, String> {
+        let trimmed = rule.trim();
+        let body = match trimmed.get(..6) {
+            Some(prefix) if prefix.eq_ignore_ascii_case("RRULE:") => &trimmed[6..],
+            _ => trimmed,
+        };
+
+        let mut frequency = None;
+        let mut interval = None;
+        let mut by_day = None;
+        let mut by_month_day = None;
+        let mut until = None;
+        let mut count = None;
+
+        for part in body.split(';').map(str::trim).filter(|p| !p.is_empty()) {
+            let (key, value) = part
+                .split_once('=')
+                .ok_or_else(|| format!("\"{}\" in the recurrence is not a KEY=VALUE pair", part))?;
+            let (key, value) = (key.trim().to_uppercase(), value.trim().to_uppercase());
+            let duplicate = match key.as_str() {
+                "FREQ" => frequency.replace(parse_frequency(&value)?).is_some(),
+                "INTERVAL" => interval.replace(parse_interval(&value)?).is_some(),
+                "BYDAY" => by_day.replace(parse_by_day(&value)?).is_some(),
+                "BYMONTHDAY" => by_month_day.replace(parse_by_month_day(&value)?).is_some(),
+                "UNTIL" => until.replace(parse_until(&value)?).is_some(),
+                "COUNT" => count.replace(parse_number(&key, &value)?).is_some(),
+                _ => return Err(format!("recurrence part {} is not supported", key)),
+            };
+            if duplicate {
+                return Err(format!("recurrence has {} twice", key));
+            }
+        }
+
+        let frequency = frequency.ok_or("recurrence needs a FREQ (DAILY, WEEKLY, MONTHLY or YEARLY)")?;
+        let by_day: Vec<(i32, Weekday)> = by_day.unwrap_or_default();
+        let by_month_day: Vec<i32> = by_month_day.unwrap_or_default();
+
+        if until.is_some() && count.is_some() {
+            return Err("recurrence can have UNTIL or COUNT, not both".to_string());
+        }
+        if frequency != Frequency::Monthly && !by_month_day.is_empty() {
+            return Err("BYMONTHDAY only works with FREQ=MONTHLY".to_string());
+        }
+        if frequency == Frequency::Yearly && !by_day.is_empty() {
+            return Err("BYDAY doesn't work with FREQ=YEARLY".to_string());
+        }
+        if frequency != Frequency::Monthly && by_day.iter().any(|(position, _)| *position != 0) {
+            return Err("BYDAY positions like 1MO only work with FREQ=MONTHLY".to_string());
+        }
+
+        Ok(Recurrence {
+            frequency,
+            interval: interval.unwrap_or(1),
+            by_day,
+            by_month_day,
+            until,
+            count,
+        })
+    }
+
+    // The occurrence that follows `current` (the due date of occurrence number `occurrence`),
+    // skipping any that are already before `not_before` - resolving a daily reminder a week
+    // late gives today's, not the six that were missed
+    // Returns the date and its occurrence number, or None when the series is over
+    pub fn next_after(&self, current: NaiveDate, occurrence: i64, not_before: NaiveDate) -> Option<(NaiveDate, i64)> {
+        let mut occurrence = occurrence;
+        for period in 0..MAX_PERIODS {
+            // Overflowing the step means the next date is past anything a date can hold
+            let step = period.checked_mul(self.interval)?;
+            for date in self.dates_in_period(current, step) {
+                if date <= current {
+                    continue;
+                }
+                if self.until.is_some_and(|until| date > until) {
+                    return None;
+                }
+                occurrence += 1;
+                if self.count.is_some_and(|count| occurrence > i64::from(count)) {
+                    return None;
+                }
+                if date >= not_before {
+                    return Some((date, occurrence));
+                }
+            }
+        }
+        None
+    }
+
+    // Every date the rule allows in the day/week/month/year that is `step` periods
+    // after the one `anchor` is in, in order
+    fn dates_in_period(&self, anchor: NaiveDate, step: u32) -> Vec<NaiveDate> {
+        let on_listed_weekday = |date: &NaiveDate| {
+            self.by_day.is_empty() || self.by_day.iter().any(|(_, weekday)| *weekday == date.weekday())
+        };
+
+        let mut dates: Vec<NaiveDate> = match self.frequency {
+            Frequency::Daily => anchor
+                .checked_add_days(Days::new(step.into()))
+                .into_iter()
+                .filter(on_listed_weekday)
+                .collect(),
+            Frequency::Weekly => {
+                let monday = anchor.checked_sub_days(Days::new(anchor.weekday().num_days_from_monday().into()));
+                let Some(monday) = monday.and_then(|m| m.checked_add_days(Days::new(u64::from(step) * 7))) else {
+                    return Vec::new();
+                };
+                let weekdays: Vec<Weekday> = if self.by_day.is_empty() {
+                    vec![anchor.weekday()]
+                } else {
+                    self.by_day.iter().map(|(_, weekday)| *weekday).collect()
+                };
+                weekdays
+                    .into_iter()
+                    .filter_map(|weekday| monday.checked_add_days(Days::new(weekday.num_days_from_monday().into())))
+                    .collect()
+            }
+            Frequency::Monthly => {
+                let Some(first) = anchor.with_day(1).and_then(|d| d.checked_add_months(Months::new(step))) else {
+                    return Vec::new();
+                };
+                self.days_in_month(anchor, first)
+            }
+            Frequency::Yearly => i32::try_from(step)
+                .ok()
+                .and_then(|step| anchor.year().checked_add(step))
+                .and_then(|year| NaiveDate::from_ymd_opt(year, anchor.month(), anchor.day()))
+                .into_iter()
+                .collect(),
+        };
+
+        dates.sort();
+        dates.dedup();
+        dates
+    }
+
+    // The days of the month starting at `first` that a MONTHLY rule picks
+    fn days_in_month(&self, anchor: NaiveDate, first: NaiveDate) -> Vec<NaiveDate> {
+        let days_in_month = first.checked_add_months(Months::new(1)).map_or(31, |next| (next - first).num_days() as i32);
+        let day = |n: i32| first.with_day(n as u32).filter(|_| n >= 1 && n <= days_in_month);
+
+        // Without BYMONTHDAY or BYDAY: the same day of the month as the reminder
+        if self.by_month_day.is_empty() && self.by_day.is_empty() {
+            return day(anchor.day() as i32).into_iter().collect();
+        }
+
+        // BYMONTHDAY, narrowed down by BYDAY when there's both ("Friday the 13th")
+        if !self.by_month_day.is_empty() {
+            return self
+                .by_month_day
+                .iter()
+                .filter_map(|&n| day(if n < 0 { days_in_month + n + 1 } else { n }))
+                .filter(|date| self.by_day.is_empty() || self.by_day.iter().any(|(_, weekday)| *weekday == date.weekday()))
+                .collect();
+        }
+
+        let mut dates = Vec::new();
+        for &(position, weekday) in &self.by_day {
+            let matching: Vec<NaiveDate> = (1..=days_in_month)
+                .filter_map(day)
+                .filter(|date| date.weekday() == weekday)
+                .collect();
+            match position {
+                0 => dates.extend(matching),
+                p if p > 0 => dates.extend(matching.get(p as usize - 1)),
+                p => dates.extend(matching.len().checked_sub(p.unsigned_abs() as usize).map(|i| matching[i])),
+            }
+        }
+        dates
+    }
+
+    // In words, for the CLI and the reminders list: "every 2 weeks on Mon, Fri"
+    pub fn describe(&self) -> String {
+        let unit = match self.frequency {
+            Frequency::Daily => "day",
+            Frequency::Weekly => "week",
+            Frequency::Monthly => "month",
+            Frequency::Yearly => "year",
+        };
+        let weekdays_only = self.by_day.len() == 5
+            && self.by_day.iter().all(|(position, weekday)| *position == 0 && weekday.num_days_from_monday() < 5);
+
+        let mut text = if weekdays_only && self.interval == 1 && self.frequency != Frequency::Monthly {
+            "every weekday".to_string()
+        } else {
+            let mut text = match self.interval {
+                1 => format!("every {}", unit),
+                n => format!("every {} {}s", n, unit),
+            };
+            if !self.by_month_day.is_empty() {
+                let days: Vec<String> = self.by_month_day.iter().map(|&n| describe_month_day(n)).collect();
+                text.push_str(&format!(" on {}", days.join(", ")));
+            }
+            if !self.by_day.is_empty() {
+                let days: Vec<String> = self.by_day.iter().map(|&(p, weekday)| describe_weekday(p, weekday)).collect();
+                text.push_str(&format!(" on {}", days.join(", ")));
+            }
+            text
+        };
+
+        if let Some(until) = self.until {
+            text.push_str(&format!(" until {}", until));
+        }
+        if let Some(count) = self.count {
+            text.push_str(&format!(", {} times", count));
+        }
+        text
+    }
+}
+
+// The canonical form stored in the database: upper case, parts in a fixed order,
+// INTERVAL only when it isn't 1
+impl fmt::Display for Recurrence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let frequency = match self.frequency {
+            Frequency::Daily => "DAILY",
+            Frequency::Weekly => "WEEKLY",
+            Frequency::Monthly => "MONTHLY",
+            Frequency::Yearly => "YEARLY",
+        };
+        write!(f, "FREQ={}", frequency)?;
+        if self.interval != 1 {
+            write!(f, ";INTERVAL={}", self.interval)?;
+        }
+        if !self.by_day.is_empty() {
+            let days: Vec<String> = self
+                .by_day
+                .iter()
+                .map(|&(position, weekday)| match position {
+                    0 => weekday_code(weekday).to_string(),
+                    p => format!("{}{}", p, weekday_code(weekday)),
+                })
+                .collect();
+            write!(f, ";BYDAY={}", days.join(","))?;
+        }
+        if !self.by_month_day.is_empty() {
+            let days: Vec<String> = self.by_month_day.iter().map(i32::to_string).collect();
+            write!(f, ";BYMONTHDAY={}", days.join(","))?;
+        }
+        if let Some(until) = self.until {
+            write!(f, ";UNTIL={}", until.format("%Y%m%d"))?;
+        }
+        if let Some(count) = self.count {
+            write!(f, ";COUNT={}", count)?;
+        }
+        Ok(())
+    }
+}
+
+fn parse_frequency(value: &str) -> Result<Frequency, String> {
+    match value {
+        "DAILY" => Ok(Frequency::Daily),
+        "WEEKLY" => Ok(Frequency::Weekly),
+        "MONTHLY" => Ok(Frequency::Monthly),
+        "YEARLY" => Ok(Frequency::Yearly),
+        other => Err(format!("FREQ={} is not supported (use DAILY, WEEKLY, MONTHLY or YEARLY)", other)),
+    }
+}
+
+// INTERVAL and COUNT: a whole number, at least 1
+fn parse_number(key: &str, value: &str) -> Result<u32, String> {
+    match value.parse::<u32>() {
+        Ok(n) if n >= 1 => Ok(n),
+        _ => Err(format!("{}={} is not a number of at least 1", key, value)),
+    }
+}
+
+// INTERVAL: a whole number from 1 to MAX_INTERVAL
+fn parse_interval(value: &str) -> Result<u32, String> {
+    match parse_number("INTERVAL", value)? {
+        n if n <= MAX_INTERVAL => Ok(n),
+        _ => Err(format!("INTERVAL={} is too large (at most {})", value, MAX_INTERVAL)),
+    }
+}
+
+// "MO,WE" or "1MO,-1FR"
+fn parse_by_day(value: &str) -> Result<Vec<(i32, Weekday)>, String> {
+    let mut days = Vec::new();
+    for item in value.split(',').map(str::trim) {
+        // Match the code at the end rather than slicing off two bytes: the rule may come
+        // from the AI, and "MÖ" would split in the middle of a character
+        let (position, weekday) = WEEKDAYS
+            .iter()
+            .find_map(|(name, weekday)| item.strip_suffix(name).map(|position| (position, *weekday)))
+            .ok_or_else(|| format!("BYDAY={} has an unknown weekday \"{}\" (use MO, TU, WE, TH, FR, SA or SU)", value, item))?;
+        let position = match position {
+            "" => 0,
+            p => match p.parse::<i32>() {
+                Ok(n) if n != 0 && n.abs() <= 5 => n,
+                _ => return Err(format!("BYDAY={} has an invalid position \"{}\"", value, item)),
+            },
+        };
+        if !days.contains(&(position, weekday)) {
+            days.push((position, weekday));
+        }
+    }
+    Ok(days)
+}
+
+// "1", "15,-1"
+fn parse_by_month_day(value: &str) -> Result<Vec<i32>, String> {
+    let mut days = Vec::new();
+    for item in value.split(',').map(str::trim) {
+        match item.parse::<i32>() {
+            Ok(n) if n != 0 && n.abs() <= 31 => {
+                if !days.contains(&n) {
+                    days.push(n);
+                }
+            }
+            _ => return Err(format!("BYMONTHDAY={} has an invalid day \"{}\"", value, item)),
+        }
+    }
+    Ok(days)
+}
+
+// "20261231", "20261231T235959Z" (only the date counts) or "2026-12-31"
+fn parse_until(value: &str) -> Result<NaiveDate, String> {
+    let date = value.split('T').next().unwrap_or(value);
+    NaiveDate::parse_from_str(date, "%Y%m%d")
+        .or_else(|_| NaiveDate::parse_from_str(date, "%Y-%m-%d"))
+        .map_err(|_| format!("UNTIL={} is not a date like 20261231", value))
+}
+
+fn weekday_code(weekday: Weekday) -> &'static str {
+    WEEKDAYS.iter().find(|(_, w)| *w == weekday).map(|(name, _)| *name).unwrap_or("MO")
+}
+
+// "day 1", "the last day", "the 2nd to last day"
+fn describe_month_day(n: i32) -> String {
+    match n {
+        -1 => "the last day".to_string(),
+        n if n < 0 => format!("the {} to last day", ordinal(-n)),
+        n => format!("day {}", n),
+    }
+}
+
+// "Mon", "the first Mon", "the last Fri"
+fn describe_weekday(position: i32, weekday: Weekday) -> String {
+    match position {
+        0 => weekday.to_string(),
+        -1 => format!("the last {}", weekday),
+        p if p < 0 => format!("the {} to last {}", ordinal(-p), weekday),
+        p => format!("the {} {}", ordinal(p), weekday),
+    }
+}
+
+fn ordinal(n: i32) -> String {
+    let suffix = match (n % 10, n % 100) {
+        (1, 11) | (2, 12) | (3, 13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{}{}", n, suffix)
+}
@@ -0,0 +1,554 @@
+// ============================================================================
+// REMINDERS
+// ============================================================================
+
+use chrono::{DateTime, NaiveDate, NaiveTime};
+use chrono_tz::Tz;
+use std::str::FromStr;
+
+use rusqlite::{Connection, OptionalExtension, Row};
+use serde::{Deserialize, Deserializer, Serialize};
+
+use super::recurrence::Recurrence;
+use super::snooze::snooze_date;
+use super::time::{due_at, format_due_at, parse_timezone, system_timezone, today_in};
+use super::validation::{checked_draft, parse_priority};
+use crate::error::JuliError;
+
+#[derive(Debug, Serialize)]
+pub struct ReminderRow {
+    pub id: i64,
+    pub text: String,
+    pub resolved: bool,
+    // None for a reminder added by hand
+    pub created_from_note_id: Option<i64>,
+    pub tags: Option<String>,
+    pub created_at: Option<String>,
+    // The day it's due, in `timezone` when it has a time
+    pub due_date: Option<String>,
+    // The time of day it's due, "15:00"
+    pub due_time: Option<String>,
+    // The moment it's due, "2026-10-16T15:00:00-04:00" - only when it has a due_time
+    pub due_at: Option<String>,
+    // The IANA timezone due_time is in, like "America/New_York"
+    pub timezone: Option<String>,
+    // Who made the reminder: "ai" (extracted from a note) or "manual"
+    pub source: String,
+    // An RRULE like "FREQ=MONTHLY;BYMONTHDAY=1" for a reminder that repeats (see core/recurrence.rs)
+    pub recurrence: Option<String>,
+    // The recurrence in words, e.g. "every month on day 1"
+    pub repeats: Option<String>,
+    // Which occurrence of the series this is, 1 for the first
+    pub occurrence: i64,
+    // The previous occurrence, which was resolved to create this one
+    pub recurs_from_id: Option<i64>,
+    // How many times it was snoozed (see ReminderStore::snooze)
+    pub snooze_count: i64,
+    // The due date it had before it was first snoozed
+    pub original_due_date: Option<String>,
+    // "low", "normal", "high" or "urgent"
+    pub priority: String,
+    // The user picked the priority, so the AI leaves it alone (see ReminderStore::set_priority)
+    pub priority_set_by_user: bool,
+}
+
+const REMINDER_COLUMNS: &str = "id, created_from_note_id, text, resolved, tags, created_at, due_date, due_at, due_timezone, \
+     source, recurrence, occurrence, recurs_from_id, snooze_count, original_due_date, priority, priority_set_by_user";
+
+// The fields of a reminder the user (or a proposal) writes
+// In TypeScript:
+// interface ReminderDraft { text: string; tags: string | null; due_date: string | null; due_time?: string | null;
+//   timezone?: string | null; recurrence?: string | null; priority?: 'low' | 'normal' | 'high' | 'urgent' | null }
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReminderDraft {
+    pub text: String,
+    #[serde(default)]
+    pub tags: Option<String>,
+    #[serde(default)]
+    pub due_date: Option<String>,
+    // "15:00" on due_date; needs a due_date
+    #[serde(default)]
+    pub due_time: Option<String>,
+    // The IANA timezone due_time is in; None for the user's own (see ReminderStore::with_timezone)
+    #[serde(default)]
+    pub timezone: Option<String>,
+    #[serde(default)]
+    pub recurrence: Option<String>,
+    // None is "normal"
+    #[serde(default)]
+    pub priority: Option<String>,
+}
+
+// A partial edit: only the fields that are present change
+// In TypeScript:
+// interface ReminderPatch { text?: string; tags?: string | null; due_date?: string | null; due_time?: string | null;
+//   recurrence?: string | null; priority?: 'low' | 'normal' | 'high' | 'urgent' }
+// Everything but text can be cleared, which is why those are an Option of an Option:
+// missing = None (keep it), null = Some(None) (clear it), "work" = Some(Some("work"))
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct ReminderPatch {
+    #[serde(default)]
+    pub text: Option<String>,
+    #[serde(default, deserialize_with = "present")]
+    pub tags: Option<Option<String>>,
+    #[serde(default, deserialize_with = "present")]
+    pub due_date: Option<Option<String>>,
+    #[serde(default, deserialize_with = "present")]
+    pub due_time: Option<Option<String>>,
+    #[serde(default, deserialize_with = "present")]
+    pub recurrence: Option<Option<String>>,
+    // Setting it counts as the user's choice (see ReminderStore::set_priority)
+    #[serde(default)]
+    pub priority: Option<String>,
+}
+
+// How a list of reminders is ordered
+// In TypeScript: type ReminderSort = 'due' | 'priority' | 'created'
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReminderSort {
+    // Soonest due first, undated ones last; the same day goes by priority
+    #[default]
+    Due,
+    // Most pressing first, then by due date
+    Priority,
+    // Newest first
+    Created,
+}
+
+impl FromStr for ReminderSort {
+    type Err = String;
+
+    fn from_str(sort: &str) -> Result<Self, Self::Err> {
+        match sort.trim().to_lowercase().as_str() {
+            "due" => Ok(ReminderSort::Due),
+            "priority" => Ok(ReminderSort::Priority),
+            "created" => Ok(ReminderSort::Created),
+            other => Err(format!("Can't sort reminders by \"{}\": use due, priority or created", other)),
+        }
+    }
+}
+
+impl ReminderSort {
+    // The ORDER BY for list()
+    // julianday() takes the offset in due_at into account, so mixed timezones sort correctly
+    fn order_by(self) -> String {
+        let due = "due_date IS NULL, due_date, due_at IS NULL, julianday(due_at)";
+        let created = "created_at IS NULL, created_at";
+        match self {
+            ReminderSort::Due => format!("{}, {}, {}, id", due, PRIORITY_RANK, created),
+            ReminderSort::Priority => format!("{}, {}, {}, id", PRIORITY_RANK, due, created),
+            ReminderSort::Created => "created_at IS NULL, created_at DESC, id DESC".to_string(),
+        }
+    }
+}
+
+// 0 for urgent to 3 for low, so ORDER BY puts the most pressing first
+const PRIORITY_RANK: &str = "CASE priority WHEN 'urgent' THEN 0 WHEN 'high' THEN 1 WHEN 'normal' THEN 2 ELSE 3 END";
+
+// Only called for fields that are in the JSON, so a null becomes Some(None) instead of None
+fn present<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    T::deserialize(deserializer).map(Some)
+}
+
+impl ReminderRow {
+    fn from_row(row: &Row) -> rusqlite::Result<ReminderRow> {
+        let recurrence: Option<String> = row.get("recurrence")?;
+        let due_at: Option<String> = row.get("due_at")?;
+        Ok(ReminderRow {
+            id: row.get("id")?,
+            created_from_note_id: row.get("created_from_note_id")?,
+            text: row.get("text")?,
+            resolved: row.get("resolved")?,
+            tags: row.get("tags")?,
+            created_at: row.get("created_at")?,
+            due_date: row.get("due_date")?,
+            // due_at carries the offset of its timezone, so its own clock time is the local one
+            due_time: due_at
+                .as_deref()
+                .and_then(|at| DateTime::parse_from_rfc3339(at).ok())
+                .map(|at| at.format("%H:%M").to_string()),
+            due_at,
+            timezone: row.get("due_timezone")?,
+            source: row.get("source")?,
+            repeats: recurrence.as_deref().and_then(|rule| Recurrence::parse(rule).ok()).map(|rule| rule.describe()),
+            recurrence,
+            occurrence: row.get("occurrence")?,
+            recurs_from_id: row.get("recurs_from_id")?,
+            snooze_count: row.get("snooze_count")?,
+            original_due_date: row.get("original_due_date")?,
+            priority: row.get("priority")?,
+            priority_set_by_user: row.get("priority_set_by_user")?,
+        })
+    }
+}
+
+pub struct ReminderStore<'a> {
+    conn: &'a Connection,
+    // What due times and "today" mean; the system's timezone unless set
+    timezone: Option<Tz>,
+    // How all(), unresolved() and resolved() are ordered
+    sort: ReminderSort,
+}
+
+impl<'a> ReminderStore<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        ReminderStore { conn, timezone: None, sort: ReminderSort::default() }
+    }
+
+    // Usage: ReminderStore::new(&conn).with_timezone(config.timezone())
+    pub fn with_timezone(mut self, timezone: Tz) -> Self {
+        self.timezone = Some(timezone);
+        self
+    }
+
+    // Usage: ReminderStore::new(&conn).with_sort(ReminderSort::Priority).unresolved()
+    pub fn with_sort(mut self, sort: ReminderSort) -> Self {
+        self.sort = sort;
+        self
+    }
+
+    fn timezone(&self) -> Tz {
+        self.timezone.unwrap_or_else(system_timezone)
+    }
+
+    pub fn all(&self) -> Result<Vec<ReminderRow>, JuliError> {
+        self.list("1 = 1")
+    }
+
+    pub fn unresolved(&self) -> Result<Vec<ReminderRow>, JuliError> {
+        self.list("resolved = 0")
+    }
+
+    pub fn resolved(&self) -> Result<Vec<ReminderRow>, JuliError> {
+        self.list("resolved = 1")
+    }
+
+    pub fn get(&self, reminder_id: i64) -> Result<ReminderRow, JuliError> {
+        self.conn
+            .query_row(
+                &format!("SELECT {} FROM reminders WHERE id = ?1", REMINDER_COLUMNS),
+                [reminder_id],
+                ReminderRow::from_row,
+            )
+            .map_err(|e| match e {
+                rusqlite::Error::QueryReturnedNoRows => not_found(reminder_id),
+                other => other.into(),
+            })
+    }
+
+    // Returns the new reminder's id
+    // The draft is written as it is - check anything that didn't come through
+    // core/validation.rs first (see create_manual)
+    pub fn create(&self, note_id: i64, draft: &ReminderDraft) -> Result<i64, JuliError> {
+        self.insert(Some(note_id), draft, "ai")
+    }
+
+    // A reminder the user wrote themselves, not tied to any note
+    // It's checked like the AI's reminders (see core/validation.rs) and then sits in the
+    // list the analysis prompt compares against, so the AI updates it instead of adding a copy
+    // Returns the new reminder's id
+    // A priority given here is the user's choice, which the AI leaves alone
+    pub fn create_manual(&self, draft: ReminderDraft) -> Result<i64, JuliError> {
+        let draft = checked_draft(draft)?;
+        self.insert(None, &draft, "manual")
+    }
+
+    fn insert(&self, note_id: Option<i64>, draft: &ReminderDraft, source: &str) -> Result<i64, JuliError> {
+        let (due_at, due_timezone) = self.due_at(draft);
+        let priority_set_by_user = source == "manual" && draft.priority.is_some();
+        self.conn.execute(
+            "INSERT INTO reminders
+               (created_from_note_id, text, tags, due_date, due_at, due_timezone, recurrence, priority, priority_set_by_user,
+                source, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))",
+            (
+                note_id,
+                &draft.text,
+                &draft.tags,
+                &draft.due_date,
+                due_at,
+                due_timezone,
+                &draft.recurrence,
+                draft.priority.as_deref().unwrap_or("normal"),
+                priority_set_by_user,
+                source,
+            ),
+        )?;
+        Ok(self.conn.last_insert_rowid())
+    }
+
+    // Change some of a reminder's fields by hand, whoever created it
+    // The result is checked as a whole, so a bad due date fails even if only the text changed
+    // A new date or time is in the user's timezone; otherwise the reminder keeps the one it was set in
+    // Returns the reminder as it is now
+    pub fn edit(&self, reminder_id: i64, patch: ReminderPatch) -> Result<ReminderRow, JuliError> {
+        let current = self.get(reminder_id)?;
+        let moved = patch.due_date.is_some() || patch.due_time.is_some();
+        let tx = self.conn.unchecked_transaction()?;
+        let reminders = ReminderStore { conn: &tx, ..*self };
+        let draft = checked_draft(ReminderDraft {
+            text: patch.text.unwrap_or(current.text),
+            tags: patch.tags.unwrap_or(current.tags),
+            due_date: patch.due_date.unwrap_or(current.due_date),
+            due_time: patch.due_time.unwrap_or(current.due_time),
+            timezone: if moved { None } else { current.timezone },
+            recurrence: patch.recurrence.unwrap_or(current.recurrence),
+            priority: Some(current.priority),
+        })?;
+        reminders.update(reminder_id, &draft)?;
+        // A date set by hand is the reminder's real date now: it's no longer snoozed, and a
+        // recurring one's next occurrence follows it rather than the date before the snooze
+        if moved {
+            tx.execute("UPDATE reminders SET snooze_count = 0, original_due_date = NULL WHERE id = ?1", (reminder_id,))?;
+        }
+        if let Some(priority) = patch.priority {
+            reminders.set_priority(reminder_id, &priority)?;
+        }
+        tx.commit()?;
+        self.get(reminder_id)
+    }
+
+    // Overwrite text, tags, due date and time, recurrence and priority (None is "normal")
+    // Returns false when no reminder has that id
+    pub fn update(&self, reminder_id: i64, draft: &ReminderDraft) -> Result<bool, JuliError> {
+        let (due_at, due_timezone) = self.due_at(draft);
+        let changed = self.conn.execute(
+            "UPDATE reminders SET text = ?1, tags = ?2, due_date = ?3, due_at = ?4, due_timezone = ?5, recurrence = ?6, priority = ?7
+             WHERE id = ?8",
+            (
+                &draft.text,
+                &draft.tags,
+                &draft.due_date,
+                due_at,
+                due_timezone,
+                &draft.recurrence,
+                draft.priority.as_deref().unwrap_or("normal"),
+                reminder_id,
+            ),
+        )?;
+        Ok(changed > 0)
+    }
+
+    // The user's own priority for a reminder: "low", "normal", "high", "urgent" (or "p1" to "p4")
+    // From then on the AI's updates keep it (see core/analyzer.rs), whatever the note says
+    // An unknown priority is a Validation error
+    // Returns the reminder as it is now
+    pub fn set_priority(&self, reminder_id: i64, priority: &str) -> Result<ReminderRow, JuliError> {
+        let priority = parse_priority(Some(priority))
+            .map_err(JuliError::validation)?
+            .ok_or_else(|| JuliError::validation("Give a priority: low, normal, high or urgent"))?;
+        let changed = self.conn.execute(
+            "UPDATE reminders SET priority = ?1, priority_set_by_user = 1 WHERE id = ?2",
+            (priority, reminder_id),
+        )?;
+        ensure_changed(changed, reminder_id)?;
+        self.get(reminder_id)
+    }
+
+    // due_at and due_timezone for a draft: both None unless it has a due date and time
+    fn due_at(&self, draft: &ReminderDraft) -> (Option<String>, Option<String>) {
+        let date = draft.due_date.as_deref().and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok());
+        let time = draft.due_time.as_deref().and_then(|t| NaiveTime::parse_from_str(t, "%H:%M").ok());
+        let (Some(date), Some(time)) = (date, time) else {
+            return (None, None);
+        };
+        let timezone = draft
+            .timezone
+            .as_deref()
+            .and_then(|name| parse_timezone(name).ok())
+            .unwrap_or_else(|| self.timezone());
+        (Some(format_due_at(due_at(date, time, timezone))), Some(timezone.name().to_string()))
+    }
+
+    // Push a reminder out to a later date: "2026-10-30", "1d", "next monday", "eow" (see core/snooze.rs)
+    // The time of day and timezone stay, snooze_count goes up and original_due_date keeps the
+    // due date from before the first snooze. Notifications wait until the new date
+    // A `when` we can't read, or one that isn't later, is a Validation error
+    // Returns the reminder as it is now
+    pub fn snooze(&self, reminder_id: i64, when: &str) -> Result<ReminderRow, JuliError> {
+        self.snooze_on(reminder_id, when, today_in(self.timezone()))
+    }
+
+    // snooze() as if it were `today`
+    pub fn snooze_on(&self, reminder_id: i64, when: &str, today: NaiveDate) -> Result<ReminderRow, JuliError> {
+        let reminder = self.get(reminder_id)?;
+        if reminder.resolved {
+            return Err(JuliError::validation(format!("Reminder {} is resolved, there's nothing to snooze", reminder_id)));
+        }
+        let due_date = reminder.due_date.as_deref().and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok());
+        let until = snooze_date(when, today, due_date).map_err(JuliError::validation)?;
+
+        let until = until.format("%Y-%m-%d").to_string();
+        let (due_at, due_timezone) = self.due_at(&ReminderDraft {
+            due_date: Some(until.clone()),
+            due_time: reminder.due_time.clone(),
+            timezone: reminder.timezone.clone(),
+            ..Default::default()
+        });
+        self.conn.execute(
+            "UPDATE reminders
+             SET due_date = ?1, due_at = ?2, due_timezone = ?3, snooze_count = snooze_count + 1,
+                 original_due_date = COALESCE(original_due_date, due_date)
+             WHERE id = ?4",
+            (until, due_at, due_timezone, reminder_id),
+        )?;
+        self.get(reminder_id)
+    }
+
+    // Mark a reminder done
+    // For a recurring reminder the next occurrence is created as a new reminder and
+    // returned, and the resolved one stays in the resolved list as a record
+    pub fn resolve(&self, reminder_id: i64) -> Result<Option<i64>, JuliError> {
+        self.resolve_on(reminder_id, today_in(self.timezone()))
+    }
+
+    // resolve() as if it were `today` - occurrences before today are skipped, so resolving
+    // a daily reminder a week late gives one for today rather than a week of catching up
+    pub fn resolve_on(&self, reminder_id: i64, today: NaiveDate) -> Result<Option<i64>, JuliError> {
+        let tx = self.conn.unchecked_transaction()?;
+        let reminders = ReminderStore { conn: &tx, ..*self };
+        let reminder = reminders.get(reminder_id)?;
+
+        // Resolving it a second time changes nothing: resolved_at stays, no change event goes
+        // out and no second occurrence is started
+        let changed = tx.execute(
+            "UPDATE reminders SET resolved = 1, resolved_at = strftime('%Y-%m-%dT%H:%M:%SZ', 'now')
+             WHERE id = ?1 AND resolved = 0",
+            (reminder_id,),
+        )?;
+        if changed == 0 {
+            return Ok(None);
+        }
+        let next_id = reminders.spawn_next(&reminder, today)?;
+
+        tx.commit()?;
+        Ok(next_id)
+    }
+
+    // Undo a resolve; the occurrence it created (if still open) goes away again
+    pub fn unresolve(&self, reminder_id: i64) -> Result<(), JuliError> {
+        let tx = self.conn.unchecked_transaction()?;
+        let changed = tx.execute(
+            "UPDATE reminders SET resolved = 0, resolved_at = NULL WHERE id = ?1",
+            (reminder_id,),
+        )?;
+        ensure_changed(changed, reminder_id)?;
+        tx.execute("DELETE FROM reminders WHERE recurs_from_id = ?1 AND resolved = 0", (reminder_id,))?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    // The occurrence after `reminder`: same note, text, tags, time of day, recurrence and priority, the next due date
+    // None when the reminder doesn't repeat or its series is over
+    fn spawn_next(&self, reminder: &ReminderRow, today: NaiveDate) -> Result<Option<i64>, JuliError> {
+        let Some(rule) = reminder.recurrence.as_deref() else {
+            return Ok(None);
+        };
+        let rule = match Recurrence::parse(rule) {
+            Ok(rule) => rule,
+            Err(e) => {
+                println!("⚠️ Reminder {} has a recurrence we can't read ({}), not repeating it", reminder.id, e);
+                return Ok(None);
+            }
+        };
+        // Without a due date the series counts from the day it was resolved
+        // A snoozed occurrence counts from where it was before, so the series keeps its dates
+        let current = reminder
+            .original_due_date
+            .as_deref()
+            .or(reminder.due_date.as_deref())
+            .and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+            .unwrap_or(today);
+        let Some((due_date, occurrence)) = rule.next_after(current, reminder.occurrence, today) else {
+            return Ok(None);
+        };
+
+        // The next occurrence is only created once: unresolve() removes it while it's still open,
+        // but once it has been resolved too, resolving this one again must not start a second copy
+        let existing: Option<i64> = self
+            .conn
+            .query_row("SELECT id FROM reminders WHERE recurs_from_id = ?1", [reminder.id], |row| row.get(0))
+            .optional()?;
+        if existing.is_some() {
+            return Ok(None);
+        }
+
+        // Same wall-clock time in the same timezone, even across a daylight saving change
+        let (due_at, due_timezone) = self.due_at(&ReminderDraft {
+            due_date: Some(due_date.format("%Y-%m-%d").to_string()),
+            due_time: reminder.due_time.clone(),
+            timezone: reminder.timezone.clone(),
+            ..Default::default()
+        });
+        self.conn.execute(
+            "INSERT INTO reminders
+               (created_from_note_id, text, tags, due_date, due_at, due_timezone, recurrence, occurrence, recurs_from_id,
+                priority, priority_set_by_user, source, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))",
+            rusqlite::params![
+                reminder.created_from_note_id,
+                &reminder.text,
+                &reminder.tags,
+                due_date.format("%Y-%m-%d").to_string(),
+                due_at,
+                due_timezone,
+                rule.to_string(),
+                occurrence,
+                reminder.id,
+                &reminder.priority,
+                reminder.priority_set_by_user,
+                &reminder.source,
+            ],
+        )?;
+        let next_id = self.conn.last_insert_rowid();
+
+        // The new occurrence comes from the same lines of the same notes
+        self.conn.execute(
+            "INSERT INTO reminder_sources (reminder_id, note_id, ai_log_id, action, start_line, end_line, excerpt, created_at)
+             SELECT ?1, note_id, ai_log_id, action, start_line, end_line, excerpt, created_at
+             FROM reminder_sources WHERE reminder_id = ?2",
+            (next_id, reminder.id),
+        )?;
+
+        Ok(Some(next_id))
+    }
+
+    pub fn delete(&self, reminder_id: i64) -> Result<(), JuliError> {
+        let changed = self.conn.execute("DELETE FROM reminders WHERE id = ?1", (reminder_id,))?;
+        ensure_changed(changed, reminder_id)
+    }
+
+    // `filter` is always one of our own constant SQL snippets, never user input
+    // The order is the store's ReminderSort
+    fn list(&self, filter: &str) -> Result<Vec<ReminderRow>, JuliError> {
+        let sql = format!(
+            "SELECT {} FROM reminders WHERE {} ORDER BY {}",
+            REMINDER_COLUMNS,
+            filter,
+            self.sort.order_by()
+        );
+        let mut stmt = self.conn.prepare(&sql)?;
+        let reminders = stmt
+            .query_map([], ReminderRow::from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(reminders)
+    }
+}
+
+fn not_found(reminder_id: i64) -> JuliError {
+    JuliError::not_found(format!("Reminder {} not found", reminder_id))
+}
+
+// execute() returns how many rows it touched - zero means the id doesn't exist
+fn ensure_changed(changed: usize, reminder_id: i64) -> Result<(), JuliError> {
+    if changed == 0 {
+        return Err(not_found(reminder_id));
+    }
+    Ok(())
+}
@@ -0,0 +1,241 @@
+// ============================================================================
+// NOTE REVISIONS
+// ============================================================================
+
+// Every NoteStore::save also records the text in note_revisions, so an accidental
+// select-all-delete (or a bad restore) can always be undone
+//
+// The app autosaves while you type, and a revision per autosave would bury the
+// versions you actually care about. So a save *extends* the latest revision
+// while that revision is younger than the coalescing window, and only starts a
+// new one after that - except for big deletions, which always start a new
+// revision so the text from just before them is kept
+
+use rusqlite::{Connection, OptionalExtension, Row};
+use serde::{Deserialize, Serialize};
+use similar::{ChangeTag, TextDiff};
+
+use crate::error::JuliError;
+
+// The "revisions" section of config.json, e.g.
+// { "revisions": { "coalesce_seconds": 300, "max_per_note": 100, "max_age_days": 365 } }
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct RevisionPolicy {
+    // Saves within this many seconds of a revision's first save update it in place
+    // 0 records every save as its own revision
+    pub coalesce_seconds: i64,
+    // Keep at most this many revisions per note (oldest are dropped first)
+    pub max_per_note: usize,
+    // Drop revisions older than this; None keeps them forever
+    pub max_age_days: Option<i64>,
+}
+
+impl Default for RevisionPolicy {
+    fn default() -> Self {
+        RevisionPolicy {
+            coalesce_seconds: 300,
+            max_per_note: 100,
+            max_age_days: None,
+        }
+    }
+}
+
+// In TypeScript:
+// interface NoteRevisionRow { id: number; note_id: number; text: string; size: number; created_at: string; updated_at: string }
+#[derive(Debug, Clone, Serialize)]
+pub struct NoteRevisionRow {
+    pub id: i64,
+    pub note_id: i64,
+    pub text: String,
+    // Length of text in characters
+    pub size: i64,
+    // When the revision was started and when it was last extended (UTC)
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+const REVISION_COLUMNS: &str = "id, note_id, text, size, created_at, updated_at";
+
+impl NoteRevisionRow {
+    fn from_row(row: &Row) -> rusqlite::Result<NoteRevisionRow> {
+        Ok(NoteRevisionRow {
+            id: row.get("id")?,
+            note_id: row.get("note_id")?,
+            text: row.get("text")?,
+            size: row.get("size")?,
+            created_at: row.get("created_at")?,
+            updated_at: row.get("updated_at")?,
+        })
+    }
+}
+
+// One line of a diff between two revisions
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DiffLine {
+    // "equal", "insert" or "delete"
+    pub kind: &'static str,
+    // 1-based line numbers in the older / newer revision (None when the line isn't in it)
+    pub old_line: Option<usize>,
+    pub new_line: Option<usize>,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RevisionDiff {
+    pub from_revision_id: i64,
+    pub to_revision_id: i64,
+    pub lines: Vec<DiffLine>,
+    // The same diff as a unified diff, for terminals and logs
+    pub unified: String,
+}
+
+pub struct RevisionStore<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> RevisionStore<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        RevisionStore { conn }
+    }
+
+    // Record the note's new text according to the policy
+    // Returns the id of the revision that now holds it
+    pub fn record(&self, note_id: i64, text: &str, policy: &RevisionPolicy) -> Result<i64, JuliError> {
+        let size = text.chars().count() as i64;
+
+        // age is in seconds, computed by SQLite from the stored UTC timestamp
+        let latest: Option<(i64, String, f64)> = self
+            .conn
+            .query_row(
+                "SELECT id, text, (julianday('now') - julianday(created_at)) * 86400
+                 FROM note_revisions WHERE note_id = ?1 ORDER BY id DESC LIMIT 1",
+                [note_id],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .optional()?;
+
+        let revision_id = match latest {
+            // Autosave fired without a change
+            Some((id, latest_text, _)) if latest_text == text => return Ok(id),
+            Some((id, latest_text, age))
+                if age < policy.coalesce_seconds as f64 && !is_large_deletion(&latest_text, text) =>
+            {
+                self.conn.execute(
+                    "UPDATE note_revisions SET text = ?1, size = ?2, updated_at = strftime('%Y-%m-%dT%H:%M:%SZ', 'now')
+                     WHERE id = ?3",
+                    (text, size, id),
+                )?;
+                id
+            }
+            _ => {
+                self.conn.execute(
+                    "INSERT INTO note_revisions (note_id, text, size) VALUES (?1, ?2, ?3)",
+                    (note_id, text, size),
+                )?;
+                self.conn.last_insert_rowid()
+            }
+        };
+
+        self.prune(note_id, policy)?;
+        Ok(revision_id)
+    }
+
+    // Newest first
+    pub fn for_note(&self, note_id: i64) -> Result<Vec<NoteRevisionRow>, JuliError> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} FROM note_revisions WHERE note_id = ?1 ORDER BY id DESC",
+            REVISION_COLUMNS
+        ))?;
+        let revisions = stmt
+            .query_map([note_id], NoteRevisionRow::from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(revisions)
+    }
+
+    pub fn get(&self, revision_id: i64) -> Result<NoteRevisionRow, JuliError> {
+        self.conn
+            .query_row(
+                &format!("SELECT {} FROM note_revisions WHERE id = ?1", REVISION_COLUMNS),
+                [revision_id],
+                NoteRevisionRow::from_row,
+            )
+            .map_err(|e| match e {
+                rusqlite::Error::QueryReturnedNoRows => {
+                    JuliError::not_found(format!("Revision {} not found", revision_id))
+                }
+                other => other.into(),
+            })
+    }
+
+    // Line diff from one revision to another (usually older -> newer)
+    pub fn diff(&self, from_revision_id: i64, to_revision_id: i64) -> Result<RevisionDiff, JuliError> {
+        let from = self.get(from_revision_id)?;
+        let to = self.get(to_revision_id)?;
+        if from.note_id != to.note_id {
+            return Err(JuliError::validation("Can only compare revisions of the same note"));
+        }
+
+        let diff = TextDiff::from_lines(&from.text, &to.text);
+        let lines = diff
+            .iter_all_changes()
+            .map(|change| DiffLine {
+                kind: match change.tag() {
+                    ChangeTag::Equal => "equal",
+                    ChangeTag::Insert => "insert",
+                    ChangeTag::Delete => "delete",
+                },
+                old_line: change.old_index().map(|i| i + 1),
+                new_line: change.new_index().map(|i| i + 1),
+                text: change.value().trim_end_matches('\n').to_string(),
+            })
+            .collect();
+        let unified = diff
+            .unified_diff()
+            .header(&format!("revision {}", from.id), &format!("revision {}", to.id))
+            .to_string();
+
+        Ok(RevisionDiff {
+            from_revision_id,
+            to_revision_id,
+            lines,
+            unified,
+        })
+    }
+
+    // Drop what the policy no longer keeps; the newest revision always stays
+    fn prune(&self, note_id: i64, policy: &RevisionPolicy) -> Result<(), JuliError> {
+        let newest_kept = "(SELECT id FROM note_revisions WHERE note_id = ?1 ORDER BY id DESC LIMIT 1)";
+
+        self.conn.execute(
+            &format!(
+                "DELETE FROM note_revisions WHERE note_id = ?1 AND id NOT IN
+                 (SELECT id FROM note_revisions WHERE note_id = ?1 ORDER BY id DESC LIMIT ?2)
+                 AND id != {}",
+                newest_kept
+            ),
+            (note_id, policy.max_per_note.max(1) as i64),
+        )?;
+
+        if let Some(days) = policy.max_age_days {
+            self.conn.execute(
+                &format!(
+                    "DELETE FROM note_revisions WHERE note_id = ?1
+                     AND updated_at < strftime('%Y-%m-%dT%H:%M:%SZ', 'now', '-' || ?2 || ' days')
+                     AND id != {}",
+                    newest_kept
+                ),
+                (note_id, days),
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+// Losing more than half of the text in one save is almost always a mistake
+// (select-all-delete, a paste over everything), so never fold it into the
+// revision that still holds the old text
+fn is_large_deletion(old: &str, new: &str) -> bool {
+    new.chars().count() * 2 < old.chars().count()
+}
@@ -0,0 +1,296 @@
+// ============================================================================
+// FULL-TEXT SEARCH
+// ============================================================================
+
+// Ranked search over notes and reminders, backed by the FTS5 indexes
+// (notes_fts, reminders_fts) that migration 6 creates and its triggers keep in sync
+//
+// Usage:
+// let hits = SearchIndex::new(&conn).search(&SearchQuery { query: "john budget".into(), ..Default::default() })?;
+
+use chrono::NaiveDate;
+use rusqlite::{params_from_iter, types::Value, Connection};
+use serde::{Deserialize, Serialize};
+
+use crate::error::JuliError;
+
+// Markers FTS5 wraps around matched terms in snippet()
+// Control characters can't appear in note text typed by a person, so we can find
+// them again afterwards and turn them into offsets
+const MATCH_START: char = '\u{2}';
+const MATCH_END: char = '\u{3}';
+
+const DEFAULT_LIMIT: usize = 50;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchScope {
+    #[default]
+    All,
+    Notes,
+    Reminders,
+}
+
+// What the frontend sends, e.g.
+// { "query": "john budget", "from_date": "2026-01-01", "tags": ["work"], "resolved": false }
+// Everything but the query is optional
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SearchQuery {
+    pub query: String,
+    #[serde(default)]
+    pub scope: SearchScope,
+    // Inclusive range of note dates, as YYYY-MM-DD
+    // A reminder's date is the date of the note it was extracted from
+    pub from_date: Option<String>,
+    pub to_date: Option<String>,
+    // Reminders must carry every listed tag; notes must have produced a reminder that does
+    #[serde(default)]
+    pub tags: Vec<String>,
+    // Only applies to reminders
+    pub resolved: Option<bool>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchHitKind {
+    Note,
+    Reminder,
+}
+
+// One search result
+// In TypeScript:
+// interface SearchHit {
+//   kind: 'note' | 'reminder';
+//   id: number;
+//   snippet: string;               // a short excerpt around the matches
+//   highlights: [number, number][]; // [start, end) of each match inside snippet
+//   rank: number;                   // lower is better
+//   ...
+// }
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchHit {
+    pub kind: SearchHitKind,
+    pub id: i64,
+    pub text: String,
+    pub snippet: String,
+    // Offsets are UTF-16 code units, so snippet.slice(start, end) works as-is in JavaScript
+    pub highlights: Vec<(usize, usize)>,
+    // FTS5's bm25 score: more negative means more relevant
+    pub rank: f64,
+    // The note's for_date, or for a reminder the for_date of its note
+    pub for_date: Option<String>,
+    // Reminder fields, None for notes
+    pub tags: Option<String>,
+    pub resolved: Option<bool>,
+    pub due_date: Option<String>,
+}
+
+pub struct SearchIndex<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> SearchIndex<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        SearchIndex { conn }
+    }
+
+    // Best matches first, at most `limit` (default 50) hits in total
+    pub fn search(&self, query: &SearchQuery) -> Result<Vec<SearchHit>, JuliError> {
+        let Some(fts_query) = fts_query(&query.query) else {
+            return Ok(Vec::new());
+        };
+        let from_date = parse_day(query.from_date.as_deref(), "from_date")?;
+        let to_date = parse_day(query.to_date.as_deref(), "to_date")?;
+        let tags: Vec<String> = query
+            .tags
+            .iter()
+            .map(|tag| tag.trim().to_lowercase())
+            .filter(|tag| !tag.is_empty())
+            .collect();
+        let limit = query.limit.unwrap_or(DEFAULT_LIMIT);
+
+        let filters = Filters { fts_query, from_date, to_date, tags, resolved: query.resolved, limit };
+
+        let mut hits = Vec::new();
+        if query.scope != SearchScope::Reminders {
+            hits.extend(self.search_notes(&filters)?);
+        }
+        if query.scope != SearchScope::Notes {
+            hits.extend(self.search_reminders(&filters)?);
+        }
+
+        // Each table was already ranked and limited on its own; merge the two lists
+        hits.sort_by(|a, b| a.rank.total_cmp(&b.rank));
+        hits.truncate(limit);
+        Ok(hits)
+    }
+
+    fn search_notes(&self, filters: &Filters) -> Result<Vec<SearchHit>, JuliError> {
+        let mut sql = String::from(
+            "SELECT n.id, n.text, n.for_date,
+                    snippet(notes_fts, 0, char(2), char(3), '…', 16),
+                    bm25(notes_fts)
+             FROM notes_fts JOIN notes n ON n.id = notes_fts.rowid
+             WHERE notes_fts MATCH ?",
+        );
+        let mut params = vec![Value::Text(filters.fts_query.clone())];
+        filters.push_date_range(&mut sql, &mut params, "n.day");
+        for tag in &filters.tags {
+            sql.push_str(&format!(
+                " AND EXISTS (SELECT 1 FROM reminders r WHERE r.created_from_note_id = n.id AND {})",
+                TAG_MATCH
+            ));
+            params.push(Value::Text(tag_pattern(tag)));
+        }
+        sql.push_str(" ORDER BY bm25(notes_fts) LIMIT ?");
+        params.push(Value::Integer(filters.limit as i64));
+
+        let mut stmt = self.conn.prepare(&sql)?;
+        let hits = stmt
+            .query_map(params_from_iter(params), |row| {
+                let (snippet, highlights) = split_highlights(&row.get::<_, String>(3)?);
+                Ok(SearchHit {
+                    kind: SearchHitKind::Note,
+                    id: row.get(0)?,
+                    text: row.get(1)?,
+                    for_date: row.get(2)?,
+                    snippet,
+                    highlights,
+                    rank: row.get(4)?,
+                    tags: None,
+                    resolved: None,
+                    due_date: None,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(hits)
+    }
+
+    fn search_reminders(&self, filters: &Filters) -> Result<Vec<SearchHit>, JuliError> {
+        // bm25 weights: a match in the reminder text counts twice as much as one in its tags
+        let mut sql = String::from(
+            "SELECT r.id, r.text, n.for_date, r.tags, r.resolved, r.due_date,
+                    snippet(reminders_fts, 0, char(2), char(3), '…', 16),
+                    bm25(reminders_fts, 2.0, 1.0)
+             FROM reminders_fts
+             JOIN reminders r ON r.id = reminders_fts.rowid
+             LEFT JOIN notes n ON n.id = r.created_from_note_id
+             WHERE reminders_fts MATCH ?",
+        );
+        let mut params = vec![Value::Text(filters.fts_query.clone())];
+        filters.push_date_range(&mut sql, &mut params, "n.day");
+        for tag in &filters.tags {
+            sql.push_str(&format!(" AND {}", TAG_MATCH));
+            params.push(Value::Text(tag_pattern(tag)));
+        }
+        if let Some(resolved) = filters.resolved {
+            sql.push_str(" AND r.resolved = ?");
+            params.push(Value::Integer(resolved as i64));
+        }
+        sql.push_str(" ORDER BY bm25(reminders_fts, 2.0, 1.0) LIMIT ?");
+        params.push(Value::Integer(filters.limit as i64));
+
+        let mut stmt = self.conn.prepare(&sql)?;
+        let hits = stmt
+            .query_map(params_from_iter(params), |row| {
+                let (snippet, highlights) = split_highlights(&row.get::<_, String>(6)?);
+                Ok(SearchHit {
+                    kind: SearchHitKind::Reminder,
+                    id: row.get(0)?,
+                    text: row.get(1)?,
+                    for_date: row.get(2)?,
+                    tags: row.get(3)?,
+                    resolved: Some(row.get(4)?),
+                    due_date: row.get(5)?,
+                    snippet,
+                    highlights,
+                    rank: row.get(7)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(hits)
+    }
+}
+
+// The validated form of a SearchQuery, shared by both table searches
+struct Filters {
+    fts_query: String,
+    from_date: Option<NaiveDate>,
+    to_date: Option<NaiveDate>,
+    tags: Vec<String>,
+    resolved: Option<bool>,
+    limit: usize,
+}
+
+impl Filters {
+    // ISO dates compare correctly as plain strings
+    fn push_date_range(&self, sql: &mut String, params: &mut Vec<Value>, column: &str) {
+        if let Some(from) = self.from_date {
+            sql.push_str(&format!(" AND {} >= ?", column));
+            params.push(Value::Text(from.to_string()));
+        }
+        if let Some(to) = self.to_date {
+            sql.push_str(&format!(" AND {} <= ?", column));
+            params.push(Value::Text(to.to_string()));
+        }
+    }
+}
+
+// Tags are stored comma separated ("work, finance"), so wrap them in commas
+// and look for ",tag," to avoid "work" also matching "homework"
+const TAG_MATCH: &str = "(',' || REPLACE(LOWER(COALESCE(r.tags, '')), ' ', '') || ',') LIKE ?";
+
+fn tag_pattern(tag: &str) -> String {
+    format!("%,{},%", tag.replace(' ', ""))
+}
+
+fn parse_day(value: Option<&str>, field: &str) -> Result<Option<NaiveDate>, JuliError> {
+    match value.map(str::trim).filter(|v| !v.is_empty()) {
+        None => Ok(None),
+        Some(v) => NaiveDate::parse_from_str(v, "%Y-%m-%d")
+            .map(Some)
+            .map_err(|_| JuliError::validation(format!("{} must be a date like 2026-10-16, got \"{}\"", field, v))),
+    }
+}
+
+// Turn what the user typed into an FTS5 query
+// Every word is quoted so characters like - or : are searched for instead of being
+// read as FTS5 operators, and the last word also matches as a prefix so results
+// show up while typing ("buy mil" finds "buy milk")
+// Returns None when there is nothing to search for
+fn fts_query(input: &str) -> Option<String> {
+    let words: Vec<String> = input
+        .split_whitespace()
+        .map(|word| format!("\"{}\"", word.replace('"', "\"\"")))
+        .collect();
+    if words.is_empty() {
+        return None;
+    }
+    Some(format!("{}*", words.join(" ")))
+}
+
+// Remove the match markers from a snippet and record where they were
+fn split_highlights(marked: &str) -> (String, Vec<(usize, usize)>) {
+    let mut snippet = String::with_capacity(marked.len());
+    let mut highlights = Vec::new();
+    let mut offset = 0;
+    let mut start = None;
+
+    for c in marked.chars() {
+        match c {
+            MATCH_START => start = Some(offset),
+            MATCH_END => {
+                if let Some(s) = start.take() {
+                    highlights.push((s, offset));
+                }
+            }
+            _ => {
+                snippet.push(c);
+                offset += c.len_utf16();
+            }
+        }
+    }
+
+    (snippet, highlights)
+}
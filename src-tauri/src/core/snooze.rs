@@ -0,0 +1,95 @@
+// ============================================================================
+// SNOOZING REMINDERS
+// ============================================================================
+
+// Pushing a reminder out to a later date (see ReminderStore::snooze)
+// "when" is either a date or something relative, counted from the reminder's due date -
+// or from today when it's overdue or has no due date:
+// - "2026-10-30"               that day
+// - "1d", "3d", "2w"           that many days or weeks later
+// - "tomorrow"                 today + 1 day
+// - "monday", "next monday"    the first Monday after it (also "mon")
+// - "next week"                the first Monday after it
+// - "eow"                      the end of the (work) week: the first Friday after it
+//
+// Usage:
+// let until = snooze_date("next monday", today, due_date)?; // Err is the message for the user
+
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+
+use super::validation::parse_due_date;
+
+const WEEKDAYS: &[(&str, Weekday)] = &[
+    ("monday", Weekday::Mon),
+    ("tuesday", Weekday::Tue),
+    ("wednesday", Weekday::Wed),
+    ("thursday", Weekday::Thu),
+    ("friday", Weekday::Fri),
+    ("saturday", Weekday::Sat),
+    ("sunday", Weekday::Sun),
+];
+
+// The new due date for `when`
+// The result is always later than the current due date, and never before today
+pub fn snooze_date(when: &str, today: NaiveDate, due_date: Option<NaiveDate>) -> Result<NaiveDate, String> {
+    let spec = when.trim().to_lowercase();
+    // Overdue or undated: count from today
+    let from = due_date.filter(|due| *due > today).unwrap_or(today);
+
+    let until = match spec.as_str() {
+        "" => return Err("Say when to snooze until, like \"1d\", \"next monday\" or \"2026-10-30\"".to_string()),
+        "tomorrow" => today + Duration::days(1),
+        "next week" => next_weekday(from, Weekday::Mon),
+        "eow" => next_weekday(from, Weekday::Fri),
+        _ => match relative(&spec, from).or_else(|| weekday(&spec).map(|day| next_weekday(from, day))) {
+            Some(until) => until,
+            None => {
+                let date = parse_due_date(Some(&spec)).map_err(|_| unknown(when))?;
+                date.and_then(|d| NaiveDate::parse_from_str(&d, "%Y-%m-%d").ok()).ok_or_else(|| unknown(when))?
+            }
+        },
+    };
+
+    if until < today {
+        return Err(format!("{} is in the past", until));
+    }
+    if due_date.is_some_and(|due| until <= due) {
+        return Err(format!("Snoozing moves a reminder later, but {} isn't after its due date", until));
+    }
+    Ok(until)
+}
+
+// "3d" or "2w"
+fn relative(spec: &str, from: NaiveDate) -> Option<NaiveDate> {
+    // strip_suffix rather than slicing off the last byte, which panics on "1€"
+    let (count, per) = match (spec.strip_suffix('d'), spec.strip_suffix('w')) {
+        (Some(count), _) => (count, 1),
+        (_, Some(count)) => (count, 7),
+        _ => return None,
+    };
+    let count: i64 = count.trim().parse().ok().filter(|n| *n > 0)?;
+    let days = count.checked_mul(per)?;
+    from.checked_add_signed(Duration::try_days(days)?)
+}
+
+// "monday", "next monday" or "mon"
+fn weekday(spec: &str) -> Option<Weekday> {
+    let name = spec.strip_prefix("next ").unwrap_or(spec).trim();
+    if name.len() < 3 {
+        return None;
+    }
+    WEEKDAYS.iter().find(|(full, _)| full.starts_with(name)).map(|(_, day)| *day)
+}
+
+// The first `day` after `from` - a week later when `from` is one
+fn next_weekday(from: NaiveDate, day: Weekday) -> NaiveDate {
+    let ahead = (day.num_days_from_monday() + 7 - from.weekday().num_days_from_monday()) % 7;
+    from + Duration::days(if ahead == 0 { 7 } else { ahead as i64 })
+}
+
+fn unknown(when: &str) -> String {
+    format!(
+        "Can't snooze until \"{}\": use a date (YYYY-MM-DD), \"1d\", \"2w\", \"tomorrow\", a weekday like \"next monday\", \"next week\" or \"eow\"",
+        when.trim()
+    )
+}
@@ -0,0 +1,96 @@
+// ============================================================================
+// REMINDER SOURCES
+// ============================================================================
+
+// Which part of which note a reminder was created or updated from
+// One reminder can have several sources: the line it was created from, and
+// every later edit that updated it
+
+use rusqlite::{Connection, Row};
+use serde::Serialize;
+
+use crate::error::JuliError;
+
+// In TypeScript:
+// interface ReminderSourceRow { id: number; reminder_id: number; note_id: number; ai_log_id: number | null;
+//   action: 'CREATE' | 'UPDATE'; start_line: number; end_line: number; excerpt: string; created_at: string }
+#[derive(Debug, Clone, Serialize)]
+pub struct ReminderSourceRow {
+    pub id: i64,
+    pub reminder_id: i64,
+    pub note_id: i64,
+    // The AI interaction that produced it (see ai_interaction_logs)
+    pub ai_log_id: Option<i64>,
+    pub action: String,
+    // 1-based, inclusive lines of the note as it was when it was analyzed
+    pub start_line: i64,
+    pub end_line: i64,
+    // Those lines' text at that time
+    pub excerpt: String,
+    pub created_at: String,
+}
+
+impl ReminderSourceRow {
+    fn from_row(row: &Row) -> rusqlite::Result<ReminderSourceRow> {
+        Ok(ReminderSourceRow {
+            id: row.get("id")?,
+            reminder_id: row.get("reminder_id")?,
+            note_id: row.get("note_id")?,
+            ai_log_id: row.get("ai_log_id")?,
+            action: row.get("action")?,
+            start_line: row.get("start_line")?,
+            end_line: row.get("end_line")?,
+            excerpt: row.get("excerpt")?,
+            created_at: row.get("created_at")?,
+        })
+    }
+}
+
+pub struct NewReminderSource<'a> {
+    pub reminder_id: i64,
+    pub note_id: i64,
+    pub ai_log_id: Option<i64>,
+    pub action: &'a str,
+    pub start_line: usize,
+    pub end_line: usize,
+    pub excerpt: &'a str,
+}
+
+pub struct ReminderSourceStore<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> ReminderSourceStore<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        ReminderSourceStore { conn }
+    }
+
+    // Oldest first, so the first entry is where the reminder came from
+    pub fn for_reminder(&self, reminder_id: i64) -> Result<Vec<ReminderSourceRow>, JuliError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, reminder_id, note_id, ai_log_id, action, start_line, end_line, excerpt, created_at
+             FROM reminder_sources WHERE reminder_id = ?1 ORDER BY id",
+        )?;
+        let sources = stmt
+            .query_map([reminder_id], ReminderSourceRow::from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(sources)
+    }
+
+    pub fn insert(&self, source: &NewReminderSource) -> Result<i64, JuliError> {
+        self.conn.execute(
+            "INSERT INTO reminder_sources (reminder_id, note_id, ai_log_id, action, start_line, end_line, excerpt)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            (
+                source.reminder_id,
+                source.note_id,
+                source.ai_log_id,
+                source.action,
+                source.start_line as i64,
+                source.end_line as i64,
+                source.excerpt,
+            ),
+        )?;
+        Ok(self.conn.last_insert_rowid())
+    }
+}
@@ -0,0 +1,77 @@
+// ============================================================================
+// TIMES AND TIMEZONES
+// ============================================================================
+
+// Two kinds of time are stored:
+// - When something happened (created_at, resolved_at, next_attempt_at, ...): always UTC,
+//   as RFC 3339 with a Z, like "2026-10-16T19:04:05Z". In SQL that's
+//   strftime('%Y-%m-%dT%H:%M:%SZ', 'now'), in Rust now_timestamp()
+//   The Z matters: JavaScript's new Date("2026-10-16 19:04:05") reads a bare time as local
+// - When a reminder is due: the user's own wall-clock time. due_date is the day in the
+//   user's timezone; a reminder with a time also has due_at, an RFC 3339 timestamp with
+//   the offset ("2026-10-16T15:00:00-04:00"), and due_timezone, the IANA name it was set in
+//
+// The user's timezone is "timezone" in config.json ("America/New_York"), or the
+// system's when it isn't set
+//
+// Usage:
+// let tz = config.timezone();
+// let due = due_at(date, NaiveTime::from_hms_opt(15, 0, 0).unwrap(), tz); // 2026-10-16T15:00:00-04:00
+
+use std::sync::OnceLock;
+
+use chrono::{DateTime, FixedOffset, LocalResult, NaiveDate, NaiveTime, SecondsFormat, TimeZone, Utc};
+use chrono_tz::Tz;
+
+// The timezone the system is set to, UTC if it can't be worked out
+// Looked up once per run, so a broken setup warns once rather than on every command
+pub fn system_timezone() -> Tz {
+    static SYSTEM: OnceLock<Tz> = OnceLock::new();
+    *SYSTEM.get_or_init(|| match iana_time_zone::get_timezone() {
+        Ok(name) => parse_timezone(&name).unwrap_or_else(|e| {
+            println!("⚠️ {}, using UTC", e);
+            Tz::UTC
+        }),
+        Err(e) => {
+            println!("⚠️ Couldn't read the system timezone ({}), using UTC", e);
+            Tz::UTC
+        }
+    })
+}
+
+// "America/New_York" -> Tz; the error says what was wrong, like parse_due_date's
+pub fn parse_timezone(name: &str) -> Result<Tz, String> {
+    name.trim()
+        .parse::<Tz>()
+        .map_err(|_| format!("\"{}\" is not an IANA timezone like \"America/New_York\"", name.trim()))
+}
+
+// Right now, for storing: "2026-10-16T19:04:05Z"
+pub fn now_timestamp() -> String {
+    Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+// Today's date where the user is
+pub fn today_in(tz: Tz) -> NaiveDate {
+    Utc::now().with_timezone(&tz).date_naive()
+}
+
+// The moment a wall-clock time happens in a timezone
+// Around daylight saving changes a time can happen twice (the earlier one is used)
+// or not at all (2:30 on the night clocks go forward - the first minute after the gap is used)
+pub fn due_at(date: NaiveDate, time: NaiveTime, tz: Tz) -> DateTime<FixedOffset> {
+    let mut local = date.and_time(time);
+    // Gaps are at most a few hours; step over them a minute at a time
+    for _ in 0..24 * 60 {
+        match tz.from_local_datetime(&local) {
+            LocalResult::Single(at) | LocalResult::Ambiguous(at, _) => return at.fixed_offset(),
+            LocalResult::None => local += chrono::Duration::minutes(1),
+        }
+    }
+    Utc.from_utc_datetime(&date.and_time(time)).fixed_offset()
+}
+
+// due_at for storing: "2026-10-16T15:00:00-04:00"
+pub fn format_due_at(at: DateTime<FixedOffset>) -> String {
+    at.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
@@ -0,0 +1,264 @@
+// ============================================================================
+// VALIDATING THE AI'S REMINDERS
+// ============================================================================
+
+// Everything the AI returns is checked before it gets anywhere near the reminders table:
+// - the action must be CREATE, or UPDATE with the id of a reminder that exists
+// - the text can't be empty
+// - due_date must be a real YYYY-MM-DD date (or missing)
+// - due_time must be a 24-hour HH:MM time (or missing), and only comes with a due_date
+// - recurrence must be an RRULE we support (or missing), see core/recurrence.rs
+// - priority must be low, normal, high or urgent (or missing, which is normal)
+// - tags are cleaned up into one consistent "tag1,tag2" format
+//
+// Items that fail are not silently dropped: the analyzer records each one, with the
+// reason, next to the AI log entry of the answer it came from
+//
+// Reminders the user writes (edited proposals, manual reminders) get the same checks
+// through checked_draft, but a failure there is simply a Validation error
+
+use chrono::{NaiveDate, NaiveTime};
+
+use super::analyzer::AiExtractedReminder;
+use super::recurrence::Recurrence;
+use super::time::parse_timezone;
+use super::{ReminderDraft, ReminderStore};
+use crate::error::JuliError;
+
+// What a reminder from the AI asks us to do
+// In TypeScript: type ReminderAction = { kind: 'CREATE' } | { kind: 'UPDATE'; reminderId: number }
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReminderAction {
+    Create,
+    Update { reminder_id: i64 },
+}
+
+impl ReminderAction {
+    // The name used in the prompt, reminder_sources and reminder_proposals
+    pub fn name(&self) -> &'static str {
+        match self {
+            ReminderAction::Create => "CREATE",
+            ReminderAction::Update { .. } => "UPDATE",
+        }
+    }
+}
+
+// A reminder that passed every check, cleaned up and ready to write
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidReminder {
+    pub action: ReminderAction,
+    pub text: String,
+    pub tags: Option<String>,
+    pub due_date: Option<String>,
+    pub due_time: Option<String>,
+    pub recurrence: Option<String>,
+    pub priority: Option<String>,
+    // Which [CHANGED n] section it came from (see analyzer.rs)
+    pub span: Option<usize>,
+}
+
+impl ValidReminder {
+    // What gets written to the reminders table (or proposed); due times are in the user's timezone
+    pub fn draft(&self) -> ReminderDraft {
+        ReminderDraft {
+            text: self.text.clone(),
+            tags: self.tags.clone(),
+            due_date: self.due_date.clone(),
+            due_time: self.due_time.clone(),
+            timezone: None,
+            recurrence: self.recurrence.clone(),
+            priority: self.priority.clone(),
+        }
+    }
+}
+
+// A reminder we refused, and why
+#[derive(Debug)]
+pub struct RejectedReminder<'a> {
+    pub item: &'a AiExtractedReminder,
+    pub reason: String,
+}
+
+// Sort the AI's reminders into the ones we'll write and the ones we won't
+// Only fails if the database can't be read
+pub fn validate_reminders<'a>(
+    extracted: &'a [AiExtractedReminder],
+    reminders: &ReminderStore,
+) -> Result<(Vec<ValidReminder>, Vec<RejectedReminder<'a>>), JuliError> {
+    let mut valid = Vec::new();
+    let mut rejected = Vec::new();
+
+    for item in extracted {
+        match validate_reminder(item, reminders)? {
+            Ok(reminder) => valid.push(reminder),
+            Err(reason) => rejected.push(RejectedReminder { item, reason }),
+        }
+    }
+
+    Ok((valid, rejected))
+}
+
+// The outer Result is a database failure, the inner one the verdict on this item
+fn validate_reminder(item: &AiExtractedReminder, reminders: &ReminderStore) -> Result<Result<ValidReminder, String>, JuliError> {
+    let action = match (item.action.trim().to_uppercase().as_str(), item.update_id) {
+        ("CREATE", _) => ReminderAction::Create,
+        ("UPDATE", None) => return Ok(Err("UPDATE without an update_id".to_string())),
+        ("UPDATE", Some(reminder_id)) => match reminders.get(reminder_id) {
+            Ok(_) => ReminderAction::Update { reminder_id },
+            Err(JuliError::NotFound { .. }) => {
+                return Ok(Err(format!("UPDATE of reminder {}, which doesn't exist", reminder_id)))
+            }
+            Err(e) => return Err(e),
+        },
+        (other, _) => return Ok(Err(format!("unknown action \"{}\" (expected CREATE or UPDATE)", other))),
+    };
+
+    let text = item.text.trim();
+    if text.is_empty() {
+        return Ok(Err("empty reminder text".to_string()));
+    }
+
+    let due_date = match parse_due_date(item.due_date.as_deref()) {
+        Ok(due_date) => due_date,
+        Err(reason) => return Ok(Err(reason)),
+    };
+
+    let due_time = match parse_due_time(item.due_time.as_deref()) {
+        Ok(due_time) => due_time,
+        Err(reason) => return Ok(Err(reason)),
+    };
+    if due_time.is_some() && due_date.is_none() {
+        return Ok(Err("due_time without a due_date".to_string()));
+    }
+
+    let recurrence = match parse_recurrence(item.recurrence.as_deref()) {
+        Ok(recurrence) => recurrence,
+        Err(reason) => return Ok(Err(reason)),
+    };
+
+    let priority = match parse_priority(item.priority.as_deref()) {
+        Ok(priority) => priority,
+        Err(reason) => return Ok(Err(reason)),
+    };
+
+    Ok(Ok(ValidReminder {
+        action,
+        text: text.to_string(),
+        tags: normalize_tags(item.tags.as_deref()),
+        due_date,
+        due_time,
+        recurrence,
+        priority,
+        span: item.span,
+    }))
+}
+
+// The user's version of a reminder, cleaned up like the AI's: trimmed text that isn't
+// empty, normalized tags, a real due date and time, a known timezone, a recurrence we understand and a known priority
+pub fn checked_draft(draft: ReminderDraft) -> Result<ReminderDraft, JuliError> {
+    let text = draft.text.trim();
+    if text.is_empty() {
+        return Err(JuliError::validation("A reminder needs some text"));
+    }
+    let due_date = parse_due_date(draft.due_date.as_deref()).map_err(JuliError::validation)?;
+    let due_time = parse_due_time(draft.due_time.as_deref()).map_err(JuliError::validation)?;
+    if due_time.is_some() && due_date.is_none() {
+        return Err(JuliError::validation("A due time needs a due date"));
+    }
+    let timezone = match draft.timezone.as_deref().map(str::trim) {
+        None | Some("") => None,
+        Some(name) => Some(parse_timezone(name).map_err(JuliError::validation)?.name().to_string()),
+    };
+    Ok(ReminderDraft {
+        text: text.to_string(),
+        tags: normalize_tags(draft.tags.as_deref()),
+        due_date,
+        due_time,
+        timezone,
+        recurrence: parse_recurrence(draft.recurrence.as_deref()).map_err(JuliError::validation)?,
+        priority: parse_priority(draft.priority.as_deref()).map_err(JuliError::validation)?,
+    })
+}
+
+// "2026-10-20" stays as it is; missing, blank or "null" mean no due date
+// Anything that isn't a real calendar date (like "2026-02-30" or "next friday") is an error
+pub fn parse_due_date(due_date: Option<&str>) -> Result<Option<String>, String> {
+    let due_date = match due_date.map(str::trim) {
+        None | Some("") => return Ok(None),
+        Some(d) if d.eq_ignore_ascii_case("null") => return Ok(None),
+        Some(d) => d,
+    };
+
+    NaiveDate::parse_from_str(due_date, "%Y-%m-%d")
+        .map(|date| Some(date.format("%Y-%m-%d").to_string()))
+        .map_err(|_| format!("due_date \"{}\" is not a YYYY-MM-DD date", due_date))
+}
+
+// "15:00", "9:30" or "15:00:00" -> "15:00", "09:30", "15:00"; missing, blank or "null" mean no time
+// Times are 24-hour - "3pm" is an error, the prompt asks for "15:00"
+pub fn parse_due_time(due_time: Option<&str>) -> Result<Option<String>, String> {
+    let due_time = match due_time.map(str::trim) {
+        None | Some("") => return Ok(None),
+        Some(t) if t.eq_ignore_ascii_case("null") => return Ok(None),
+        Some(t) => t,
+    };
+
+    NaiveTime::parse_from_str(due_time, "%H:%M")
+        .or_else(|_| NaiveTime::parse_from_str(due_time, "%H:%M:%S"))
+        .map(|time| Some(time.format("%H:%M").to_string()))
+        .map_err(|_| format!("due_time \"{}\" is not a 24-hour HH:MM time", due_time))
+}
+
+// "freq=monthly;bymonthday=1" -> "FREQ=MONTHLY;BYMONTHDAY=1"; missing, blank or "null" mean it doesn't repeat
+// Anything Recurrence::parse doesn't accept is an error
+pub fn parse_recurrence(recurrence: Option<&str>) -> Result<Option<String>, String> {
+    match recurrence.map(str::trim) {
+        None | Some("") => Ok(None),
+        Some(r) if r.eq_ignore_ascii_case("null") => Ok(None),
+        Some(r) => Recurrence::parse(r).map(|rule| Some(rule.to_string())),
+    }
+}
+
+// The priorities, most pressing first (see ReminderSort::Priority)
+pub const PRIORITIES: &[&str] = &["urgent", "high", "normal", "low"];
+
+// "High", "p2" -> "high"; missing, blank or "null" mean no priority given (which is "normal")
+// P1 is the most pressing, like in most task apps: p1 = urgent, p2 = high, p3 = normal, p4 = low
+pub fn parse_priority(priority: Option<&str>) -> Result<Option<String>, String> {
+    let priority = match priority.map(|p| p.trim().to_lowercase()) {
+        None => return Ok(None),
+        Some(p) if p.is_empty() || p == "null" => return Ok(None),
+        Some(p) => p,
+    };
+
+    let name = match priority.as_str() {
+        "p1" => "urgent",
+        "p2" => "high",
+        "p3" | "medium" => "normal",
+        "p4" => "low",
+        p => PRIORITIES
+            .iter()
+            .find(|name| **name == p)
+            .ok_or_else(|| format!("priority \"{}\" is not one of low, normal, high or urgent", priority))?,
+    };
+    Ok(Some(name.to_string()))
+}
+
+// " Work, #urgent,,work " -> "work,urgent"
+// Lowercased, trimmed, without a leading #, empty and duplicate tags removed
+// None when nothing is left
+pub fn normalize_tags(tags: Option<&str>) -> Option<String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for tag in tags.unwrap_or("").split(',') {
+        let tag = tag.trim().trim_start_matches('#').trim().to_lowercase();
+        if !tag.is_empty() && !normalized.contains(&tag) {
+            normalized.push(tag);
+        }
+    }
+
+    if normalized.is_empty() {
+        None
+    } else {
+        Some(normalized.join(","))
+    }
+}
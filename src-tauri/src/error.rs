@@ -0,0 +1,138 @@
+// ============================================================================
+// ERROR TYPE SHARED BY ALL COMMANDS
+// ============================================================================
+
+// Every command returns Result<_, JuliError> so the frontend can tell failures apart
+// `#[serde(tag = "kind")]` serializes each variant as a flat object:
+// { "kind": "NotFound", "message": "No note for ...", "details": null }
+// In TypeScript this is a discriminated union you can switch on `error.kind`
+
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::migrations::MigrationError;
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind")]
+pub enum JuliError {
+    // SQLite failed (locked, disk full, constraint violation, ...)
+    Database { message: String, details: Option<String> },
+    // The row the caller asked for doesn't exist
+    NotFound { message: String, details: Option<String> },
+    // API key or config.json missing/invalid - the UI should send the user to setup
+    Config { message: String, details: Option<String> },
+    // Couldn't reach the AI provider, or it answered with an error status
+    AiTransport { message: String, details: Option<String> },
+    // The AI answered but we couldn't make sense of the response
+    AiParse { message: String, details: Option<String> },
+    // The caller (or the AI) gave us input we refuse to store
+    Validation { message: String, details: Option<String> },
+}
+
+impl JuliError {
+    pub fn database(message: impl Into<String>) -> Self {
+        JuliError::Database { message: message.into(), details: None }
+    }
+
+    pub fn not_found(message: impl Into<String>) -> Self {
+        JuliError::NotFound { message: message.into(), details: None }
+    }
+
+    pub fn config(message: impl Into<String>) -> Self {
+        JuliError::Config { message: message.into(), details: None }
+    }
+
+    pub fn ai_transport(message: impl Into<String>) -> Self {
+        JuliError::AiTransport { message: message.into(), details: None }
+    }
+
+    pub fn ai_parse(message: impl Into<String>) -> Self {
+        JuliError::AiParse { message: message.into(), details: None }
+    }
+
+    pub fn validation(message: impl Into<String>) -> Self {
+        JuliError::Validation { message: message.into(), details: None }
+    }
+
+    // Attach extra context (raw API body, SQL error, file path, ...)
+    // Usage: JuliError::ai_transport("API error").with_details(body)
+    pub fn with_details(mut self, extra: impl Into<String>) -> Self {
+        match &mut self {
+            JuliError::Database { details, .. }
+            | JuliError::NotFound { details, .. }
+            | JuliError::Config { details, .. }
+            | JuliError::AiTransport { details, .. }
+            | JuliError::AiParse { details, .. }
+            | JuliError::Validation { details, .. } => *details = Some(extra.into()),
+        }
+        self
+    }
+
+    // The variant name, same string the frontend sees in `kind`
+    pub fn kind(&self) -> &'static str {
+        match self {
+            JuliError::Database { .. } => "Database",
+            JuliError::NotFound { .. } => "NotFound",
+            JuliError::Config { .. } => "Config",
+            JuliError::AiTransport { .. } => "AiTransport",
+            JuliError::AiParse { .. } => "AiParse",
+            JuliError::Validation { .. } => "Validation",
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            JuliError::Database { message, .. }
+            | JuliError::NotFound { message, .. }
+            | JuliError::Config { message, .. }
+            | JuliError::AiTransport { message, .. }
+            | JuliError::AiParse { message, .. }
+            | JuliError::Validation { message, .. } => message,
+        }
+    }
+
+    pub fn details(&self) -> Option<&str> {
+        match self {
+            JuliError::Database { details, .. }
+            | JuliError::NotFound { details, .. }
+            | JuliError::Config { details, .. }
+            | JuliError::AiTransport { details, .. }
+            | JuliError::AiParse { details, .. }
+            | JuliError::Validation { details, .. } => details.as_deref(),
+        }
+    }
+}
+
+impl fmt::Display for JuliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.details() {
+            Some(details) => write!(f, "{}: {} ({})", self.kind(), self.message(), details),
+            None => write!(f, "{}: {}", self.kind(), self.message()),
+        }
+    }
+}
+
+impl std::error::Error for JuliError {}
+
+// ============================================================================
+// CONVERSIONS
+// ============================================================================
+
+// These let the ? operator turn library errors into JuliError automatically
+// In TypeScript terms: a catch block that wraps every error into our own class
+
+impl From<rusqlite::Error> for JuliError {
+    fn from(e: rusqlite::Error) -> Self {
+        match e {
+            rusqlite::Error::QueryReturnedNoRows => JuliError::not_found("Record not found"),
+            other => JuliError::database(other.to_string()),
+        }
+    }
+}
+
+impl From<MigrationError> for JuliError {
+    fn from(e: MigrationError) -> Self {
+        JuliError::database(e.to_string())
+    }
+}
@@ -1,9 +1,25 @@
-use std::{env, sync::Mutex, fs};
+use std::{sync::Mutex, path::PathBuf, thread, time::Duration};
 
+use chrono::Utc;
+use chrono_tz::Tz;
 use rusqlite::Connection;
-use tauri::{AppHandle, Manager, State};
-use serde::{Deserialize, Serialize};
-use tokio::sync::Mutex as TokioMutex;
+use tauri::{AppHandle, Emitter, Manager, State};
+use tauri_plugin_notification::{NotificationExt, PermissionState};
+use tokio::sync::Notify;
+
+pub mod ai;
+pub mod core;
+pub mod error;
+pub mod migrations;
+
+use ai::{AiProvider, CompletionOptions};
+use crate::core::{
+    blocking, database_path, load_config, note_date_key, prune_delivered_notifications, run_analysis_job, today_in, AiLogRow, AiLogStore, AnalysisJobResult,
+    AnalysisOutcome, AnalysisQueue, AppConfig, ChangeFeed, Database, DbPool, NoteRow, PendingAnalysis,
+    NoteRevisionRow, NoteStore, Notifier, ProposalRow, ProposalStore, ReminderDraft, ReminderPatch, ReminderRow, ReminderSort, ReminderSourceRow, ReminderSourceStore, ReminderStore, RevisionDiff,
+    RevisionPolicy, RevisionStore, SearchHit, SearchIndex, SearchQuery,
+};
+use error::JuliError;
 
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
 #[tauri::command]
@@ -15,188 +31,137 @@ fn greet(name: &str) -> String {
 // DATABASE STATE WRAPPER
 // ============================================================================
 
-// This is a "tuple struct" that wraps our database connection
-// Think of it like: class Db { constructor(public connection: Mutex<Connection>) }
+// This is a "tuple struct" that wraps our database connections
+// Think of it like: class Db { constructor(public pool: DbPool) }
 // But in Rust, we use a tuple struct for simple wrappers with a single field
 //
-// Mutex<Connection> is crucial for thread safety:
-// - Mutex is like a lock that ensures only ONE thread can access the data at a time
+// DbPool (see core/database.rs) is one writer connection plus a few readers:
 // - In TypeScript, you don't usually worry about this because JS is single-threaded
-// - But Rust apps can run code on multiple threads, so we need protection
-// - When you call .lock(), you get exclusive access until the lock is released
-struct Db(Mutex<Connection>);
+// - But Rust apps can run code on multiple threads, so a connection can only be used
+//   by one of them at a time - the pool hands each query a connection of its own
+// - db.0.read(...) / db.0.write(...) run the query on a background thread and are awaited,
+//   so commands never block the async runtime while SQLite works
+struct Db(DbPool);
 
-// A lock to ensure only one AI analysis runs at a time
-// This prevents race conditions where the same note gets analyzed twice
-// before the first analysis has created reminders
-// Uses TokioMutex because it needs to be held across async await points
-struct AiLock(TokioMutex<()>);
+// Wakes the background analysis worker when a job is queued
+// Notify is like a one-shot event the worker can await: notify_one() before the
+// worker is waiting isn't lost, the next wait returns right away
+// There is only one worker, which is also what guarantees that only one AI
+// analysis runs at a time (the same note is never analyzed twice in parallel)
+struct AnalysisWorker(Notify);
 
-// ============================================================================
-// CONFIG FILE STRUCTURE
-// ============================================================================
+// How note revisions are coalesced and pruned, read from config.json at startup
+struct RevisionSettings(RevisionPolicy);
 
-// Configuration loaded from config.json in the app's config directory
-// This is used for production builds where .env files aren't loaded
-#[derive(Deserialize)]
-struct AppConfig {
-    claude_api_key: String,
-}
+// The user's timezone, read from config.json at startup (see core/time.rs)
+// What "today" is, and what the due times of reminders added or edited in the app mean
+struct UserTimezone(Tz);
+
+// The id of the last change_feed row sent to the frontend (see core/changes.rs)
+struct ChangeCursor(Mutex<i64>);
 
 // ============================================================================
-// NOTE DATA STRUCTURE
+// CHANGE EVENTS
 // ============================================================================
 
-// This defines the shape of data we'll send to the frontend
-// Think of it like a TypeScript interface:
-// interface NoteRow {
-//   id: number;
-//   text: string;
-//   for_date: string;
-// }
-//
-// The attributes above the struct are "derive macros":
-// - #[derive(Debug)] - Lets you print the struct for debugging (like console.log)
-// - #[derive(Serialize)] - Converts this struct to JSON automatically
-//   In TypeScript, objects are already JSON-compatible
-//   In Rust, we need explicit serialization via the serde crate
-#[derive(Debug, Serialize)]
-struct NoteRow {
-    id: i64,         // i64 is a 64-bit integer (TypeScript's number type)
-    text: String,    // String is Rust's owned string type (like TypeScript's string)
-    for_date: String, // Wes store dates as strings for simplicity
-}
-
-#[derive(Debug, Serialize)]
-struct ReminderRow {
-    id: i64,
-    text: String,
-    resolved: bool,
-    created_from_note_id: i64,
-    tags: Option<String>,
-    created_at: Option<String>,
-    due_date: Option<String>,
+// Run a write against the database, then tell the frontend what it changed
+// Commands that modify reminders or notes go through here instead of db.0.write(),
+// so no change goes out without its reminder:* / note:saved event
+// Usage: write_db(&app, move |conn| ReminderStore::new(conn).resolve(reminder_id)).await
+async fn write_db<T, F>(app: &AppHandle, write: F) -> Result<T, JuliError>
+where
+    T: Send + 'static,
+    F: FnOnce(&Connection) -> Result<T, JuliError> + Send + 'static,
+{
+    let app = app.clone();
+    blocking(move || {
+        let result = app.state::<Db>().0.with_writer(write);
+        // Even a failed write may have changed something before it failed
+        publish_changes(&app);
+        result
+    })
+    .await
 }
 
-#[derive(Debug, Serialize)]
-struct AiLogRow {
-    id: i64,
-    note_id: i64,
-    prompt: String,
-    response: String,
-    success: bool,
-    reasoning: String,
-    reminders_count: i64,
-    created_at: String,
+// Emit an event for every change_feed row that hasn't been sent yet
+// The cursor stays locked while emitting, so two callers never send the same change twice
+// Reads the database, so only call it off the async runtime (write_db, the worker, the poller)
+fn publish_changes(app: &AppHandle) {
+    let cursor = app.state::<ChangeCursor>();
+    // A panic while publishing left the cursor as it was, which is still correct
+    let mut last_sent = cursor.0.lock().unwrap_or_else(|e| e.into_inner());
+
+    let changes = app.state::<Db>().0.with_writer(|conn| {
+        let feed = ChangeFeed::new(conn);
+        let (changes, last_id) = feed.since(*last_sent)?;
+        feed.prune(last_id)?;
+        Ok((changes, last_id))
+    });
+
+    match changes {
+        Ok((changes, last_id)) => {
+            for change in &changes {
+                emit_event(app, &change.event, &change.payload);
+            }
+            *last_sent = last_id;
+        }
+        Err(e) => println!("⚠️ Failed to read the change feed: {}", e),
+    }
 }
 
-// What the AI returns when analyzing a note
-// We use this to parse the AI's JSON response
-#[derive(Debug, Deserialize)]
-struct AiExtractedReminder {
-    text: String,                    // The reminder text (without due date info)
-    action: String,
-    update_id: Option<i64>,
-    tags: Option<String>,            // Comma-separated tags
-    due_date: Option<String>,        // "2025-12-20" or null
+// How often to look for changes made outside the app (juli-cli, a SQL session, ...)
+const CHANGE_POLL_INTERVAL_MS: u64 = 2_000;
+
+// The app's own writes publish their changes right away; this catches everyone else's
+// Runs on its own thread: it only sleeps and queries, there's nothing to await
+fn run_change_poller(app: AppHandle) {
+    loop {
+        thread::sleep(Duration::from_millis(CHANGE_POLL_INTERVAL_MS));
+        publish_changes(&app);
+    }
 }
 
-#[derive(Debug, Deserialize)]
-struct AiAnalysisResponse {
-    reminders: Vec<AiExtractedReminder>,
-    reasoning: String,
+// app.emit() sends to every window, like socket.io's io.emit(event, payload)
+// A window that isn't listening is not an error worth failing a command over, so just log it
+fn emit_event<S: serde::Serialize + Clone>(app: &AppHandle, event: &str, payload: S) {
+    if let Err(e) = app.emit(event, payload) {
+        println!("⚠️ Failed to emit {}: {}", event, e);
+    }
 }
 
 // ============================================================================
 // DATABASE INITIALIZATION COMMAND
 // ============================================================================
 
-// This command sets up the database schema (creates tables if they don't exist)
-// In TypeScript/Node.js, you might do this in a migration file or setup script
+// This command makes sure the database schema is up to date
+// In TypeScript/Node.js, this is like running `knex migrate:latest`
 #[tauri::command]
-fn init_db(db: State<Db>) -> Result<(), String> {
+async fn init_db(db: State<'_, Db>) -> Result<(), JuliError> {
     // State<Db> is Tauri's way of passing shared state to commands
     // Think of it like dependency injection in TypeScript:
     // In Angular: constructor(private db: DbService)
     // In Express: function handler(req, res) { const db = req.app.locals.db }
     //
-    // Return type Result<(), String> means:
+    // Return type Result<(), JuliError> means:
     // - Success: Ok(()) - the empty tuple () is like TypeScript's void
-    // - Error: Err(String) - an error message as a string
+    // - Error: Err(JuliError) - a typed error the frontend can switch on (see error.rs)
     // This is Rust's way of handling errors instead of try/catch
 
-    // Access the database connection from our State wrapper
+    // Access the connection pool from our State wrapper
     // db.0 accesses the first (and only) field in the Db tuple struct
-    // .lock() acquires the Mutex lock (waits if another thread has it)
-    // .unwrap() says "panic if the lock is poisoned" (rare, usually means another thread crashed)
+    // migrate() takes the writer connection for itself (migrations need exclusive access)
+    // and runs them on a background thread; .await waits for it, just like in TypeScript
     //
-    // In TypeScript, you'd just access db.connection directly
-    // Rust makes thread safety explicit with Mutex
-    let conn = db.0.lock().unwrap();
-
-    // Execute multiple SQL statements at once
-    // execute_batch is like running multiple db.query() calls in TypeScript
+    // The schema itself lives in migrations.rs as numbered migrations
+    // The app already migrated the database at startup, so this is usually a no-op,
+    // but running it here means the frontend gets a real error message if it failed
     //
-    // r#"..."# is a "raw string literal" - no need to escape quotes inside
-    // In TypeScript: `SELECT * FROM "users"`
-    // In Rust raw string: r#"SELECT * FROM "users""#
-    conn.execute_batch(
-        r#"
-        PRAGMA journal_mode = WAL;
-
-        CREATE TABLE IF NOT EXISTS notes (
-          id INTEGER PRIMARY KEY AUTOINCREMENT,
-          text TEXT NOT NULL,
-          for_date TEXT NOT NULL UNIQUE
-        );
-
-        CREATE TABLE IF NOT EXISTS reminders (
-          id INTEGER PRIMARY KEY AUTOINCREMENT,
-          created_from_note_id INTEGER NOT NULL,
-          text TEXT NOT NULL,
-          resolved BOOLEAN NOT NULL DEFAULT FALSE,
-          tags TEXT
-        );
-
-        CREATE TABLE IF NOT EXISTS ai_interaction_logs (
-          id INTEGER PRIMARY KEY AUTOINCREMENT,
-          note_id INTEGER NOT NULL,
-          prompt TEXT NOT NULL,
-          response TEXT NOT NULL,
-          success BOOLEAN NOT NULL,
-          reasoning TEXT NOT NULL DEFAULT '',
-          reminders_count INTEGER NOT NULL DEFAULT 0,
-          created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
-        );
-
-        CREATE TABLE IF NOT EXISTS last_used_note_in_ai (
-          id INTEGER PRIMARY KEY CHECK (id = 1),
-          note_text TEXT NOT NULL
-        );
-        "#,
-    )
-    // map_err converts the rusqlite::Error to a String
-    // This is necessary because our function returns Result<(), String>
-    // In TypeScript: .catch(e => throw e.toString())
-    .map_err(|e| e.to_string())?;
-
-    // Add resolved_at column to existing reminders table (for analytics)
-    // This will fail silently if the column already exists
-    let _ = conn.execute("ALTER TABLE reminders ADD COLUMN resolved_at TEXT", ());
-
-    // Add created_at column to existing reminders table
-    // This will fail silently if the column already exists
-    // Nullable for backwards compatibility with existing reminders
-    let _ = conn.execute("ALTER TABLE reminders ADD COLUMN created_at TEXT", ());
-
-    // Add due_date column to existing reminders table
-    // Stores the due date as a date string (YYYY-MM-DD format)
-    // Nullable for reminders without specific due dates
-    let _ = conn.execute("ALTER TABLE reminders ADD COLUMN due_date TEXT", ());
     // The ? operator is shorthand for:
     // if error, return Err(error) immediately
     // if ok, unwrap and continue
+    // It also converts the MigrationError into our JuliError (see the From impls in error.rs)
     // In TypeScript, this is like: await query() with automatic error propagation
+    db.0.migrate().await?;
 
     // Return success
     // Ok(()) wraps the empty tuple in the Result type
@@ -208,636 +173,530 @@ fn init_db(db: State<Db>) -> Result<(), String> {
 // ADD/UPDATE NOTE COMMAND
 // ============================================================================
 
+// What add_note returns
+// In TypeScript: interface SavedNote { note_id: number; job_id: number }
+#[derive(serde::Serialize)]
+struct SavedNote {
+    note_id: i64,
+    // The analysis job for this note; its analysis:* events carry the same id
+    job_id: i64,
+}
+
 // This command inserts a new note or updates an existing one for a given date
-// In TypeScript: async function addNote(text: string, for_date: string): Promise<number>
+// In TypeScript: async function addNote(text: string, for_date: string): Promise<SavedNote>
+//
+// It only saves: the AI analysis is queued as a job and runs in the background (see
+// the ANALYSIS QUEUE section below), so the save is instant, with or without a network
 #[tauri::command]
-async fn add_note(app: AppHandle, db: State<'_, Db>, ai_lock: State<'_, AiLock>, text: String, for_date: String) -> Result<i64, String> {
+async fn add_note(
+    app: AppHandle,
+    worker: State<'_, AnalysisWorker>,
+    revisions: State<'_, RevisionSettings>,
+    timezone: State<'_, UserTimezone>,
+    text: String,
+    for_date: String,
+) -> Result<SavedNote, JuliError> {
     // Parameters:
-    // - db: State<Db> - our shared database connection (injected by Tauri)
-    // - ai_lock: State<AiLock> - lock to prevent concurrent AI analyses
+    // - app: AppHandle - gives write_db the database, and tells the frontend the analysis was queued
+    // - worker: State<AnalysisWorker> - wakes the background worker once the job is queued
+    // - revisions: State<RevisionSettings> - when a save starts a new revision in the note's history
+    // - timezone: State<UserTimezone> - what today's date is for the analysis job
     // - text: String - the note content (owned String, not a reference)
     // - for_date: String - the date this note is for
     //
-    // Return type Result<i64, String>:
-    // - Success: Ok(i64) - returns the row ID of the inserted/updated note
-    // - Error: Err(String) - error message if something goes wrong
-    // i64 is a 64-bit signed integer (TypeScript's number)
-
-    // Clone text before using it, since we'll need it again after the lock is released
-    let note_text = text.clone();
-    let for_date_clone = for_date.clone();
-
-    // Lock the database connection for thread-safe access
-    // Same pattern as init_db - acquire exclusive access to the database
-    let note_id = {
-        let conn = db.0.lock().unwrap();
-
-        // Execute a single SQL statement with parameters
-        // This uses "UPSERT" logic (INSERT or UPDATE if exists)
-        //
-        // Parameterized queries (?1, ?2):
-        // - ?1 refers to the first parameter (text)
-        // - ?2 refers to the second parameter (for_date)
-        // - This prevents SQL injection, just like prepared statements in TypeScript
-        // - In TypeScript: db.query('INSERT INTO notes VALUES ($1, $2)', [text, for_date])
-        //
-        // ON CONFLICT(for_date) DO UPDATE:
-        // - If a note for this date already exists, update it instead of failing
-        // - excluded.text refers to the value we tried to insert
-        // - Like: if (exists) { update() } else { insert() }
-        conn.execute(
-            "INSERT INTO notes (text, for_date) VALUES (?1, ?2)
-             ON CONFLICT(for_date) DO UPDATE SET text = excluded.text",
-            (text, for_date), // Tuple of parameters that match ?1 and ?2
-        )
-        // If execute() returns an error, convert it to String
-        .map_err(|e| e.to_string())?;
-
-        // Fetch the actual note ID by querying for the note with this date
-        // This works whether we inserted or updated
-        let mut stmt = conn.prepare("SELECT id FROM notes WHERE for_date = ?1")
-            .map_err(|e| e.to_string())?;
-
-        let note_id: i64 = stmt.query_row([for_date_clone], |row| row.get(0))
-            .map_err(|e| e.to_string())?;
-
-        note_id
-    };
+    // Return type Result<SavedNote, JuliError>:
+    // - Success: Ok(SavedNote) - the row ID of the inserted/updated note, and its analysis job
+    // - Error: Err(JuliError) - what went wrong, and what kind of failure it was
+
+    // write_db runs the closure on the writer connection, then sends note:saved
+    // move |conn| { ... } is a closure, like (conn) => { ... } in TypeScript; `move` hands it
+    // text, for_date and policy, because it runs on another thread
+    let policy = revisions.0.clone();
+    let today = note_date_key(today_in(timezone.0));
+    let saved = write_db(&app, move |conn| {
+        let note_id = NoteStore::new(conn)
+            .with_revision_policy(policy)
+            .save(&text, &for_date)?;
+        let job_id = AnalysisQueue::new(conn).enqueue(note_id, &today)?;
+        Ok(SavedNote { note_id, job_id })
+    })
+    .await?;
 
-    create_reminder_from_note(app, db, ai_lock, note_id, note_text).await?;
+    emit_event(&app, "analysis:queued", AnalysisQueued { job_id: saved.job_id, note_id: saved.note_id });
+    worker.0.notify_one();
 
-    Ok(note_id)
+    Ok(saved)
 }
 
 // ============================================================================
 // FETCH ALL NOTES COMMAND
 // ============================================================================
 
+// Reads go to one of the pool's reader connections, so they don't wait for a write
+// (like an analysis applying its reminders) to finish
 #[tauri::command]
-fn get_all_notes(db: State<Db>) -> Result<Vec<NoteRow>, String> {
-    let conn = db.0.lock().unwrap();
+async fn get_all_notes(db: State<'_, Db>) -> Result<Vec<NoteRow>, JuliError> {
+    db.0.read(|conn| NoteStore::new(conn).all()).await
+}
 
-    let mut stmt = conn.prepare("SELECT * FROM notes ORDER BY id").map_err(|e| e.to_string())?;
+#[tauri::command]
+async fn get_notes_for_date(db: State<'_, Db>, for_date: String) -> Result<NoteRow, JuliError> {
+    db.0.read(move |conn| NoteStore::new(conn).for_date(&for_date)).await
+}
 
-    let notes = stmt.query_map([], |row| {
-        Ok(NoteRow {
-            id: row.get(0)?,
-            text: row.get(1)?,
-            for_date: row.get(2)?,
-        })
-    })
-    .map_err(|e| e.to_string())?
-    .collect::<Result<Vec<_>, _>>()
-    .map_err(|e| e.to_string())?;
+// ============================================================================
+// NOTE HISTORY COMMANDS
+// ============================================================================
 
-    Ok(notes)
+// Every saved version of a date's note, newest first
+#[tauri::command]
+async fn get_note_revisions(db: State<'_, Db>, for_date: String) -> Result<Vec<NoteRevisionRow>, JuliError> {
+    db.0.read(move |conn| {
+        let note = NoteStore::new(conn).for_date(&for_date)?;
+        RevisionStore::new(conn).for_note(note.id)
+    })
+    .await
 }
 
 #[tauri::command]
-fn get_notes_for_date(db: State<Db>, for_date: String) -> Result<NoteRow, String> {
-    let conn = db.0.lock().unwrap();
-
-    let mut stmt = conn.prepare("SELECT * FROM notes WHERE for_date = ?1 ORDER BY id").map_err(|e| e.to_string())?;
+async fn diff_note_revisions(db: State<'_, Db>, from_revision_id: i64, to_revision_id: i64) -> Result<RevisionDiff, JuliError> {
+    db.0.read(move |conn| RevisionStore::new(conn).diff(from_revision_id, to_revision_id)).await
+}
 
-    let note = stmt.query_row([for_date], |row| {
-        Ok(NoteRow {
-            id: row.get(0)?,
-            text: row.get(1)?,
-            for_date: row.get(2)?,
-        })
+// Returns the note with the restored text so the editor can show it right away
+#[tauri::command]
+async fn restore_note_revision(
+    app: AppHandle,
+    revisions: State<'_, RevisionSettings>,
+    revision_id: i64,
+) -> Result<NoteRow, JuliError> {
+    let policy = revisions.0.clone();
+    write_db(&app, move |conn| {
+        NoteStore::new(conn)
+            .with_revision_policy(policy)
+            .restore_revision(revision_id)
     })
-    .map_err(|e| e.to_string())?;
-
-    Ok(note)
+    .await
 }
 
 // This command retrieves all notes from the database
 // It also prints them to the console for debugging (you'll see this in your terminal)
 // In TypeScript: async function getAllNotes(): Promise<NoteRow[]>
 #[tauri::command]
-fn print_all_tables(db: State<Db>) -> Result<Vec<NoteRow>, String> {
-    // Return type Result<Vec<NoteRow>, String>:
+async fn print_all_tables(db: State<'_, Db>) -> Result<Vec<NoteRow>, JuliError> {
+    // Return type Result<Vec<NoteRow>, JuliError>:
     // - Success: Ok(Vec<NoteRow>) - returns a vector (array) of NoteRow structs
-    // - Error: Err(String) - error message
+    // - Error: Err(JuliError) - typed error
     // Vec<NoteRow> is like TypeScript's NoteRow[]
 
-    // Lock the database connection
-    let conn = db.0.lock().unwrap();
-
-    // ========================================================================
-    // PART 1: Print to console for debugging
-    // ========================================================================
+    // Everything below runs on a reader connection, inside the closure
+    db.0.read(|conn| {
+        // ========================================================================
+        // PART 1: Print to console for debugging
+        // ========================================================================
 
-    // println! is Rust's version of console.log
-    // The \n adds a blank line before the output
-    println!("\n📊 Notes Table:");
+        // println! is Rust's version of console.log
+        // The \n adds a blank line before the output
+        println!("\n📊 Notes Table:");
 
-    // "=".repeat(80) creates a string of 80 equal signs (like "=".repeat(80) in JS)
-    // {} is a placeholder for the string (like ${} in template literals)
-    println!("{}", "=".repeat(80));
+        // "=".repeat(80) creates a string of 80 equal signs (like "=".repeat(80) in JS)
+        // {} is a placeholder for the string (like ${} in template literals)
+        println!("{}", "=".repeat(80));
 
-    // Use the pretty-sqlite library to print a nicely formatted table
-    // match is like a switch statement, but more powerful
-    // It handles both success (Ok) and error (Err) cases
-    //
-    // &*conn is a bit complex:
-    // - conn is a MutexGuard (the locked reference)
-    // - *conn dereferences it to get the Connection
-    // - &*conn takes a reference to that Connection
-    // - This is needed because print_select expects &Connection
-    // In TypeScript, you wouldn't need to worry about these reference conversions
-    match pretty_sqlite::print_select(&*conn, "SELECT * FROM notes ORDER BY id", []) {
-        Ok(_) => {},  // Success - the underscore means we ignore the return value
-        Err(e) => println!("Error formatting table: {}", e), // Print error if it fails
-    }
+        // Use the pretty-sqlite library to print a nicely formatted table
+        // match is like a switch statement, but more powerful
+        // It handles both success (Ok) and error (Err) cases
+        //
+        // conn is already a &Connection, which is what print_select expects
+        match pretty_sqlite::print_select(conn, "SELECT * FROM notes ORDER BY id", []) {
+            Ok(_) => {},  // Success - the underscore means we ignore the return value
+            Err(e) => println!("Error formatting table: {}", e), // Print error if it fails
+        }
 
-    println!("{}", "=".repeat(80));
+        println!("{}", "=".repeat(80));
 
-    // Print reminders table
-    println!("\n🔔 Reminders Table:");
-    println!("{}", "=".repeat(80));
+        // Print reminders table
+        println!("\n🔔 Reminders Table:");
+        println!("{}", "=".repeat(80));
 
-    match pretty_sqlite::print_select(&*conn, "SELECT * FROM reminders ORDER BY id", []) {
-        Ok(_) => {},
-        Err(e) => println!("Error formatting table: {}", e),
-    }
+        match pretty_sqlite::print_select(conn, "SELECT * FROM reminders ORDER BY id", []) {
+            Ok(_) => {},
+            Err(e) => println!("Error formatting table: {}", e),
+        }
 
-    println!("{}", "=".repeat(80));
+        println!("{}", "=".repeat(80));
 
-    // ========================================================================
-    // PART 2: Fetch data to return to the frontend
-    // ========================================================================
+        // ========================================================================
+        // PART 2: Fetch data to return to the frontend
+        // ========================================================================
 
-    // Prepare a SQL statement (like creating a prepared statement in TypeScript)
-    // .prepare() returns Result<Statement, Error>
-    // We use ? to propagate any errors up to the caller
-    //
-    // let mut stmt means "mutable statement" - we need to mutate it to query it
-    // In TypeScript, everything is mutable by default
-    // In Rust, everything is immutable unless you explicitly say mut
-    let mut stmt = conn
-        .prepare("SELECT id, text, for_date FROM notes ORDER BY id")
-        .map_err(|e| e.to_string())?;
-
-    // Query the database and map each row to a NoteRow struct
-    // This is a complex chain of operations:
-    let notes = stmt
-        // query_map takes parameters ([]) and a closure (|row| {...})
-        // The closure runs for each row in the result set
-        // In TypeScript: rows.map(row => ({ id: row.id, text: row.text, ... }))
-        .query_map([], |row| {
-            // |row| is a closure parameter (like an arrow function parameter)
-            // row.get(0) gets the first column (id)
-            // row.get(1) gets the second column (text)
-            // row.get(2) gets the third column (for_date)
-            // The ? propagates errors within this closure
-            Ok(NoteRow {
-                id: row.get(0)?,      // Get column 0 (id) from the row
-                text: row.get(1)?,    // Get column 1 (text)
-                for_date: row.get(2)?, // Get column 2 (for_date)
-            })
-        })
-        .map_err(|e| e.to_string())?
-        // At this point we have an iterator of Result<NoteRow, Error>
-        // .collect() converts the iterator into a Vec<NoteRow>
-        // The turbofish ::<Result<Vec<_>, _>> tells Rust what type to collect into
-        // Result<Vec<_>, _> means: Result containing a Vec of something, or an error
-        // The underscores (_) let Rust infer the specific types
-        .collect::<Result<Vec<_>, _>>()
-        .map_err(|e| e.to_string())?;
-        // After collecting, we have Result<Vec<NoteRow>, Error>
-        // The ? unwraps it, returning early if there's an error
-
-    // Return the notes wrapped in Ok
-    // In TypeScript: return notes;
-    // In Rust: return Ok(notes); (but we omit 'return' for the last expression)
-    Ok(notes)
+        // The notes are read through the same NoteStore the other commands use (see core/notes.rs)
+        NoteStore::new(conn).all()
+    })
+    .await
 }
 
 // ============================================================================
-// API KEY CONFIGURATION
+// API KEY / AI PROVIDER CONFIGURATION
 // ============================================================================
 
-// Get the API key from either .env (dev) or config.json (production)
-// This ensures the app works both during development and in packaged builds
-#[tauri::command]
-fn get_api_key(app: AppHandle) -> Result<String, String> {
-    // Try .env first (for development)
-    if let Ok(key) = env::var("CLAUDE_API_KEY") {
-        println!("✓ Using API key from .env file");
-        return Ok(key);
-    }
-
-    // Fallback to config.json (for production/packaged builds)
-    load_api_key_from_config(app)
-}
-
-// Load the API key from config.json in the app's config directory
-fn load_api_key_from_config(app: AppHandle) -> Result<String, String> {
-    // Get the app config directory path
-    // macOS: ~/Library/Application Support/<app-name>/config.json
-    // Windows: C:\Users\<user>\AppData\Roaming\<app-name>\config.json
-    // Linux: ~/.config/<app-name>/config.json
+// Where config.json lives:
+// macOS: ~/Library/Application Support/<app-name>/config.json
+// Windows: C:\Users\<user>\AppData\Roaming\<app-name>\config.json
+// Linux: ~/.config/<app-name>/config.json
+fn config_file_path(app: &AppHandle) -> Result<PathBuf, JuliError> {
     let config_dir = app
         .path()
         .app_config_dir()
-        .map_err(|e| format!("Failed to resolve app config dir: {}", e))?;
-
-    let config_path = config_dir.join("config.json");
+        .map_err(|e| JuliError::config("Failed to resolve app config dir").with_details(e.to_string()))?;
 
-    // Read the config file
-    let contents = fs::read_to_string(&config_path)
-        .map_err(|_| {
-            format!(
-                "config.json not found. Create it at: {}\n\nExample content:\n{{\n  \"claude_api_key\": \"sk-...\"\n}}",
-                config_path.display()
-            )
-        })?;
+    Ok(config_dir.join("config.json"))
+}
 
-    // Parse the JSON
-    let config: AppConfig = serde_json::from_str(&contents)
-        .map_err(|e| format!("Invalid config.json: {}", e))?;
+// Get the Anthropic API key from either .env (dev) or config.json (production)
+#[tauri::command]
+fn get_api_key(app: AppHandle) -> Result<String, JuliError> {
+    let config_path = config_file_path(&app)?;
+
+    load_config(&config_path)?
+        .claude_api_key
+        .filter(|key| !key.is_empty())
+        .ok_or_else(|| {
+            // Only tell the user to create config.json when there isn't one
+            let message = if config_path.exists() {
+                format!(
+                    "No claude_api_key in {}\n\nAdd it like this:\n  \"claude_api_key\": \"sk-...\"",
+                    config_path.display()
+                )
+            } else {
+                format!(
+                    "config.json not found. Create it at: {}\n\nExample content:\n{{\n  \"claude_api_key\": \"sk-...\"\n}}",
+                    config_path.display()
+                )
+            };
+            JuliError::config(message).with_details(config_path.display().to_string())
+        })
+}
 
-    println!("✓ Using API key from config.json at: {}", config_path.display());
-    Ok(config.claude_api_key)
+// Build whichever AI provider the config selects (Anthropic by default)
+// Also returns the config, for the options that aren't about the provider (like review_changes and timezone)
+fn ai_provider_from_config(app: &AppHandle) -> Result<(Box<dyn AiProvider>, AppConfig), JuliError> {
+    let config_path = config_file_path(app)?;
+    let config = load_config(&config_path)?;
+    let provider = config
+        .provider()
+        .map_err(|e| e.with_details(format!("config.json location: {}", config_path.display())))?;
+    Ok((provider, config))
 }
 
+// Send a prompt to the configured provider and return its text answer
+// Kept under its original name because the frontend's debug button calls it
 #[tauri::command]
-async fn test_claude_api(app: AppHandle, prompt: String) -> Result<String, String> {
-    let api_key = get_api_key(app)?;
+async fn test_claude_api(app: AppHandle, prompt: String) -> Result<String, JuliError> {
+    let (provider, _) = ai_provider_from_config(&app)?;
 
-    let client = reqwest::Client::new();
+    let completion = provider.complete(&prompt, &CompletionOptions::default()).await?;
 
-    let body = serde_json::json!({
-        "model": "claude-sonnet-4-20250514",
-        "max_tokens": 1024,
-        "messages": [
-            {"role": "user", "content": prompt}
-        ]
-    });
+    Ok(ai::strip_code_fences(&completion.text))
+}
 
-    let response: reqwest::Response = client
-    .post("https://api.anthropic.com/v1/messages")
-    .header("x-api-key", &api_key)
-    .header("anthropic-version", "2023-06-01")
-    .header("content-type", "application/json")
-    .json(&body)
-    .send()
-    .await
-    .map_err(|e| format!("API request failed: {}", e))?;
+// The reminder lists take an optional sort: 'due' (the default), 'priority' or 'created' (newest first)
+// In TypeScript: invoke<Reminder[]>('get_unresolved_reminders', { sort: 'priority' })
+#[tauri::command]
+async fn get_all_reminders(db: State<'_, Db>, sort: Option<ReminderSort>) -> Result<Vec<ReminderRow>, JuliError> {
+    db.0.read(move |conn| ReminderStore::new(conn).with_sort(sort.unwrap_or_default()).all()).await
+}
 
-    if !response.status().is_success() {
-        let error_text = response.text().await.unwrap_or_default();
-        return Err(format!("API error: {}", error_text));
-    }
+#[tauri::command]
+async fn get_unresolved_reminders(db: State<'_, Db>, sort: Option<ReminderSort>) -> Result<Vec<ReminderRow>, JuliError> {
+    db.0.read(move |conn| ReminderStore::new(conn).with_sort(sort.unwrap_or_default()).unresolved()).await
+}
 
-    let response_json: serde_json::Value = response
-        .json()
-        .await
-        .map_err(|e| format!("Failed to parse response: {}", e))?;
-
-    // Extract the text content from Claude's response
-    let content = response_json["content"][0]["text"]
-        .as_str()
-        .ok_or("Nos text in response")?;
-
-    let mut content_string = content.to_string();
-    content_string = content_string.replace("```json", "");
-    content_string = content_string.replace("```", "");
-
-    Ok(content_string)
-}
-
-// This is the AI prompt we'll send to analyze notes
-fn build_analysis_prompt(note_text: &str, current_date: &str, reminders: &Vec<ReminderRow>) -> String {
-    let reminders_text = reminders.iter().map(|reminder| {
-        let due_info = reminder.due_date.as_deref().map(|d| format!(", due: {}", d)).unwrap_or_default();
-        format!("{}: {} (tags: {}{})", reminder.id, reminder.text, reminder.tags.as_deref().unwrap_or("none"), due_info)
-    }).collect::<Vec<String>>().join("\n");
-    let reminders_prompt = if reminders_text.is_empty() {
-        "".to_string()
-    } else {
-        format!("These are the existing reminders. Before creating a new reminder, check if it matches an existing one: \n{}", reminders_text)
-    };
-
-    format!(r#"You are analyzing a note to extract actionable reminders. Today's date is {}.
-
-Analyze this note and extract any tasks, reminders, or action items. For each one, determine:
-1. The reminder text (what needs to be done) - DO NOT include the due date in the text
-2. The due date as a separate field (if mentioned or implied) in YYYY-MM-DD format
-3. Tags (if the user ends a sentence with --[comma separated list])
-
-Common patterns to recognize for due_date:
-- "before eow" / "by end of week" = Friday of current week
-- "before eom" / "by end of month" = last day of month
-- "tomorrow" = next day
-- "today" / "eod" = same day
-- "next week" = 7 days from now
-- Specific dates like "Dec 20" or "12/20"
-- No deadline mentioned = null for due_date
-
-For tags:
-- If a sentence ends with --[tag1, tag2, tag3], extract those as tags,
-- Remove the --[tags] part from the reminder text
-- Store tags as a comma-separated string like "tag1,tag2,tag3"
-- Example: "Call John about the project --[work, urgent]" should extract tags "work,urgent" and text "Call John about the project"
-- If no tags are specified, use null
-- Note that the user may provide tags in a different format em dash or double dash or single dash, use context to understand what is a tag
-
-CRITICAL: The reminder text should be CLEAN - do NOT include due date information in the text field.
-- BAD: "Message Jon about the project (due date: 2025-12-20)"
-- GOOD: "Message Jon about the project" with due_date: "2025-12-20" as a separate field
-
-CRITICAL DUPLICATE DETECTION RULES:
-- FIRST, check if the note is referring to an EXISTING reminder about the same task/subject
-- Match reminders by semantic similarity, not just exact text match. Examples:
-  * "finish simpli rewind" matches "simpli rewind should happen before end of year" → UPDATE the existing reminder
-  * "call John" matches "call John about the project" → UPDATE the existing reminder
-  * "review PR" matches "review the pull request" → UPDATE the existing reminder
-- If a new note provides additional information (like a due date) for an existing task, ALWAYS UPDATE the existing reminder instead of creating a new one
-- If a reminder already exists with the EXACT SAME text, tags, and due_date, DO NOT include it in your response at all (no CREATE, no UPDATE)
-- ONLY use UPDATE action if:
-  * The note is about the same task/subject as an existing reminder (even if wording differs slightly), OR
-  * The tags or due_date have actually CHANGED (different from what currently exists)
-- When updating, use the most complete/clear version of the text from either the existing reminder or the new note
-- If the reminder text, tags, and due_date are identical to an existing reminder, simply omit it from your response
-- Do NOT update a reminder just to "confirm" values remain the same - that's a waste of database operations
-- When in doubt about whether something matches an existing reminder, prefer UPDATE over CREATE
-
-Respond ONLY with valid JSON in this exact format, just straight JSON, no template literals or anything else:
-{{
-  "reminders": [
-    {{
-      "text": "Message Jon about the project",
-      "action": "CREATE" | "UPDATE",
-      "update_id": 1,
-      "tags": "work,urgent",
-      "due_date": "2025-12-20"
-    }}
-  ],
-  "reasoning": "Explain your decision here - why you extracted these reminders, or why you found no actionable items in the note."
-}}
-
-{}
-
-If there are no actionable items, respond with:
-{{"reminders": [], "reasoning": "No actionable tasks or deadlines found in this note."}}
-
-Note to analyze:
-{}
-"#, current_date, reminders_prompt, note_text)
-}
-
-fn get_all_reminders_impl(db: &State<'_, Db>) -> Result<Vec<ReminderRow>, String> {
-    let conn = db.0.lock().unwrap();
-    // Order by due_date first (NULLs at end), then by created_at
-    let mut stmt = conn.prepare("SELECT id, created_from_note_id, text, resolved, tags, created_at, due_date FROM reminders ORDER BY due_date IS NULL, due_date, created_at IS NULL, created_at, id").map_err(|e| e.to_string())?;
-    let reminders = stmt.query_map([], |row| {
-        Ok(ReminderRow {
-            id: row.get(0)?,
-            created_from_note_id: row.get(1)?,
-            text: row.get(2)?,
-            resolved: row.get(3)?,
-            tags: row.get(4)?,
-            created_at: row.get(5)?,
-            due_date: row.get(6)?,
-        })
-    })
-    .map_err(|e| e.to_string())?
-    .collect::<Result<Vec<_>, _>>()
-    .map_err(|e| e.to_string())?;
+#[tauri::command]
+async fn get_resolved_reminders(db: State<'_, Db>, sort: Option<ReminderSort>) -> Result<Vec<ReminderRow>, JuliError> {
+    db.0.read(move |conn| ReminderStore::new(conn).with_sort(sort.unwrap_or_default()).resolved()).await
+}
 
-    Ok(reminders)
+// Which lines of which notes created or updated a reminder, oldest first
+#[tauri::command]
+async fn get_reminder_sources(db: State<'_, Db>, reminder_id: i64) -> Result<Vec<ReminderSourceRow>, JuliError> {
+    db.0.read(move |conn| ReminderSourceStore::new(conn).for_reminder(reminder_id)).await
 }
 
+// Full-text search over notes and reminders, best matches first
+// In TypeScript: invoke('search', { query: { query: 'john', tags: ['work'], resolved: false } })
 #[tauri::command]
-fn get_all_reminders(db: State<'_, Db>) -> Result<Vec<ReminderRow>, String> {
-    get_all_reminders_impl(&db)
+async fn search(db: State<'_, Db>, query: SearchQuery) -> Result<Vec<SearchHit>, JuliError> {
+    db.0.read(move |conn| SearchIndex::new(conn).search(&query)).await
 }
 
+// Returns the id of the next occurrence when the reminder repeats (see core/recurrence.rs)
+// In TypeScript: const nextId = await invoke<number | null>('resolve_reminder', { reminderId: 3 })
 #[tauri::command]
-fn get_unresolved_reminders(db: State<'_, Db>) -> Result<Vec<ReminderRow>, String> {
-    let conn = db.0.lock().unwrap();
-    // Order by due_date first (NULLs at end), then by created_at
-    let mut stmt = conn.prepare("SELECT id, created_from_note_id, text, resolved, tags, created_at, due_date FROM reminders WHERE resolved = 0 ORDER BY due_date IS NULL, due_date, created_at IS NULL, created_at, id").map_err(|e| e.to_string())?;
-    let reminders = stmt.query_map([], |row| {
-        Ok(ReminderRow {
-            id: row.get(0)?,
-            created_from_note_id: row.get(1)?,
-            text: row.get(2)?,
-            resolved: row.get(3)?,
-            tags: row.get(4)?,
-            created_at: row.get(5)?,
-            due_date: row.get(6)?,
-        })
-    })
-    .map_err(|e| e.to_string())?
-    .collect::<Result<Vec<_>, _>>()
-    .map_err(|e| e.to_string())?;
-
-    Ok(reminders)
-}
-
-#[tauri::command]
-fn get_resolved_reminders(db: State<'_, Db>) -> Result<Vec<ReminderRow>, String> {
-    let conn = db.0.lock().unwrap();
-    // Order by due_date first (NULLs at end), then by created_at
-    let mut stmt = conn.prepare("SELECT id, created_from_note_id, text, resolved, tags, created_at, due_date FROM reminders WHERE resolved = 1 ORDER BY due_date IS NULL, due_date, created_at IS NULL, created_at, id").map_err(|e| e.to_string())?;
-    let reminders = stmt.query_map([], |row| {
-        Ok(ReminderRow {
-            id: row.get(0)?,
-            created_from_note_id: row.get(1)?,
-            text: row.get(2)?,
-            resolved: row.get(3)?,
-            tags: row.get(4)?,
-            created_at: row.get(5)?,
-            due_date: row.get(6)?,
-        })
-    })
-    .map_err(|e| e.to_string())?
-    .collect::<Result<Vec<_>, _>>()
-    .map_err(|e| e.to_string())?;
+async fn resolve_reminder(app: AppHandle, timezone: State<'_, UserTimezone>, reminder_id: i64) -> Result<Option<i64>, JuliError> {
+    let timezone = timezone.0;
+    write_db(&app, move |conn| ReminderStore::new(conn).with_timezone(timezone).resolve(reminder_id)).await
+}
+
+#[tauri::command]
+async fn unresolve_reminder(app: AppHandle, reminder_id: i64) -> Result<(), JuliError> {
+    write_db(&app, move |conn| ReminderStore::new(conn).unresolve(reminder_id)).await
+}
 
-    Ok(reminders)
+// The user's own priority for a reminder; the AI doesn't change it after this
+// In TypeScript: const reminder = await invoke<Reminder>('set_reminder_priority', { reminderId: 3, priority: 'urgent' })
+// A priority other than low, normal, high or urgent (or p1 to p4) is a Validation error
+#[tauri::command]
+async fn set_reminder_priority(app: AppHandle, reminder_id: i64, priority: String) -> Result<ReminderRow, JuliError> {
+    write_db(&app, move |conn| ReminderStore::new(conn).set_priority(reminder_id, &priority)).await
 }
 
+// Push a reminder out to a later date, keeping its time of day
+// In TypeScript: const reminder = await invoke<Reminder>('snooze_reminder', { reminderId: 3, until: 'next monday' })
+// until is a date (YYYY-MM-DD) or "1d", "2w", "tomorrow", "next monday", "next week", "eow" (see core/snooze.rs)
 #[tauri::command]
-async fn create_reminder_from_note(app: AppHandle, db: State<'_, Db>, ai_lock: State<'_, AiLock>, note_id: i64, note_text: String) -> Result<(), String> {
-    // Acquire the AI lock to ensure only one analysis runs at a time
-    // This prevents race conditions from rapid successive saves
-    let _lock = ai_lock.0.lock().await;
+async fn snooze_reminder(app: AppHandle, timezone: State<'_, UserTimezone>, reminder_id: i64, until: String) -> Result<ReminderRow, JuliError> {
+    let timezone = timezone.0;
+    write_db(&app, move |conn| ReminderStore::new(conn).with_timezone(timezone).snooze(reminder_id, &until)).await
+}
 
-    // Check if this note has already been processed by AI
-    // If the note text is identical to the last one processed, skip AI analysis
-    {
-        let conn = db.0.lock().unwrap();
-        let mut stmt = conn.prepare("SELECT note_text FROM last_used_note_in_ai WHERE id = 1")
-            .map_err(|e| e.to_string())?;
+#[tauri::command]
+async fn delete_reminder(app: AppHandle, reminder_id: i64) -> Result<(), JuliError> {
+    write_db(&app, move |conn| ReminderStore::new(conn).delete(reminder_id)).await
+}
 
-        let last_note_text: Result<String, _> = stmt.query_row([], |row| row.get(0));
+// Add a reminder by hand, without a note or the AI
+// In TypeScript: invoke('create_reminder', { reminder: { text: 'Call John', tags: 'work', due_date: '2026-10-20', due_time: '15:00' } })
+// Empty text, a due date that isn't YYYY-MM-DD, a time that isn't HH:MM or a recurrence we can't read is a Validation error
+// The due time is in the user's timezone unless the draft names another one
+#[tauri::command]
+async fn create_reminder(app: AppHandle, timezone: State<'_, UserTimezone>, reminder: ReminderDraft) -> Result<ReminderRow, JuliError> {
+    let timezone = timezone.0;
+    write_db(&app, move |conn| {
+        let reminders = ReminderStore::new(conn).with_timezone(timezone);
+        let reminder_id = reminders.create_manual(reminder)?;
+        reminders.get(reminder_id)
+    })
+    .await
+}
 
-        // If we found a previous note and it matches the current one, skip AI processing
-        if let Ok(last_text) = last_note_text {
-            if last_text == note_text {
-                println!("⏭️  Skipping AI analysis - note unchanged from last AI processing");
-                return Ok(());
-            }
-        }
-    }
+// Change a reminder's text, tags, due date and time, and/or recurrence - fields left out keep their value
+// In TypeScript: invoke('update_reminder', { reminderId: 3, patch: { due_date: '2026-10-23', due_time: '09:00' } })
+// Send null to clear tags, the due date, the time or the recurrence: { patch: { due_time: null } }
+#[tauri::command]
+async fn update_reminder(
+    app: AppHandle,
+    timezone: State<'_, UserTimezone>,
+    reminder_id: i64,
+    patch: ReminderPatch,
+) -> Result<ReminderRow, JuliError> {
+    let timezone = timezone.0;
+    write_db(&app, move |conn| ReminderStore::new(conn).with_timezone(timezone).edit(reminder_id, patch)).await
+}
 
-    let current_date = get_formatted_date();
-    let reminders = get_all_reminders_impl(&db)?;
-
-    let prompt = build_analysis_prompt(note_text.as_str(), &current_date, &reminders);
-
-    // Try to call the AI API and log the result
-    let api_result = test_claude_api(app, prompt.clone()).await;
-
-    match api_result {
-        Ok(response) => {
-            // Try to parse the AI response
-            match serde_json::from_str::<AiAnalysisResponse>(&response) {
-                Ok(analysis) => {
-                    // Success! Insert reminders
-                    let conn = db.0.lock().unwrap();
-                    let reminders_count = analysis.reminders.len() as i64;
-
-                    for extracted in &analysis.reminders {
-                        if extracted.action == "CREATE" {
-                            conn.execute(
-                                "INSERT INTO reminders (created_from_note_id, text, tags, due_date, created_at) VALUES (?1, ?2, ?3, ?4, datetime('now'))",
-                                (note_id, &extracted.text, &extracted.tags, &extracted.due_date),
-                            )
-                            .map_err(|e| e.to_string())?;
-                        } else if extracted.action == "UPDATE" {
-                            conn.execute(
-                                "UPDATE reminders SET text = ?1, tags = ?2, due_date = ?3 WHERE id = ?4",
-                                (&extracted.text, &extracted.tags, &extracted.due_date, &extracted.update_id)
-                            ).map_err(|e| e.to_string())?;
-
-                        }
-                    }
+// ============================================================================
+// REVIEW MODE PROPOSALS
+// ============================================================================
 
-                    // Log successful AI interaction
-                    conn.execute(
-                        "INSERT INTO ai_interaction_logs (note_id, prompt, response, success, reasoning, reminders_count) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-                        (note_id, &prompt, &response, true, &analysis.reasoning, reminders_count),
-                    )
-                    .map_err(|e| e.to_string())?;
-
-                    // Update the last used note in AI table
-                    // This uses UPSERT logic to either insert or update the single row
-                    conn.execute(
-                        "INSERT INTO last_used_note_in_ai (id, note_text) VALUES (1, ?1)
-                         ON CONFLICT(id) DO UPDATE SET note_text = excluded.note_text",
-                        (&note_text,),
-                    )
-                    .map_err(|e| e.to_string())?;
-
-                    Ok(())
-                },
-                Err(e) => {
-                    // Failed to parse AI response
-                    let error_msg = format!("Failed to parse AI response as JSON: {}. Response was: {}", e, response);
-                    let conn = db.0.lock().unwrap();
-
-                    // Log failed AI interaction
-                    conn.execute(
-                        "INSERT INTO ai_interaction_logs (note_id, prompt, response, success, reasoning, reminders_count) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-                        (note_id, &prompt, &error_msg, false, "", 0),
-                    )
-                    .map_err(|e| e.to_string())?;
-
-                    Err(error_msg)
-                }
-            }
-        },
-        Err(e) => {
-            // AI API call failed
-            let error_msg = format!("AI API call failed: {}", e);
-            let conn = db.0.lock().unwrap();
-
-            // Log failed AI interaction
-            conn.execute(
-                "INSERT INTO ai_interaction_logs (note_id, prompt, response, success, reasoning, reminders_count) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-                (note_id, &prompt, &error_msg, false, "", 0),
-            )
-            .map_err(|e| e.to_string())?;
-
-            Err(error_msg)
-        }
-    }
+// The AI's suggested changes that haven't been accepted or rejected yet
+// Only ever filled when "review_changes" is on in config.json
+#[tauri::command]
+async fn get_pending_proposals(db: State<'_, Db>) -> Result<Vec<ProposalRow>, JuliError> {
+    db.0.read(|conn| ProposalStore::new(conn).pending()).await
 }
 
+// Returns the id of the reminder that was created or updated
+// In TypeScript: invoke('accept_proposal', { proposalId: 3 })
 #[tauri::command]
-fn resolve_reminder(db: State<'_, Db>, reminder_id: i64) -> Result<(), String> {
-    let conn = db.0.lock().unwrap();
-    conn.execute(
-        "UPDATE reminders SET resolved = 1, resolved_at = datetime('now') WHERE id = ?1",
-        (reminder_id,),
-    )
-    .map_err(|e| e.to_string())?;
-    Ok(())
+async fn accept_proposal(app: AppHandle, timezone: State<'_, UserTimezone>, proposal_id: i64) -> Result<i64, JuliError> {
+    let timezone = timezone.0;
+    write_db(&app, move |conn| ProposalStore::new(conn).with_timezone(timezone).accept(proposal_id, None)).await
 }
 
+// Accept, but write the user's version instead of the AI's
+// In TypeScript: invoke('accept_proposal_with_edits', { proposalId: 3, edits: { text: '...', tags: 'work', due_date: null } })
 #[tauri::command]
-fn unresolve_reminder(db: State<'_, Db>, reminder_id: i64) -> Result<(), String> {
-    let conn = db.0.lock().unwrap();
-    conn.execute(
-        "UPDATE reminders SET resolved = 0, resolved_at = NULL WHERE id = ?1",
-        (reminder_id,),
-    )
-    .map_err(|e| e.to_string())?;
-    Ok(())
+async fn accept_proposal_with_edits(
+    app: AppHandle,
+    timezone: State<'_, UserTimezone>,
+    proposal_id: i64,
+    edits: ReminderDraft,
+) -> Result<i64, JuliError> {
+    let timezone = timezone.0;
+    write_db(&app, move |conn| ProposalStore::new(conn).with_timezone(timezone).accept(proposal_id, Some(edits))).await
 }
 
 #[tauri::command]
-fn delete_reminder(db: State<'_, Db>, reminder_id: i64) -> Result<(), String> {
-    let conn = db.0.lock().unwrap();
-    conn.execute("DELETE FROM reminders WHERE id = ?1", (reminder_id,))
-        .map_err(|e| e.to_string())?;
-    Ok(())
+async fn reject_proposal(db: State<'_, Db>, proposal_id: i64) -> Result<(), JuliError> {
+    db.0.write(move |conn| ProposalStore::new(conn).reject(proposal_id)).await
 }
 
 #[tauri::command]
-fn get_all_ai_logs(db: State<'_, Db>) -> Result<Vec<AiLogRow>, String> {
-    let conn = db.0.lock().unwrap();
-    let mut stmt = conn.prepare("SELECT * FROM ai_interaction_logs ORDER BY id DESC").map_err(|e| e.to_string())?;
-    let logs = stmt.query_map([], |row| {
-        Ok(AiLogRow {
-            id: row.get(0)?,
-            note_id: row.get(1)?,
-            prompt: row.get(2)?,
-            response: row.get(3)?,
-            success: row.get(4)?,
-            reasoning: row.get(5)?,
-            reminders_count: row.get(6)?,
-            created_at: row.get(7)?,
-        })
-    })
-    .map_err(|e| e.to_string())?
-    .collect::<Result<Vec<_>, _>>()
-    .map_err(|e| e.to_string())?;
+async fn get_all_ai_logs(db: State<'_, Db>) -> Result<Vec<AiLogRow>, JuliError> {
+    db.0.read(|conn| AiLogStore::new(conn).all()).await
+}
 
-    Ok(logs)
+#[tauri::command]
+async fn delete_ai_log(db: State<'_, Db>, log_id: i64) -> Result<(), JuliError> {
+    db.0.write(move |conn| AiLogStore::new(conn).delete(log_id)).await
 }
 
 #[tauri::command]
-fn delete_ai_log(db: State<'_, Db>, log_id: i64) -> Result<(), String> {
-    let conn = db.0.lock().unwrap();
-    conn.execute("DELETE FROM ai_interaction_logs WHERE id = ?1", (log_id,))
-        .map_err(|e| e.to_string())?;
-    Ok(())
+async fn delete_all_ai_logs(db: State<'_, Db>) -> Result<(), JuliError> {
+    db.0.write(|conn| AiLogStore::new(conn).delete_all()).await
 }
 
+// ============================================================================
+// ANALYSIS QUEUE
+// ============================================================================
+
+// Notes waiting to be analyzed, the next one first
+// A job with attempts > 0 is waiting for the provider to be reachable again
 #[tauri::command]
-fn delete_all_ai_logs(db: State<'_, Db>) -> Result<(), String> {
-    let conn = db.0.lock().unwrap();
-    conn.execute("DELETE FROM ai_interaction_logs", ())
-        .map_err(|e| e.to_string())?;
+async fn get_pending_analyses(db: State<'_, Db>) -> Result<Vec<PendingAnalysis>, JuliError> {
+    db.0.read(|conn| AnalysisQueue::new(conn).all()).await
+}
+
+// The longest the worker sleeps without being woken up
+// Retries are scheduled in the database, so this is only a safety net
+const ANALYSIS_WORKER_IDLE_SECS: u64 = 60;
+
+// The analysis:* events, in the order a job sends them:
+// - analysis:queued     the note was saved and its job added to the queue
+// - analysis:started    the worker picked the job up (again, after a failure)
+// - analysis:completed  the AI's answer was applied, with the reminders it touched
+// - analysis:failed     it didn't work out; will_retry says whether the job is still queued
+// Every payload has the job_id that add_note returned
+// In TypeScript: listen<AnalysisCompleted>('analysis:completed', (event) => event.payload.created)
+
+#[derive(Clone, serde::Serialize)]
+struct AnalysisQueued {
+    job_id: i64,
+    note_id: i64,
+}
+
+#[derive(Clone, serde::Serialize)]
+struct AnalysisStarted {
+    job_id: i64,
+    note_id: i64,
+    // 1 for the first try
+    attempt: i64,
+}
+
+#[derive(Clone, serde::Serialize)]
+struct AnalysisCompleted {
+    job_id: i64,
+    note_id: i64,
+    // Ids of the reminders the analysis created and updated
+    created: Vec<i64>,
+    updated: Vec<i64>,
+    // Review mode: ids of the proposals waiting for the user
+    proposals: Vec<i64>,
+}
+
+#[derive(Clone, serde::Serialize)]
+struct AnalysisFailed<'a> {
+    job_id: i64,
+    note_id: i64,
+    // JuliError::kind(), e.g. "AiTransport" while offline or "Config" without an API key
+    kind: &'static str,
+    error: &'a JuliError,
+    // true: the job is still queued and runs again at next_attempt_at
+    will_retry: bool,
+    next_attempt_at: Option<&'a str>,
+}
+
+// Runs for as long as the app does, one job at a time
+// Started from setup() on a thread of its own, like a setInterval loop that
+// can also be woken up early (by add_note, through AnalysisWorker)
+//
+// The worker uses the database synchronously between AI calls (the Database trait),
+// which would stall the runtime the commands run on - so it gets its own small runtime
+fn start_analysis_worker(app: AppHandle) -> std::io::Result<()> {
+    let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build()?;
+    thread::Builder::new()
+        .name("analysis-worker".into())
+        .spawn(move || runtime.block_on(run_analysis_worker(app)))?;
     Ok(())
 }
 
+async fn run_analysis_worker(app: AppHandle) {
+    let db = app.state::<Db>();
+    let worker = app.state::<AnalysisWorker>();
+
+    loop {
+        let next = db.0.with_reader(|conn| AnalysisQueue::new(conn).next_due());
+        match next {
+            Ok(Some(job)) => {
+                let started = AnalysisStarted { job_id: job.id, note_id: job.note_id, attempt: job.attempts + 1 };
+                emit_event(&app, "analysis:started", started);
+
+                let result = run_analysis_job(&db.0, job, || ai_provider_from_config(&app)).await;
+                // The reminder:* events go out before analysis:completed, so the UI
+                // already shows the reminders when it reports them
+                publish_changes(&app);
+                match result {
+                    Ok(result) => {
+                        emit_analysis_result(&app, &result);
+                        // There may be more due right away
+                        continue;
+                    }
+                    Err(e) => println!("⚠️ Analysis queue failed: {}", e),
+                }
+            }
+            Ok(None) => {}
+            Err(e) => println!("⚠️ Analysis queue failed: {}", e),
+        }
+
+        // Sleep until the next retry is due, or until a note is saved
+        let due_in = db.0.with_reader(|conn| AnalysisQueue::new(conn).seconds_until_next());
+        let wait = match due_in {
+            Ok(Some(seconds)) => (seconds.max(1) as u64).min(ANALYSIS_WORKER_IDLE_SECS),
+            _ => ANALYSIS_WORKER_IDLE_SECS,
+        };
+        // timeout() returns Err when the time is up - either way, go look at the queue again
+        let _ = tokio::time::timeout(Duration::from_secs(wait), worker.0.notified()).await;
+    }
+}
+
+// Tell the frontend how a job went
+fn emit_analysis_result(app: &AppHandle, result: &AnalysisJobResult) {
+    match result {
+        AnalysisJobResult::Completed { job, outcome } => {
+            let (created, updated, proposals) = match outcome {
+                AnalysisOutcome::Skipped => (Vec::new(), Vec::new(), Vec::new()),
+                AnalysisOutcome::Applied { created, updated } => (created.clone(), updated.clone(), Vec::new()),
+                AnalysisOutcome::Proposed { proposals } => (Vec::new(), Vec::new(), proposals.clone()),
+            };
+            let completed = AnalysisCompleted { job_id: job.id, note_id: job.note_id, created, updated, proposals };
+            emit_event(app, "analysis:completed", completed);
+        }
+        AnalysisJobResult::Postponed { job, error, next_attempt_at } => {
+            println!("📥 Analysis of note {} stays queued: {}", job.note_id, error);
+            let failed = AnalysisFailed {
+                job_id: job.id,
+                note_id: job.note_id,
+                kind: error.kind(),
+                error,
+                will_retry: true,
+                next_attempt_at: next_attempt_at.as_deref(),
+            };
+            emit_event(app, "analysis:failed", failed);
+        }
+        AnalysisJobResult::Failed { job, error } => {
+            let failed = AnalysisFailed {
+                job_id: job.id,
+                note_id: job.note_id,
+                kind: error.kind(),
+                error,
+                will_retry: false,
+                next_attempt_at: None,
+            };
+            emit_event(app, "analysis:failed", failed);
+        }
+    }
+}
+
 // ============================================================================
 // DATE FORMATTING COMMAND
 // ============================================================================
@@ -846,36 +705,94 @@ fn delete_all_ai_logs(db: State<'_, Db>) -> Result<(), String> {
 // Think of it like exposing a function in an API endpoint
 // In TypeScript, you might use something like app.get('/api/date', ...)
 #[tauri::command]
-fn get_formatted_date() -> String {
-    // Import the Local struct from chrono to work with the system's local time
-    // In TypeScript, this is similar to: new Date()
-    // But Rust requires explicit imports for everything
-    use chrono::Local;
-
-    // Get the current date and time in the local timezone
-    // Local::now() is similar to TypeScript's: new Date()
-    // The type here is DateTime<Local>, which is more specific than JS's Date
-    let now = Local::now();
-
-    // Format the date using a format string
-    // In TypeScript you used: toLocaleDateString('en-US', options)
-    // In Rust, we use strftime-style format strings:
-    //   %A = Full weekday name (e.g., "Monday")
-    //   %B = Full month name (e.g., "December")
-    //   %d = Day of month (e.g., "14")
-    //   %Y = Full year (e.g., "2025")
-    let formatted = now.format("%A, %B %d, %Y");
-
-    // Convert the formatted date to a String
-    // .to_string() is needed because format() returns a DelayedFormat type
-    // In TypeScript, toLocaleDateString() already returns a string
-    // Rust is more explicit about type conversions
-    formatted.to_string()
-
+fn get_formatted_date(timezone: State<'_, UserTimezone>) -> String {
+    // Today's date in the user's timezone, e.g. "Friday, October 16, 2026"
+    // The formatting lives in core/notes.rs because notes are keyed by this exact string
+    //
     // Note on return values:
     // In Rust, the last expression without a semicolon is automatically returned
-    // This is equivalent to: return formatted.to_string();
+    // This is equivalent to: return note_date_key(today_in(timezone.0));
     // In TypeScript, you'd need an explicit `return` statement
+    note_date_key(today_in(timezone.0))
+}
+
+// ============================================================================
+// NOTIFICATIONS
+// ============================================================================
+
+// The scheduler looks at the reminders at least this often, and sooner when a
+// notification is due sooner (core/notifications.rs works out when)
+// It also picks up reminders added or moved in the meantime this way
+const NOTIFICATION_CHECK_SECS: u64 = 60;
+
+// Runs for as long as the app does: shows the notifications core/notifications.rs says
+// are due and records them, so they aren't shown again after a restart
+// Unlike the analysis worker it never holds the database while waiting, so it's a
+// plain task on Tauri's runtime
+async fn run_notification_scheduler(app: AppHandle) {
+    // macOS asks the user once; without permission show() fails and we log it
+    match app.notification().permission_state() {
+        Ok(PermissionState::Granted) => {}
+        _ => {
+            if let Err(e) = app.notification().request_permission() {
+                println!("⚠️ Couldn't ask for notification permission: {}", e);
+            }
+        }
+    }
+
+    // Old records of shown notifications are cleared out on startup and then once a day
+    let mut pruned_on = None;
+    loop {
+        let today = Utc::now().date_naive();
+        if pruned_on != Some(today) {
+            let db = app.state::<Db>();
+            match db.0.write(|conn| prune_delivered_notifications(conn, Utc::now())).await {
+                Ok(0) => {}
+                Ok(pruned) => println!("🧹 Forgot {} old notification(s)", pruned),
+                Err(e) => println!("⚠️ Couldn't prune delivered notifications: {}", e),
+            }
+            pruned_on = Some(today);
+        }
+
+        let wait = match deliver_due_notifications(&app).await {
+            Ok(Some(seconds)) => (seconds.max(1) as u64).min(NOTIFICATION_CHECK_SECS),
+            Ok(None) => NOTIFICATION_CHECK_SECS,
+            Err(e) => {
+                println!("⚠️ Notification scheduler failed: {}", e);
+                NOTIFICATION_CHECK_SECS
+            }
+        };
+        tokio::time::sleep(Duration::from_secs(wait)).await;
+    }
+}
+
+// Show everything that's due, then return the seconds until the next one
+async fn deliver_due_notifications(app: &AppHandle) -> Result<Option<i64>, JuliError> {
+    // config.json is read every time, so new lead times or digest_time apply without a restart
+    // A broken config.json is reported by the commands that need it; here the defaults do
+    let settings = config_file_path(app)
+        .and_then(|path| load_config(&path))
+        .map(|config| config.notifications)
+        .unwrap_or_default();
+    let timezone = app.state::<UserTimezone>().0;
+    let db = app.state::<Db>();
+
+    let now = Utc::now();
+    let due_settings = settings.clone();
+    let due = db.0.read(move |conn| Notifier::new(conn, &due_settings, timezone).due(now)).await?;
+
+    for notification in due {
+        // A notification that couldn't be shown isn't recorded, so the next check tries again
+        if let Err(e) = app.notification().builder().title(&notification.title).body(&notification.body).show() {
+            println!("⚠️ Couldn't show notification \"{}\": {}", notification.title, e);
+            continue;
+        }
+        println!("🔔 {}", notification.title);
+        let settings = settings.clone();
+        db.0.write(move |conn| Notifier::new(conn, &settings, timezone).mark_delivered(&notification)).await?;
+    }
+
+    db.0.read(move |conn| Notifier::new(conn, &settings, timezone).seconds_until_next(Utc::now())).await
 }
 
 // ============================================================================
@@ -902,38 +819,59 @@ pub fn run() {
 
             // Build the path to the SQLite database file
             // This will be in the app's data directory, which varies by OS:
-            // - macOS: ~/Library/Application Support/com.juli.app/app.sqlite
-            // - Windows: C:\Users\<user>\AppData\Roaming\com.juli.app\app.sqlite
-            // - Linux: ~/.config/com.juli.app/app.sqlite
-            let path = app
+            // - macOS: ~/Library/Application Support/com.hassanahmed.juli/app.sqlite
+            // - Windows: C:\Users\<user>\AppData\Roaming\com.hassanahmed.juli\app.sqlite
+            // - Linux: ~/.local/share/com.hassanahmed.juli/app.sqlite
+            let data_dir = app
                 .path()              // Get the path resolver
                 .app_data_dir()      // Get the app data directory path
-                .map_err(|e| e.to_string())?  // Convert error to String if it fails
-                .join("app.sqlite"); // Append "app.sqlite" to the path
-                // In TypeScript: path.join(appDataDir, 'app.sqlite')
-
-            // Create the directory if it doesn't exist
-            // path.parent() gets the directory containing the file
-            // .unwrap() panics if there's no parent (shouldn't happen here)
-            // create_dir_all creates all missing directories in the path (like mkdir -p)
-            // In TypeScript: fs.mkdirSync(path.dirname(dbPath), { recursive: true })
-            std::fs::create_dir_all(path.parent().unwrap())
-                .map_err(|e| e.to_string())?;
-
-            // Open (or create) the SQLite database file
-            // Connection::open() creates the file if it doesn't exist
-            // In TypeScript: const db = new Database(dbPath)
-            let conn = Connection::open(path)
-                .map_err(|e| e.to_string())?;
-
-            // Store the database connection globally so all commands can access it
+                .map_err(|e| e.to_string())?;  // Convert error to String if it fails
+
+            // Create the file if needed, turn on WAL and run migrations
+            // juli-cli opens the database through the same function, so both always agree on the schema
+            // If a migration fails the app refuses to start instead of running on a half-upgraded schema
+            // The pool opens its reader connections to the same file as they're needed
+            let pool = DbPool::open(&database_path(&data_dir))?;
+
+            // Only changes made from now on become events; older ones are history
+            let last_change = pool.with_writer(|conn| {
+                let feed = ChangeFeed::new(conn);
+                let last_change = feed.latest_id()?;
+                feed.prune(last_change)?;
+                Ok(last_change)
+            })?;
+            app.manage(ChangeCursor(Mutex::new(last_change)));
+
+            // Store the connection pool globally so all commands can access it
             // app.manage() makes the Db state available to all Tauri commands
-            // Db(Mutex::new(conn)) wraps the connection in our tuple struct
+            // Db(pool) wraps the pool in our tuple struct
             // In TypeScript: app.locals.db = db (Express) or providers: [DbService] (Angular)
-            app.manage(Db(Mutex::new(conn)));
-
-            // Initialize the AI lock to prevent concurrent analyses
-            app.manage(AiLock(TokioMutex::new(())));
+            app.manage(Db(pool));
+
+            // Start the background worker that analyzes saved notes
+            // Jobs left over from the last run (e.g. the app was closed while offline) are picked up right away
+            app.manage(AnalysisWorker(Notify::new()));
+            start_analysis_worker(app.handle().clone())?;
+            let poller = app.handle().clone();
+            thread::Builder::new()
+                .name("change-poller".into())
+                .spawn(move || run_change_poller(poller))?;
+
+            // A broken config.json shouldn't stop the app from saving notes;
+            // the AI commands report the config error when they run
+            let config = config_file_path(app.handle())
+                .and_then(|path| load_config(&path))
+                .unwrap_or_else(|e| {
+                    println!("⚠️ Using default revision settings and the system timezone: {}", e);
+                    AppConfig::default()
+                });
+            let timezone = config.timezone();
+            println!("🕒 Timezone: {}", timezone.name());
+            app.manage(UserTimezone(timezone));
+            app.manage(RevisionSettings(config.revisions));
+
+            // Desktop notifications for due reminders and the morning digest
+            tauri::async_runtime::spawn(run_notification_scheduler(app.handle().clone()));
 
             // Return Ok(()) to indicate setup succeeded
             Ok(())
@@ -941,6 +879,8 @@ pub fn run() {
         // Register plugins (like middleware in Express)
         // tauri-plugin-opener allows opening URLs and files
         .plugin(tauri_plugin_opener::init())
+        // tauri-plugin-notification shows the reminder notifications
+        .plugin(tauri_plugin_notification::init())
         // Register Tauri commands here so they can be called from the frontend
         // Think of this like registering routes in an Express app
         // Each command name in the brackets becomes callable via invoke('command_name')
@@ -952,14 +892,28 @@ pub fn run() {
             print_all_tables,
             get_all_notes,
             get_notes_for_date,
+            get_note_revisions,
+            diff_note_revisions,
+            restore_note_revision,
             get_api_key,
             test_claude_api,
             get_all_reminders,
             get_unresolved_reminders,
             get_resolved_reminders,
+            get_reminder_sources,
+            search,
             resolve_reminder,
             unresolve_reminder,
+            snooze_reminder,
+            set_reminder_priority,
             delete_reminder,
+            create_reminder,
+            update_reminder,
+            get_pending_proposals,
+            accept_proposal,
+            accept_proposal_with_edits,
+            reject_proposal,
+            get_pending_analyses,
             get_all_ai_logs,
             delete_ai_log,
             delete_all_ai_logs,
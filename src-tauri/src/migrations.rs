@@ -0,0 +1,762 @@
+// ============================================================================
+// SCHEMA MIGRATIONS
+// ============================================================================
+
+// Every change to the database schema lives here as a numbered migration.
+// The version the database is currently at is stored in SQLite's built-in
+// `PRAGMA user_version` header field (0 for a brand new file), so on startup
+// we only run the migrations with a higher number, in order.
+//
+// To change the schema: append a new Migration with the next version number.
+// Never edit or reorder a migration that has already shipped - users' databases
+// have already recorded that they ran it.
+
+use std::fmt;
+
+use rusqlite::{Connection, OptionalExtension, Transaction};
+
+use crate::core::parse_note_date;
+
+pub struct Migration {
+    pub version: i64,
+    pub description: &'static str,
+    apply: fn(&Transaction) -> rusqlite::Result<()>,
+}
+
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "create notes, reminders, ai_interaction_logs and last_used_note_in_ai",
+        apply: create_base_tables,
+    },
+    Migration {
+        version: 2,
+        description: "add reminders.resolved_at",
+        apply: |tx| add_column(tx, "reminders", "resolved_at", "TEXT"),
+    },
+    Migration {
+        version: 3,
+        description: "add reminders.created_at",
+        apply: |tx| add_column(tx, "reminders", "created_at", "TEXT"),
+    },
+    Migration {
+        version: 4,
+        description: "add reminders.due_date",
+        apply: |tx| add_column(tx, "reminders", "due_date", "TEXT"),
+    },
+    Migration {
+        version: 5,
+        description: "add notes.day (the note's date as YYYY-MM-DD)",
+        apply: add_note_day,
+    },
+    Migration {
+        version: 6,
+        description: "create notes_fts and reminders_fts full-text indexes",
+        apply: create_search_indexes,
+    },
+    Migration {
+        version: 7,
+        description: "create note_revisions",
+        apply: create_note_revisions,
+    },
+    Migration {
+        version: 8,
+        description: "create note_analysis_state and reminder_sources",
+        apply: create_analysis_tracking,
+    },
+    Migration {
+        version: 9,
+        description: "replace last_used_note_in_ai with the per-note analysis_cache",
+        apply: create_analysis_cache,
+    },
+    Migration {
+        version: 10,
+        description: "create reminder_proposals and proposal_sources",
+        apply: create_reminder_proposals,
+    },
+    Migration {
+        version: 11,
+        description: "create ai_log_rejections",
+        apply: create_ai_log_rejections,
+    },
+    Migration {
+        version: 12,
+        description: "create ai_request_attempts",
+        apply: create_ai_request_attempts,
+    },
+    Migration {
+        version: 13,
+        description: "create pending_analyses",
+        apply: create_pending_analyses,
+    },
+    Migration {
+        version: 14,
+        description: "create change_feed",
+        apply: create_change_feed,
+    },
+    Migration {
+        version: 15,
+        description: "add reminders.source and allow reminders without a note",
+        apply: add_reminder_source,
+    },
+    Migration {
+        version: 16,
+        description: "add recurrence to reminders and reminder_proposals",
+        apply: add_recurrence,
+    },
+    Migration {
+        version: 17,
+        description: "add reminders.due_at and due_timezone, and due times to reminder_proposals",
+        apply: add_due_times,
+    },
+    Migration {
+        version: 18,
+        description: "store timestamps as RFC 3339 in UTC",
+        apply: use_utc_timestamps,
+    },
+    Migration {
+        version: 19,
+        description: "add delivered_notifications",
+        apply: create_delivered_notifications,
+    },
+    Migration {
+        version: 20,
+        description: "add reminders.snooze_count and original_due_date",
+        apply: add_snooze,
+    },
+    Migration {
+        version: 21,
+        description: "add priority to reminders and reminder_proposals",
+        apply: add_priority,
+    },
+];
+
+// The schema version this build of the app expects
+pub fn latest_version() -> i64 {
+    MIGRATIONS.last().map(|m| m.version).unwrap_or(0)
+}
+
+// A migration failed (or the database is from a newer build of the app)
+// Carries the version that failed so the error message tells us exactly where we stopped
+#[derive(Debug)]
+pub struct MigrationError {
+    pub version: i64,
+    pub message: String,
+}
+
+impl fmt::Display for MigrationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Database migration {} failed: {}", self.version, self.message)
+    }
+}
+
+impl std::error::Error for MigrationError {}
+
+pub fn current_version(conn: &Connection) -> rusqlite::Result<i64> {
+    conn.query_row("PRAGMA user_version", [], |row| row.get(0))
+}
+
+// Bring the database up to the latest schema version
+// Returns the version the database ended up at
+pub fn run(conn: &mut Connection) -> Result<i64, MigrationError> {
+    migrate_to(conn, latest_version())
+}
+
+// Apply every pending migration up to and including `target`
+// Each migration runs in its own transaction together with the user_version bump,
+// so a failure leaves the database at the last version that fully succeeded
+pub fn migrate_to(conn: &mut Connection, target: i64) -> Result<i64, MigrationError> {
+    let starting_version = current_version(conn).map_err(|e| MigrationError {
+        version: 0,
+        message: format!("could not read user_version: {}", e),
+    })?;
+
+    if starting_version > latest_version() {
+        return Err(MigrationError {
+            version: starting_version,
+            message: format!(
+                "database is at schema version {} but this build only knows up to {}",
+                starting_version,
+                latest_version()
+            ),
+        });
+    }
+
+    let mut version = starting_version;
+    for migration in MIGRATIONS.iter().filter(|m| m.version > starting_version && m.version <= target) {
+        let failed = |e: rusqlite::Error| MigrationError {
+            version: migration.version,
+            message: format!("{} ({})", e, migration.description),
+        };
+
+        let tx = conn.transaction().map_err(failed)?;
+        (migration.apply)(&tx).map_err(failed)?;
+        // PRAGMA does not accept bound parameters, but the version is our own integer
+        tx.execute_batch(&format!("PRAGMA user_version = {}", migration.version))
+            .map_err(failed)?;
+        tx.commit().map_err(failed)?;
+
+        println!("✓ Applied migration {}: {}", migration.version, migration.description);
+        version = migration.version;
+    }
+
+    Ok(version)
+}
+
+// ============================================================================
+// MIGRATION STEPS
+// ============================================================================
+
+fn create_base_tables(tx: &Transaction) -> rusqlite::Result<()> {
+    // IF NOT EXISTS keeps this safe for databases created before migrations existed
+    // (those are still at user_version 0 but already have the tables)
+    tx.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS notes (
+          id INTEGER PRIMARY KEY AUTOINCREMENT,
+          text TEXT NOT NULL,
+          for_date TEXT NOT NULL UNIQUE
+        );
+
+        CREATE TABLE IF NOT EXISTS reminders (
+          id INTEGER PRIMARY KEY AUTOINCREMENT,
+          created_from_note_id INTEGER NOT NULL,
+          text TEXT NOT NULL,
+          resolved BOOLEAN NOT NULL DEFAULT FALSE,
+          tags TEXT
+        );
+
+        CREATE TABLE IF NOT EXISTS ai_interaction_logs (
+          id INTEGER PRIMARY KEY AUTOINCREMENT,
+          note_id INTEGER NOT NULL,
+          prompt TEXT NOT NULL,
+          response TEXT NOT NULL,
+          success BOOLEAN NOT NULL,
+          reasoning TEXT NOT NULL DEFAULT '',
+          reminders_count INTEGER NOT NULL DEFAULT 0,
+          created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+        );
+
+        CREATE TABLE IF NOT EXISTS last_used_note_in_ai (
+          id INTEGER PRIMARY KEY CHECK (id = 1),
+          note_text TEXT NOT NULL
+        );
+        "#,
+    )
+}
+
+// for_date is the display string ("Friday, October 16, 2026"), which SQLite can't compare
+// or sort, so keep a sortable copy for date range queries
+fn add_note_day(tx: &Transaction) -> rusqlite::Result<()> {
+    add_column(tx, "notes", "day", "TEXT")?;
+
+    let mut stmt = tx.prepare("SELECT id, for_date FROM notes WHERE day IS NULL")?;
+    let notes = stmt
+        .query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)))?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // Dates we can't parse stay NULL; they just never match a date filter
+    for (id, for_date) in notes {
+        if let Some(day) = parse_note_date(&for_date) {
+            tx.execute("UPDATE notes SET day = ?1 WHERE id = ?2", (day.to_string(), id))?;
+        }
+    }
+
+    tx.execute_batch("CREATE INDEX IF NOT EXISTS notes_day ON notes(day)")
+}
+
+// FTS5 "external content" tables: the index stores only the tokens and reads
+// the text itself from notes/reminders, so nothing is stored twice
+// The triggers keep each index in step with every INSERT, UPDATE and DELETE,
+// no matter whether it comes from the app, juli-cli or a manual SQL session
+fn create_search_indexes(tx: &Transaction) -> rusqlite::Result<()> {
+    tx.execute_batch(
+        r#"
+        CREATE VIRTUAL TABLE IF NOT EXISTS notes_fts USING fts5(
+          text,
+          content = 'notes',
+          content_rowid = 'id',
+          tokenize = 'porter unicode61 remove_diacritics 2'
+        );
+
+        CREATE TRIGGER IF NOT EXISTS notes_fts_insert AFTER INSERT ON notes BEGIN
+          INSERT INTO notes_fts (rowid, text) VALUES (new.id, new.text);
+        END;
+
+        CREATE TRIGGER IF NOT EXISTS notes_fts_delete AFTER DELETE ON notes BEGIN
+          INSERT INTO notes_fts (notes_fts, rowid, text) VALUES ('delete', old.id, old.text);
+        END;
+
+        CREATE TRIGGER IF NOT EXISTS notes_fts_update AFTER UPDATE OF text ON notes BEGIN
+          INSERT INTO notes_fts (notes_fts, rowid, text) VALUES ('delete', old.id, old.text);
+          INSERT INTO notes_fts (rowid, text) VALUES (new.id, new.text);
+        END;
+
+        CREATE VIRTUAL TABLE IF NOT EXISTS reminders_fts USING fts5(
+          text,
+          tags,
+          content = 'reminders',
+          content_rowid = 'id',
+          tokenize = 'porter unicode61 remove_diacritics 2'
+        );
+
+        CREATE TRIGGER IF NOT EXISTS reminders_fts_insert AFTER INSERT ON reminders BEGIN
+          INSERT INTO reminders_fts (rowid, text, tags) VALUES (new.id, new.text, new.tags);
+        END;
+
+        CREATE TRIGGER IF NOT EXISTS reminders_fts_delete AFTER DELETE ON reminders BEGIN
+          INSERT INTO reminders_fts (reminders_fts, rowid, text, tags) VALUES ('delete', old.id, old.text, old.tags);
+        END;
+
+        CREATE TRIGGER IF NOT EXISTS reminders_fts_update AFTER UPDATE OF text, tags ON reminders BEGIN
+          INSERT INTO reminders_fts (reminders_fts, rowid, text, tags) VALUES ('delete', old.id, old.text, old.tags);
+          INSERT INTO reminders_fts (rowid, text, tags) VALUES (new.id, new.text, new.tags);
+        END;
+
+        -- Index everything that was written before this migration
+        INSERT INTO notes_fts (notes_fts) VALUES ('rebuild');
+        INSERT INTO reminders_fts (reminders_fts) VALUES ('rebuild');
+        "#,
+    )
+}
+
+// Every saved version of a note (see core/revisions.rs for when a save
+// extends the latest revision instead of adding one)
+// created_at is when the revision was started, updated_at its last save
+fn create_note_revisions(tx: &Transaction) -> rusqlite::Result<()> {
+    tx.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS note_revisions (
+          id INTEGER PRIMARY KEY AUTOINCREMENT,
+          note_id INTEGER NOT NULL,
+          text TEXT NOT NULL,
+          size INTEGER NOT NULL,
+          created_at TEXT NOT NULL DEFAULT (datetime('now')),
+          updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+        );
+
+        CREATE INDEX IF NOT EXISTS note_revisions_note_id ON note_revisions(note_id, id);
+
+        -- Start every existing note's history with its current text
+        INSERT INTO note_revisions (note_id, text, size)
+        SELECT id, text, length(text) FROM notes;
+        "#,
+    )
+}
+
+// note_analysis_state: the text each note had when it was last analyzed, so the
+// next analysis only sends what changed since (see core/note_diff.rs)
+// reminder_sources: which lines of which note created or updated each reminder
+fn create_analysis_tracking(tx: &Transaction) -> rusqlite::Result<()> {
+    tx.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS note_analysis_state (
+          note_id INTEGER PRIMARY KEY,
+          analyzed_text TEXT NOT NULL,
+          analyzed_at TEXT NOT NULL DEFAULT (datetime('now'))
+        );
+
+        CREATE TABLE IF NOT EXISTS reminder_sources (
+          id INTEGER PRIMARY KEY AUTOINCREMENT,
+          reminder_id INTEGER NOT NULL,
+          note_id INTEGER NOT NULL,
+          ai_log_id INTEGER,
+          action TEXT NOT NULL,
+          start_line INTEGER NOT NULL,
+          end_line INTEGER NOT NULL,
+          excerpt TEXT NOT NULL,
+          created_at TEXT NOT NULL DEFAULT (datetime('now'))
+        );
+
+        CREATE INDEX IF NOT EXISTS reminder_sources_reminder_id ON reminder_sources(reminder_id);
+
+        CREATE TRIGGER IF NOT EXISTS reminder_sources_cleanup AFTER DELETE ON reminders BEGIN
+          DELETE FROM reminder_sources WHERE reminder_id = old.id;
+        END;
+
+        -- The note last_used_note_in_ai remembers has already been analyzed as it is
+        INSERT OR IGNORE INTO note_analysis_state (note_id, analyzed_text)
+        SELECT n.id, n.text FROM notes n JOIN last_used_note_in_ai l ON l.note_text = n.text;
+        "#,
+    )
+}
+
+// One row per note and prompt/provider/model combination (see core/analysis_cache.rs)
+// last_used_note_in_ai only ever remembered a single note, and we can't tell which
+// prompt or model produced it, so it's dropped rather than carried over
+fn create_analysis_cache(tx: &Transaction) -> rusqlite::Result<()> {
+    tx.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS analysis_cache (
+          note_id INTEGER NOT NULL,
+          prompt_version TEXT NOT NULL,
+          provider TEXT NOT NULL,
+          model TEXT NOT NULL,
+          content_hash TEXT NOT NULL,
+          analyzed_at TEXT NOT NULL DEFAULT (datetime('now')),
+          PRIMARY KEY (note_id, prompt_version, provider, model)
+        );
+
+        DROP TABLE IF EXISTS last_used_note_in_ai;
+        "#,
+    )
+}
+
+// Reminder changes the AI suggested while review mode was on (see core/proposals.rs)
+// text/tags/due_date are the proposed values, before_* what an UPDATE's target held
+// when it was proposed; reminder_id is the UPDATE's target, or the reminder an
+// accepted CREATE made
+// proposal_sources holds the note lines each proposal came from until it's accepted
+// and they become reminder_sources
+fn create_reminder_proposals(tx: &Transaction) -> rusqlite::Result<()> {
+    tx.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS reminder_proposals (
+          id INTEGER PRIMARY KEY AUTOINCREMENT,
+          note_id INTEGER NOT NULL,
+          ai_log_id INTEGER,
+          action TEXT NOT NULL,
+          reminder_id INTEGER,
+          text TEXT NOT NULL,
+          tags TEXT,
+          due_date TEXT,
+          before_text TEXT,
+          before_tags TEXT,
+          before_due_date TEXT,
+          status TEXT NOT NULL DEFAULT 'pending',
+          created_at TEXT NOT NULL DEFAULT (datetime('now')),
+          decided_at TEXT
+        );
+
+        CREATE INDEX IF NOT EXISTS reminder_proposals_status ON reminder_proposals(status, id);
+
+        CREATE TABLE IF NOT EXISTS proposal_sources (
+          id INTEGER PRIMARY KEY AUTOINCREMENT,
+          proposal_id INTEGER NOT NULL,
+          start_line INTEGER NOT NULL,
+          end_line INTEGER NOT NULL,
+          excerpt TEXT NOT NULL
+        );
+
+        CREATE INDEX IF NOT EXISTS proposal_sources_proposal_id ON proposal_sources(proposal_id);
+        "#,
+    )
+}
+
+// Reminders from an AI answer that failed validation (see core/validation.rs), kept
+// next to the log entry of that answer with the reason they were turned down
+// item is the reminder exactly as the AI sent it, as JSON
+fn create_ai_log_rejections(tx: &Transaction) -> rusqlite::Result<()> {
+    tx.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS ai_log_rejections (
+          id INTEGER PRIMARY KEY AUTOINCREMENT,
+          ai_log_id INTEGER NOT NULL,
+          item TEXT NOT NULL,
+          reason TEXT NOT NULL
+        );
+
+        CREATE INDEX IF NOT EXISTS ai_log_rejections_ai_log_id ON ai_log_rejections(ai_log_id);
+
+        CREATE TRIGGER IF NOT EXISTS ai_log_rejections_cleanup AFTER DELETE ON ai_interaction_logs BEGIN
+          DELETE FROM ai_log_rejections WHERE ai_log_id = old.id;
+        END;
+        "#,
+    )
+}
+
+// Every HTTP request behind an AI log entry, retries included (see ai/http.rs)
+// status is NULL when no response came back, error is NULL for the one that succeeded
+fn create_ai_request_attempts(tx: &Transaction) -> rusqlite::Result<()> {
+    tx.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS ai_request_attempts (
+          id INTEGER PRIMARY KEY AUTOINCREMENT,
+          ai_log_id INTEGER NOT NULL,
+          attempt INTEGER NOT NULL,
+          status INTEGER,
+          error TEXT,
+          duration_ms INTEGER NOT NULL,
+          retry_in_ms INTEGER,
+          created_at TEXT NOT NULL DEFAULT (datetime('now'))
+        );
+
+        CREATE INDEX IF NOT EXISTS ai_request_attempts_ai_log_id ON ai_request_attempts(ai_log_id);
+
+        CREATE TRIGGER IF NOT EXISTS ai_request_attempts_cleanup AFTER DELETE ON ai_interaction_logs BEGIN
+          DELETE FROM ai_request_attempts WHERE ai_log_id = old.id;
+        END;
+        "#,
+    )
+}
+
+// Notes waiting for the background worker to analyze them (see core/analysis_queue.rs)
+// One row per note: saving a note that is already waiting just makes it due again
+// saved_on is the day the note was saved (a note date key), used as "today" in the
+// prompt so a note written on a flight and analyzed the next morning still resolves
+// "tomorrow" to the right day
+fn create_pending_analyses(tx: &Transaction) -> rusqlite::Result<()> {
+    tx.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS pending_analyses (
+          id INTEGER PRIMARY KEY AUTOINCREMENT,
+          note_id INTEGER NOT NULL UNIQUE,
+          saved_on TEXT NOT NULL,
+          attempts INTEGER NOT NULL DEFAULT 0,
+          last_error TEXT,
+          last_error_kind TEXT,
+          next_attempt_at TEXT NOT NULL DEFAULT (datetime('now')),
+          created_at TEXT NOT NULL DEFAULT (datetime('now')),
+          saves INTEGER NOT NULL DEFAULT 1
+        );
+
+        CREATE INDEX IF NOT EXISTS pending_analyses_next_attempt_at ON pending_analyses(next_attempt_at);
+        "#,
+    )
+}
+
+// Every change to a reminder or note, in order, for the app to turn into events (see core/changes.rs)
+// Like the search indexes, it's filled by triggers, so changes made by juli-cli
+// or a manual SQL session show up in the app just like its own
+fn create_change_feed(tx: &Transaction) -> rusqlite::Result<()> {
+    tx.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS change_feed (
+          id INTEGER PRIMARY KEY AUTOINCREMENT,
+          event TEXT NOT NULL,
+          entity_id INTEGER NOT NULL,
+          created_at TEXT NOT NULL DEFAULT (datetime('now'))
+        );
+
+        CREATE TRIGGER IF NOT EXISTS reminders_change_insert AFTER INSERT ON reminders BEGIN
+          INSERT INTO change_feed (event, entity_id) VALUES ('reminder:created', new.id);
+        END;
+
+        CREATE TRIGGER IF NOT EXISTS reminders_change_update AFTER UPDATE ON reminders BEGIN
+          INSERT INTO change_feed (event, entity_id)
+          VALUES (CASE WHEN new.resolved AND NOT old.resolved THEN 'reminder:resolved' ELSE 'reminder:updated' END, new.id);
+        END;
+
+        CREATE TRIGGER IF NOT EXISTS reminders_change_delete AFTER DELETE ON reminders BEGIN
+          INSERT INTO change_feed (event, entity_id) VALUES ('reminder:deleted', old.id);
+        END;
+
+        CREATE TRIGGER IF NOT EXISTS notes_change_insert AFTER INSERT ON notes BEGIN
+          INSERT INTO change_feed (event, entity_id) VALUES ('note:saved', new.id);
+        END;
+
+        CREATE TRIGGER IF NOT EXISTS notes_change_update AFTER UPDATE OF text ON notes BEGIN
+          INSERT INTO change_feed (event, entity_id) VALUES ('note:saved', new.id);
+        END;
+        "#,
+    )
+}
+
+// Reminders added by hand (see ReminderStore::create_manual) don't come from a note,
+// so created_from_note_id has to allow NULL, and source says who made the reminder:
+// 'ai' for everything extracted from a note (all reminders so far), 'manual' otherwise
+// SQLite can't drop a NOT NULL constraint, so the table is rebuilt - same ids, so the
+// search index, sources and change feed still line up
+fn add_reminder_source(tx: &Transaction) -> rusqlite::Result<()> {
+    if column_exists(tx, "reminders", "source")? {
+        return Ok(());
+    }
+
+    rebuild_table(
+        tx,
+        "reminders",
+        r#"
+          id INTEGER PRIMARY KEY AUTOINCREMENT,
+          created_from_note_id INTEGER,
+          text TEXT NOT NULL,
+          resolved BOOLEAN NOT NULL DEFAULT FALSE,
+          tags TEXT,
+          resolved_at TEXT,
+          created_at TEXT,
+          due_date TEXT,
+          source TEXT NOT NULL DEFAULT 'ai'
+        "#,
+        "id, created_from_note_id, text, resolved, tags, resolved_at, created_at, due_date",
+    )
+}
+
+// Recurring reminders (see core/recurrence.rs): the RRULE, which occurrence of the
+// series this reminder is (1 for the first) and the reminder it was spawned from
+// when the previous occurrence was resolved
+fn add_recurrence(tx: &Transaction) -> rusqlite::Result<()> {
+    add_column(tx, "reminders", "recurrence", "TEXT")?;
+    add_column(tx, "reminders", "occurrence", "INTEGER NOT NULL DEFAULT 1")?;
+    add_column(tx, "reminders", "recurs_from_id", "INTEGER")?;
+    add_column(tx, "reminder_proposals", "recurrence", "TEXT")?;
+    add_column(tx, "reminder_proposals", "before_recurrence", "TEXT")
+}
+
+// A reminder can be due at a time of day (see core/time.rs): due_at is the moment as
+// RFC 3339 with the offset, due_timezone the IANA timezone it was set in
+// Proposals keep the proposed wall-clock time, turned into due_at when accepted
+fn add_due_times(tx: &Transaction) -> rusqlite::Result<()> {
+    add_column(tx, "reminders", "due_at", "TEXT")?;
+    add_column(tx, "reminders", "due_timezone", "TEXT")?;
+    add_column(tx, "reminder_proposals", "due_time", "TEXT")?;
+    add_column(tx, "reminder_proposals", "due_timezone", "TEXT")?;
+    add_column(tx, "reminder_proposals", "before_due_time", "TEXT")?;
+    add_column(tx, "reminder_proposals", "before_due_timezone", "TEXT")
+}
+
+// Every timestamp column, in every table
+const TIMESTAMP_COLUMNS: &[(&str, &[&str])] = &[
+    ("reminders", &["created_at", "resolved_at"]),
+    ("ai_interaction_logs", &["created_at"]),
+    ("note_revisions", &["created_at", "updated_at"]),
+    ("note_analysis_state", &["analyzed_at"]),
+    ("reminder_sources", &["created_at"]),
+    ("analysis_cache", &["analyzed_at"]),
+    ("reminder_proposals", &["created_at", "decided_at"]),
+    ("ai_request_attempts", &["created_at"]),
+    ("pending_analyses", &["next_attempt_at", "created_at"]),
+    ("change_feed", &["created_at"]),
+];
+
+// SQLite's datetime('now') and CURRENT_TIMESTAMP give "2026-10-16 19:04:05": UTC, but
+// nothing says so, and JavaScript reads it as local time. From now on every timestamp
+// is "2026-10-16T19:04:05Z" (see core/time.rs)
+// The old values are rewritten, and tables whose columns default to the old format are
+// rebuilt with the new default (a DEFAULT can't be changed with ALTER TABLE)
+fn use_utc_timestamps(tx: &Transaction) -> rusqlite::Result<()> {
+    for (table, columns) in TIMESTAMP_COLUMNS {
+        let sql: Option<String> = tx
+            .query_row("SELECT sql FROM sqlite_master WHERE type = 'table' AND name = ?1", [table], |row| row.get(0))
+            .optional()?;
+        let Some(sql) = sql else {
+            continue;
+        };
+
+        let utc_sql = sql
+            .replace("DEFAULT (datetime('now'))", "DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))")
+            .replace("DEFAULT CURRENT_TIMESTAMP", "DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))");
+        if utc_sql != sql {
+            let (Some(start), Some(end)) = (utc_sql.find('('), utc_sql.rfind(')')) else {
+                continue;
+            };
+            let mut stmt = tx.prepare("SELECT name FROM pragma_table_info(?1) ORDER BY cid")?;
+            let existing = stmt
+                .query_map([table], |row| row.get::<_, String>(0))?
+                .collect::<Result<Vec<_>, _>>()?;
+            rebuild_table(tx, table, &utc_sql[start + 1..end], &existing.join(", "))?;
+        }
+
+        for column in *columns {
+            if column_exists(tx, table, column)? {
+                tx.execute_batch(&format!(
+                    "UPDATE {table} SET {column} = COALESCE(strftime('%Y-%m-%dT%H:%M:%SZ', {column}), {column})
+                     WHERE {column} IS NOT NULL"
+                ))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+// Every notification that was shown, so a restart doesn't show it again (see core/notifications.rs)
+// key says what it was about: "reminder:42:2026-10-20T15:00:00-04:00:15" (15 minutes
+// before that due time) or "digest:2026-10-16" - a reminder that moves gets new keys
+fn create_delivered_notifications(tx: &Transaction) -> rusqlite::Result<()> {
+    tx.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS delivered_notifications (
+          id INTEGER PRIMARY KEY AUTOINCREMENT,
+          key TEXT NOT NULL UNIQUE,
+          reminder_id INTEGER,
+          title TEXT NOT NULL,
+          body TEXT NOT NULL,
+          delivered_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ', 'now'))
+        );
+
+        CREATE INDEX IF NOT EXISTS delivered_notifications_reminder_id ON delivered_notifications(reminder_id);
+
+        CREATE TRIGGER IF NOT EXISTS delivered_notifications_cleanup AFTER DELETE ON reminders BEGIN
+          DELETE FROM delivered_notifications WHERE reminder_id = old.id;
+        END;
+        "#,
+    )
+}
+
+// Snoozed reminders (see core/snooze.rs): how often a reminder was pushed out, and the
+// due date it had before the first time, so a recurring series keeps its own dates
+fn add_snooze(tx: &Transaction) -> rusqlite::Result<()> {
+    add_column(tx, "reminders", "snooze_count", "INTEGER NOT NULL DEFAULT 0")?;
+    add_column(tx, "reminders", "original_due_date", "TEXT")
+}
+
+// Reminder priorities: "low", "normal", "high" or "urgent", inferred by the AI or set by
+// the user - priority_set_by_user stops the AI from changing the user's choice
+fn add_priority(tx: &Transaction) -> rusqlite::Result<()> {
+    add_column(tx, "reminders", "priority", "TEXT NOT NULL DEFAULT 'normal'")?;
+    add_column(tx, "reminders", "priority_set_by_user", "INTEGER NOT NULL DEFAULT 0")?;
+    add_column(tx, "reminder_proposals", "priority", "TEXT")?;
+    add_column(tx, "reminder_proposals", "before_priority", "TEXT")
+}
+
+// Replace a table with a new definition, keeping its rows, indexes and triggers
+// (SQLite's recommended way to make changes ALTER TABLE can't:
+// https://www.sqlite.org/lang_altertable.html#otheralter)
+// `definition` is what goes between the parentheses of CREATE TABLE
+// `columns` are copied over by name; every other column of the new table gets its default
+fn rebuild_table(tx: &Transaction, table: &str, definition: &str, columns: &str) -> rusqlite::Result<()> {
+    // Dropping the table drops its indexes and triggers too, so save them to create again afterwards
+    // (indexes SQLite made itself for UNIQUE and PRIMARY KEY have no sql and come back with the table)
+    let mut stmt = tx.prepare(
+        "SELECT sql FROM sqlite_master WHERE type IN ('index', 'trigger') AND tbl_name = ?1 AND sql IS NOT NULL",
+    )?;
+    let schema = stmt
+        .query_map([table], |row| row.get::<_, String>(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // The AUTOINCREMENT counter, so ids of deleted rows aren't handed out again
+    let sequence: Option<i64> = tx
+        .query_row("SELECT seq FROM sqlite_sequence WHERE name = ?1", [table], |row| row.get(0))
+        .optional()?;
+
+    // Triggers on other tables may refer to this one (reminder_sources_cleanup on reminders deletes
+    // from reminder_sources, the reminders_change_* triggers write to change_feed).
+    // A modern RENAME checks every trigger in the schema and fails on those while the table
+    // is gone, so the rename is done the legacy way, which leaves other triggers alone
+    let rebuilt = format!("{}_rebuilt", table);
+    tx.execute_batch(&format!(
+        "CREATE TABLE {rebuilt} ({definition});
+         INSERT INTO {rebuilt} ({columns}) SELECT {columns} FROM {table};
+         DROP TABLE {table};
+         PRAGMA legacy_alter_table = ON;
+         ALTER TABLE {rebuilt} RENAME TO {table};
+         PRAGMA legacy_alter_table = OFF;",
+    ))?;
+
+    if let Some(sequence) = sequence {
+        tx.execute("DELETE FROM sqlite_sequence WHERE name = ?1", [table])?;
+        tx.execute("INSERT INTO sqlite_sequence (name, seq) VALUES (?1, ?2)", (table, sequence))?;
+    }
+    for sql in schema {
+        tx.execute_batch(&sql)?;
+    }
+    Ok(())
+}
+
+// ALTER TABLE ... ADD COLUMN, skipped when the column is already there
+// Older builds added these columns with unversioned ALTER TABLE calls, so a
+// database at user_version 0 may already have some of them
+fn add_column(tx: &Transaction, table: &str, column: &str, definition: &str) -> rusqlite::Result<()> {
+    if column_exists(tx, table, column)? {
+        return Ok(());
+    }
+
+    tx.execute_batch(&format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition))
+}
+
+fn column_exists(conn: &Connection, table: &str, column: &str) -> rusqlite::Result<bool> {
+    let count: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM pragma_table_info(?1) WHERE name = ?2",
+        (table, column),
+        |row| row.get(0),
+    )?;
+    Ok(count > 0)
+}
@@ -0,0 +1,167 @@
+// Timeouts, retries and backoff for provider requests, against a local stub server
+
+use std::sync::Mutex;
+use std::time::Duration;
+
+use juli_lib::ai::{AiProvider, AnthropicProvider, AttemptLog, CompletionOptions, HttpClient, RetryPolicy};
+use juli_lib::core::{AiLogStore, Analyzer, NoteStore};
+use juli_lib::error::JuliError;
+use juli_lib::migrations;
+use rusqlite::Connection;
+use serde_json::json;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+// Same rules as the default policy, but fast enough for tests
+fn fast_policy() -> RetryPolicy {
+    RetryPolicy {
+        max_attempts: 3,
+        connect_timeout_ms: 500,
+        request_timeout_ms: 300,
+        initial_backoff_ms: 10,
+        max_backoff_ms: 1_000,
+    }
+}
+
+fn provider(server: &MockServer) -> AnthropicProvider {
+    AnthropicProvider::new("test-key".to_string(), None, Some(server.uri())).with_http(HttpClient::new(fast_policy()))
+}
+
+fn answer(text: &str) -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_json(json!({"content": [{"type": "text", "text": text}]}))
+}
+
+async fn mount(server: &MockServer, response: ResponseTemplate, times: u64) {
+    Mock::given(method("POST"))
+        .and(path("/v1/messages"))
+        .respond_with(response)
+        .up_to_n_times(times)
+        .mount(server)
+        .await;
+}
+
+// Send one prompt and return what happened, plus every attempt it took
+async fn complete(server: &MockServer) -> (Result<String, JuliError>, Vec<juli_lib::ai::RequestAttempt>) {
+    let attempts = AttemptLog::default();
+    let options = CompletionOptions { attempts: attempts.clone(), ..Default::default() };
+    let result = provider(server).complete("hello", &options).await.map(|c| c.text);
+    (result, attempts.attempts())
+}
+
+#[tokio::test]
+async fn overloaded_responses_are_retried() {
+    let server = MockServer::start().await;
+    mount(&server, ResponseTemplate::new(529), 1).await;
+    mount(&server, answer("hi"), 1).await;
+
+    let (result, attempts) = complete(&server).await;
+
+    assert_eq!(result.unwrap(), "hi");
+    assert_eq!(attempts.len(), 2);
+    assert_eq!(attempts[0].status, Some(529));
+    assert_eq!(attempts[0].retry_in_ms, Some(10));
+    assert_eq!(attempts[1].status, Some(200));
+    assert_eq!(attempts[1].error, None);
+}
+
+#[tokio::test]
+async fn client_errors_are_not_retried() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(401).set_body_string("invalid x-api-key"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let (result, attempts) = complete(&server).await;
+
+    let error = result.unwrap_err();
+    assert!(matches!(error, JuliError::AiTransport { .. }));
+    assert_eq!(error.details(), Some("invalid x-api-key"));
+    assert_eq!(attempts.len(), 1);
+    assert_eq!(attempts[0].retry_in_ms, None);
+}
+
+#[tokio::test]
+async fn backoff_doubles_until_the_attempts_run_out() {
+    let server = MockServer::start().await;
+    mount(&server, ResponseTemplate::new(503), 10).await;
+
+    let (result, attempts) = complete(&server).await;
+
+    assert_eq!(result.unwrap_err().details(), Some("gave up after 3 attempts"));
+    let waits: Vec<Option<u64>> = attempts.iter().map(|a| a.retry_in_ms).collect();
+    assert_eq!(waits, vec![Some(10), Some(20), None]);
+}
+
+#[tokio::test]
+async fn retry_after_is_honored() {
+    let server = MockServer::start().await;
+    mount(&server, ResponseTemplate::new(429).insert_header("retry-after", "0.05"), 1).await;
+    mount(&server, answer("hi"), 1).await;
+
+    let (result, attempts) = complete(&server).await;
+
+    assert!(result.is_ok());
+    assert_eq!(attempts[0].retry_in_ms, Some(50));
+}
+
+#[tokio::test]
+async fn retry_after_beyond_the_longest_backoff_gives_up() {
+    let server = MockServer::start().await;
+    mount(&server, ResponseTemplate::new(429).insert_header("retry-after", "120"), 1).await;
+    mount(&server, answer("hi"), 1).await;
+
+    let (result, attempts) = complete(&server).await;
+
+    assert!(result.is_err());
+    assert_eq!(attempts.len(), 1);
+}
+
+#[tokio::test]
+async fn retry_after_too_long_for_a_duration_gives_up() {
+    for retry_after in ["inf", "1e30"] {
+        let server = MockServer::start().await;
+        mount(&server, ResponseTemplate::new(429).insert_header("retry-after", retry_after), 1).await;
+        mount(&server, answer("hi"), 1).await;
+
+        let (result, attempts) = complete(&server).await;
+
+        assert!(result.is_err(), "retry-after: {}", retry_after);
+        assert_eq!(attempts.len(), 1);
+    }
+}
+
+#[tokio::test]
+async fn timeouts_are_retried() {
+    let server = MockServer::start().await;
+    mount(&server, answer("too late").set_delay(Duration::from_secs(2)), 1).await;
+    mount(&server, answer("hi"), 1).await;
+
+    let (result, attempts) = complete(&server).await;
+
+    assert_eq!(result.unwrap(), "hi");
+    assert_eq!(attempts[0].status, None);
+    assert!(attempts[0].error.is_some());
+}
+
+#[tokio::test]
+async fn every_attempt_is_kept_with_the_ai_log() {
+    let server = MockServer::start().await;
+    mount(&server, ResponseTemplate::new(529), 1).await;
+    mount(&server, answer(r#"{"reminders": [], "reasoning": "nothing to do"}"#), 1).await;
+    let mut conn = Connection::open_in_memory().unwrap();
+    migrations::run(&mut conn).unwrap();
+    let db = Mutex::new(conn);
+    let note_id = NoteStore::new(&db.lock().unwrap()).save("Just a thought", "Friday, October 16, 2026").unwrap();
+
+    Analyzer::new(&provider(&server))
+        .analyze(&db, note_id, "Just a thought", "Friday, October 16, 2026")
+        .await
+        .unwrap();
+
+    let logs = AiLogStore::new(&db.lock().unwrap()).all().unwrap();
+    assert!(logs[0].success);
+    let statuses: Vec<Option<u16>> = logs[0].attempts.iter().map(|a| a.status).collect();
+    assert_eq!(statuses, vec![Some(529), Some(200)]);
+}
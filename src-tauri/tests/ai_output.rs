@@ -0,0 +1,76 @@
+// Getting the analysis JSON out of the providers: Anthropic's forced tool call,
+// and the extractor used for everyone else
+
+use juli_lib::ai::{extract_json, AiProvider, AnthropicProvider, CompletionOptions, OutputSchema};
+use serde_json::json;
+use wiremock::matchers::{body_partial_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[test]
+fn extracts_the_object_from_fences_and_prose() {
+    assert_eq!(extract_json("{\"reminders\": []}"), "{\"reminders\": []}");
+    assert_eq!(extract_json("```json\n{\"reminders\": []}\n```"), "{\"reminders\": []}");
+    assert_eq!(
+        extract_json("Here are the reminders:\n{\"reminders\": [{\"text\": \"a\"}]}\nLet me know!"),
+        "{\"reminders\": [{\"text\": \"a\"}]}"
+    );
+}
+
+#[test]
+fn backticks_and_braces_inside_strings_are_kept() {
+    let answer = r#"```json
+{"reminders": [], "reasoning": "The note only has a code sample: ```let x = {}``` and \"quotes }\""}
+```"#;
+
+    let json = extract_json(answer);
+
+    assert!(json.starts_with("{\"reminders\""));
+    assert!(json.ends_with("\\\"quotes }\\\"\"}"));
+    let value: serde_json::Value = serde_json::from_str(json).unwrap();
+    assert_eq!(value["reasoning"], "The note only has a code sample: ```let x = {}``` and \"quotes }\"");
+}
+
+#[test]
+fn braces_in_prose_before_the_json_are_skipped() {
+    let answer = "I replaced {name} with John.\n{\"reminders\": [], \"reasoning\": \"none\"}";
+
+    assert_eq!(extract_json(answer), "{\"reminders\": [], \"reasoning\": \"none\"}");
+}
+
+#[test]
+fn text_without_json_is_returned_trimmed() {
+    assert_eq!(extract_json("  Sorry, I can't help with that.\n"), "Sorry, I can't help with that.");
+}
+
+#[tokio::test]
+async fn anthropic_answers_through_the_schema_tool() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/messages"))
+        .and(body_partial_json(json!({"tool_choice": {"type": "tool", "name": "save_reminders"}})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "content": [
+                {"type": "text", "text": "Saving the reminders now."},
+                {"type": "tool_use", "id": "toolu_1", "name": "save_reminders",
+                 "input": {"reminders": [], "reasoning": "Nothing to do"}}
+            ],
+            "stop_reason": "tool_use"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    let provider = AnthropicProvider::new("test-key".to_string(), None, Some(server.uri()));
+    let options = CompletionOptions {
+        schema: Some(OutputSchema {
+            name: "save_reminders",
+            description: "Save the reminders",
+            schema: json!({"type": "object"}),
+        }),
+        ..Default::default()
+    };
+
+    let completion = provider.complete("Note to analyze", &options).await.unwrap();
+
+    let answer: serde_json::Value = serde_json::from_str(&completion.text).unwrap();
+    assert_eq!(answer, json!({"reminders": [], "reasoning": "Nothing to do"}));
+}
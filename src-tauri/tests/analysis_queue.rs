@@ -0,0 +1,178 @@
+// The offline queue: notes are saved without the AI, and analyzed once the provider answers
+
+use std::sync::Mutex;
+
+use juli_lib::ai::{AiProvider, MockProvider};
+use juli_lib::core::{
+    run_analysis_job, run_next_analysis, AiLogStore, AnalysisJobResult, AnalysisOutcome, AnalysisQueue, AppConfig, NoteStore, ReminderStore,
+};
+use juli_lib::error::JuliError;
+use juli_lib::migrations;
+use rusqlite::Connection;
+
+const TODAY: &str = "Friday, October 16, 2026";
+
+const CREATE_CALL_JOHN: &str = include_str!("fixtures/create_call_john.json");
+
+fn test_db() -> Mutex<Connection> {
+    let mut conn = Connection::open_in_memory().unwrap();
+    migrations::run(&mut conn).unwrap();
+    Mutex::new(conn)
+}
+
+// Same steps as the add_note command: save the note and queue it
+fn add_note(db: &Mutex<Connection>, text: &str) -> i64 {
+    let conn = db.lock().unwrap();
+    let note_id = NoteStore::new(&conn).save(text, TODAY).unwrap();
+    AnalysisQueue::new(&conn).enqueue(note_id, TODAY).unwrap();
+    note_id
+}
+
+// No scripted responses: every call fails like a network error
+fn offline() -> Result<(Box<dyn AiProvider>, AppConfig), JuliError> {
+    Ok((Box::new(MockProvider::new()), AppConfig::default()))
+}
+
+fn online() -> Result<(Box<dyn AiProvider>, AppConfig), JuliError> {
+    Ok((Box::new(MockProvider::new().respond_otherwise(CREATE_CALL_JOHN)), AppConfig::default()))
+}
+
+// Pretend the retry is due now instead of in 30 seconds
+fn make_due(db: &Mutex<Connection>) {
+    db.lock().unwrap().execute("UPDATE pending_analyses SET next_attempt_at = datetime('now')", []).unwrap();
+}
+
+#[tokio::test]
+async fn offline_analyses_stay_queued_and_run_once_online() {
+    let db = test_db();
+    let note_id = add_note(&db, "Call John tomorrow");
+
+    let result = run_next_analysis(&db, offline).await.unwrap().unwrap();
+    let AnalysisJobResult::Postponed { error, next_attempt_at, .. } = result else {
+        panic!("expected the job to be postponed, got {:?}", result);
+    };
+    assert!(matches!(error, JuliError::AiTransport { .. }));
+    assert!(next_attempt_at.is_some());
+
+    // Not due yet, so nothing runs
+    assert!(run_next_analysis(&db, online).await.unwrap().is_none());
+    {
+        let conn = db.lock().unwrap();
+        let pending = AnalysisQueue::new(&conn).all().unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].note_id, note_id);
+        assert_eq!(pending[0].attempts, 1);
+        assert_eq!(pending[0].last_error_kind.as_deref(), Some("AiTransport"));
+        assert!(AnalysisQueue::new(&conn).seconds_until_next().unwrap().unwrap() > 0);
+    }
+
+    make_due(&db);
+    let result = run_next_analysis(&db, online).await.unwrap().unwrap();
+    let AnalysisJobResult::Completed { outcome: AnalysisOutcome::Applied { created, .. }, .. } = result else {
+        panic!("expected the job to complete, got {:?}", result);
+    };
+    assert_eq!(created.len(), 1);
+
+    let conn = db.lock().unwrap();
+    assert_eq!(ReminderStore::new(&conn).all().unwrap().len(), 1);
+    assert!(AnalysisQueue::new(&conn).all().unwrap().is_empty());
+    assert_eq!(AnalysisQueue::new(&conn).seconds_until_next().unwrap(), None);
+}
+
+#[tokio::test]
+async fn saving_again_keeps_one_job_per_note() {
+    let db = test_db();
+    add_note(&db, "Call John tomorrow");
+    run_next_analysis(&db, offline).await.unwrap();
+
+    // The new save makes the waiting job due again, with a clean slate
+    add_note(&db, "Call John tomorrow\nBuy milk");
+
+    let conn = db.lock().unwrap();
+    let pending = AnalysisQueue::new(&conn).all().unwrap();
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].attempts, 0);
+    assert_eq!(AnalysisQueue::new(&conn).seconds_until_next().unwrap(), Some(0));
+}
+
+#[tokio::test]
+async fn a_save_during_the_analysis_keeps_the_job_queued() {
+    let db = test_db();
+    let note_id = add_note(&db, "Call John tomorrow");
+    let job = AnalysisQueue::new(&db.lock().unwrap()).next_due().unwrap().unwrap();
+
+    // The user saves again while the AI is still busy with the first version
+    let job_id = {
+        let conn = db.lock().unwrap();
+        NoteStore::new(&conn).save("Call John tomorrow\nBuy milk", TODAY).unwrap();
+        AnalysisQueue::new(&conn).enqueue(note_id, TODAY).unwrap()
+    };
+    assert_eq!(job_id, job.id);
+
+    let result = run_analysis_job(&db, job, online).await.unwrap();
+    assert!(matches!(result, AnalysisJobResult::Completed { .. }));
+
+    // Still queued, so the newer text gets analyzed too
+    let conn = db.lock().unwrap();
+    assert_eq!(AnalysisQueue::new(&conn).next_due().unwrap().unwrap().id, job_id);
+}
+
+#[tokio::test]
+async fn one_success_makes_every_waiting_job_due() {
+    let db = test_db();
+    let first = {
+        let conn = db.lock().unwrap();
+        let note_id = NoteStore::new(&conn).save("Call John tomorrow", "Thursday, October 15, 2026").unwrap();
+        AnalysisQueue::new(&conn).enqueue(note_id, "Thursday, October 15, 2026").unwrap();
+        note_id
+    };
+    run_next_analysis(&db, offline).await.unwrap();
+
+    add_note(&db, "Call John tomorrow");
+    run_next_analysis(&db, online).await.unwrap();
+
+    // The first note's retry was 30 seconds away; now it runs straight away
+    let result = run_next_analysis(&db, online).await.unwrap().unwrap();
+    let AnalysisJobResult::Completed { job, .. } = result else {
+        panic!("expected the job to complete, got {:?}", result);
+    };
+    assert_eq!(job.note_id, first);
+    // The day it was saved, not the day it finally ran
+    assert_eq!(job.saved_on, "Thursday, October 15, 2026");
+}
+
+#[tokio::test]
+async fn config_errors_are_logged_once_and_retried() {
+    let db = test_db();
+    add_note(&db, "Call John tomorrow");
+    let no_api_key = || Err(JuliError::config("No API key"));
+
+    let result = run_next_analysis(&db, no_api_key).await.unwrap().unwrap();
+    assert!(matches!(result, AnalysisJobResult::Postponed { .. }));
+    make_due(&db);
+    run_next_analysis(&db, no_api_key).await.unwrap();
+
+    let conn = db.lock().unwrap();
+    assert_eq!(AiLogStore::new(&conn).all().unwrap().len(), 1);
+    assert_eq!(AnalysisQueue::new(&conn).all().unwrap()[0].attempts, 2);
+}
+
+#[tokio::test]
+async fn unusable_answers_are_not_retried() {
+    let db = test_db();
+    add_note(&db, "Call John tomorrow");
+    let rambling = || Ok((Box::new(MockProvider::new().respond_otherwise("I'd rather not")) as Box<dyn AiProvider>, AppConfig::default()));
+
+    // Spawned, which also checks the worker's future can move between threads
+    let result = tokio::spawn(async move {
+        let result = run_next_analysis(&db, rambling).await.unwrap().unwrap();
+        (result, db)
+    });
+    let (result, db) = result.await.unwrap();
+
+    let AnalysisJobResult::Failed { error, .. } = result else {
+        panic!("expected the job to fail, got {:?}", result);
+    };
+    assert!(matches!(error, JuliError::AiParse { .. }));
+    assert!(AnalysisQueue::new(&db.lock().unwrap()).all().unwrap().is_empty());
+}
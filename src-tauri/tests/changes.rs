@@ -0,0 +1,121 @@
+// Every write to reminders and notes shows up in the change feed, whoever made it
+
+use juli_lib::core::{open_database, ChangeFeed, ChangePayload, NoteStore, ReminderDraft, ReminderStore};
+use juli_lib::migrations;
+use rusqlite::Connection;
+use serde_json::json;
+
+const TODAY: &str = "Friday, October 16, 2026";
+
+fn test_db() -> Connection {
+    let mut conn = Connection::open_in_memory().unwrap();
+    migrations::run(&mut conn).unwrap();
+    conn
+}
+
+fn draft(text: &str, tags: Option<&str>) -> ReminderDraft {
+    ReminderDraft { text: text.to_string(), tags: tags.map(str::to_string), ..Default::default() }
+}
+
+fn events(conn: &Connection, after_id: i64) -> Vec<(String, i64)> {
+    let (changes, _) = ChangeFeed::new(conn).since(after_id).unwrap();
+    changes
+        .into_iter()
+        .map(|change| {
+            let id = match change.payload {
+                ChangePayload::Reminder(reminder) => reminder.id,
+                ChangePayload::Note(note) => note.id,
+                ChangePayload::Deleted { id } => id,
+            };
+            (change.event, id)
+        })
+        .collect()
+}
+
+#[test]
+fn reminder_and_note_writes_become_events() {
+    let conn = test_db();
+    let note_id = NoteStore::new(&conn).save("Call John", TODAY).unwrap();
+    let reminders = ReminderStore::new(&conn);
+    let reminder_id = reminders.create(note_id, &draft("Call John", None)).unwrap();
+    reminders.update(reminder_id, &draft("Call John about the project", Some("work"))).unwrap();
+    reminders.resolve(reminder_id).unwrap();
+    reminders.unresolve(reminder_id).unwrap();
+    NoteStore::new(&conn).save("Call John\nBuy milk", TODAY).unwrap();
+
+    assert_eq!(
+        events(&conn, 0),
+        vec![
+            ("note:saved".to_string(), note_id),
+            ("reminder:created".to_string(), reminder_id),
+            ("reminder:updated".to_string(), reminder_id),
+            ("reminder:resolved".to_string(), reminder_id),
+            ("reminder:updated".to_string(), reminder_id),
+            ("note:saved".to_string(), note_id),
+        ]
+    );
+}
+
+#[test]
+fn payloads_carry_the_current_row() {
+    let conn = test_db();
+    let note_id = NoteStore::new(&conn).save("Call John", TODAY).unwrap();
+    let reminders = ReminderStore::new(&conn);
+    let call = ReminderDraft { due_date: Some("2026-10-17".to_string()), ..draft("Call John", Some("work")) };
+    let kept = reminders.create(note_id, &call).unwrap();
+    let gone = reminders.create(note_id, &draft("Buy milk", None)).unwrap();
+    let after = ChangeFeed::new(&conn).latest_id().unwrap();
+    reminders.resolve(kept).unwrap();
+    reminders.resolve(gone).unwrap();
+    reminders.delete(gone).unwrap();
+
+    let (changes, last_id) = ChangeFeed::new(&conn).since(after).unwrap();
+
+    // The deleted reminder's resolve is skipped; its reminder:deleted is all that's left
+    let payloads: Vec<(String, serde_json::Value)> = changes
+        .into_iter()
+        .map(|change| (change.event, serde_json::to_value(&change.payload).unwrap()))
+        .collect();
+    assert_eq!(payloads.len(), 2);
+    assert_eq!(payloads[0].0, "reminder:resolved");
+    assert_eq!(payloads[0].1["id"], json!(kept));
+    assert_eq!(payloads[0].1["resolved"], json!(true));
+    assert_eq!(payloads[0].1["due_date"], json!("2026-10-17"));
+    assert_eq!(payloads[1], ("reminder:deleted".to_string(), json!({ "id": gone })));
+    assert_eq!(last_id, ChangeFeed::new(&conn).latest_id().unwrap());
+}
+
+#[test]
+fn pruned_changes_are_not_sent_again() {
+    let conn = test_db();
+    let note_id = NoteStore::new(&conn).save("Call John", TODAY).unwrap();
+    let feed = ChangeFeed::new(&conn);
+    let (_, last_id) = feed.since(0).unwrap();
+    feed.prune(last_id).unwrap();
+
+    assert!(events(&conn, 0).is_empty());
+    // Ids keep counting up after a prune, so a saved cursor stays valid
+    NoteStore::new(&conn).save("Call John\nBuy milk", TODAY).unwrap();
+    assert_eq!(events(&conn, last_id), vec![("note:saved".to_string(), note_id)]);
+}
+
+#[test]
+fn opening_the_database_forgets_old_changes() {
+    let dir = std::env::temp_dir().join(format!("juli-changes-test-{}", std::process::id()));
+    let path = dir.join("app.sqlite");
+    let _ = std::fs::remove_dir_all(&dir);
+
+    // juli-cli writes, and no app is running to read the feed
+    let conn = open_database(&path).unwrap();
+    ReminderStore::new(&conn).create_manual(draft("Call John", None)).unwrap();
+    conn.execute("UPDATE change_feed SET created_at = '2026-10-16T08:00:00Z'", []).unwrap();
+    let recent = ReminderStore::new(&conn).create_manual(draft("Buy milk", None)).unwrap();
+    drop(conn);
+
+    let conn = open_database(&path).unwrap();
+    let left = events(&conn, 0);
+    drop(conn);
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(left, vec![("reminder:created".to_string(), recent)]);
+}
@@ -0,0 +1,116 @@
+// End-to-end tests for the juli-cli binary
+// Each test points --db at its own throwaway file and uses --no-ai, so nothing
+// touches the real app.sqlite or the network
+
+use std::{
+    path::PathBuf,
+    process::{Command, Output},
+};
+
+use juli_lib::core::{open_database, NoteStore, ReminderDraft, ReminderStore};
+
+struct TempDb(PathBuf);
+
+impl TempDb {
+    fn new(name: &str) -> TempDb {
+        let dir = std::env::temp_dir().join(format!("juli-cli-test-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        TempDb(dir.join("app.sqlite"))
+    }
+
+    fn juli(&self, args: &[&str]) -> Output {
+        Command::new(env!("CARGO_BIN_EXE_juli-cli"))
+            .arg("--db")
+            .arg(&self.0)
+            .args(args)
+            .output()
+            .unwrap()
+    }
+}
+
+impl Drop for TempDb {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(self.0.parent().unwrap());
+    }
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+fn draft(text: &str, tags: Option<&str>) -> ReminderDraft {
+    ReminderDraft { text: text.to_string(), tags: tags.map(str::to_string), ..Default::default() }
+}
+
+#[test]
+fn note_add_appends_to_the_days_note() {
+    let db = TempDb::new("note-add");
+
+    assert!(db.juli(&["note", "add", "--no-ai", "--date", "2026-10-16", "Call John"]).status.success());
+    assert!(db.juli(&["note", "add", "--no-ai", "--date", "2026-10-16", "Buy milk"]).status.success());
+
+    // Same key the desktop app uses, so the app shows these lines for that day
+    let conn = open_database(&db.0).unwrap();
+    let note = NoteStore::new(&conn).for_date("Friday, October 16, 2026").unwrap();
+    assert_eq!(note.text, "Call John\nBuy milk");
+
+    let shown = db.juli(&["note", "show", "--date", "2026-10-16"]);
+    assert!(stdout(&shown).contains("Call John\nBuy milk"));
+}
+
+#[test]
+fn reminders_list_filters_by_state_and_tag() {
+    let db = TempDb::new("reminders-list");
+    {
+        let conn = open_database(&db.0).unwrap();
+        let reminders = ReminderStore::new(&conn);
+        let report = draft("Send the report", Some("work,finance"));
+        reminders.create(1, &ReminderDraft { due_date: Some("2026-10-17".to_string()), ..report }).unwrap();
+        reminders.create(1, &draft("Buy milk", Some("groceries"))).unwrap();
+        let done = reminders.create(1, &draft("Book the venue", Some("work"))).unwrap();
+        reminders.resolve(done).unwrap();
+    }
+
+    let output = stdout(&db.juli(&["reminders", "list", "--unresolved", "--tag", "work"]));
+
+    assert!(output.contains("Send the report (due 2026-10-17) #work #finance"));
+    assert!(!output.contains("Buy milk"));
+    assert!(!output.contains("Book the venue"));
+}
+
+#[test]
+fn reminders_resolve_marks_the_reminder_done() {
+    let db = TempDb::new("reminders-resolve");
+    let id = {
+        let conn = open_database(&db.0).unwrap();
+        ReminderStore::new(&conn).create(1, &draft("Call John", None)).unwrap()
+    };
+
+    assert!(db.juli(&["reminders", "resolve", &id.to_string()]).status.success());
+
+    let conn = open_database(&db.0).unwrap();
+    assert!(ReminderStore::new(&conn).get(id).unwrap().resolved);
+
+    // Unknown ids are reported instead of silently ignored
+    let missing = db.juli(&["reminders", "resolve", "9999"]);
+    assert!(!missing.status.success());
+    assert!(String::from_utf8_lossy(&missing.stderr).contains("NotFound"));
+}
+
+#[test]
+fn reminders_snooze_pushes_the_reminder_out() {
+    let db = TempDb::new("reminders-snooze");
+    let id = {
+        let conn = open_database(&db.0).unwrap();
+        let dated = ReminderDraft { due_date: Some("2999-01-01".to_string()), ..draft("Renew passport", None) };
+        ReminderStore::new(&conn).create(1, &dated).unwrap()
+    };
+
+    let snoozed = db.juli(&["reminders", "snooze", &id.to_string(), "2w"]);
+    assert!(snoozed.status.success());
+    assert!(stdout(&snoozed).contains("(due 2999-01-15) (snoozed 1x, was due 2999-01-01)"));
+
+    let refused = db.juli(&["reminders", "snooze", &id.to_string(), "whenever"]);
+    assert!(!refused.status.success());
+    assert!(String::from_utf8_lossy(&refused.stderr).contains("Validation"));
+}
@@ -0,0 +1,97 @@
+// The connection pool: reads don't wait for writes, and a panic doesn't take the database down
+
+use std::{panic, path::PathBuf, sync::Mutex};
+
+use juli_lib::core::{Database, DbPool, NoteStore};
+use juli_lib::error::JuliError;
+use juli_lib::migrations;
+use rusqlite::Connection;
+
+const TODAY: &str = "Friday, October 16, 2026";
+
+struct TempDb(PathBuf);
+
+impl TempDb {
+    fn new(name: &str) -> TempDb {
+        let dir = std::env::temp_dir().join(format!("juli-db-test-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        TempDb(dir.join("app.sqlite"))
+    }
+}
+
+impl Drop for TempDb {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(self.0.parent().unwrap());
+    }
+}
+
+fn note_count(conn: &Connection) -> Result<i64, JuliError> {
+    Ok(conn.query_row("SELECT COUNT(*) FROM notes", [], |row| row.get(0))?)
+}
+
+#[test]
+fn reads_see_the_last_commit_while_a_write_is_running() {
+    let file = TempDb::new("reads");
+    let pool = DbPool::open(&file.0).unwrap();
+    pool.with_writer(|conn| NoteStore::new(conn).save("Call John", TODAY)).unwrap();
+
+    // A read from inside an open write transaction would deadlock on a single connection
+    let seen_during_write = pool
+        .with_writer(|conn| {
+            let tx = conn.unchecked_transaction()?;
+            NoteStore::new(&tx).save("Buy milk", "Saturday, October 17, 2026")?;
+            let seen = pool.with_reader(note_count)?;
+            tx.commit()?;
+            Ok(seen)
+        })
+        .unwrap();
+
+    assert_eq!(seen_during_write, 1);
+    assert_eq!(pool.with_reader(note_count).unwrap(), 2);
+}
+
+#[test]
+fn readers_cannot_write() {
+    let file = TempDb::new("read-only");
+    let pool = DbPool::open(&file.0).unwrap();
+
+    let result = pool.with_reader(|conn| NoteStore::new(conn).save("Call John", TODAY));
+
+    assert!(matches!(result, Err(JuliError::Database { .. })));
+}
+
+#[test]
+fn a_panic_rolls_back_and_leaves_the_connection_usable() {
+    let mut conn = Connection::open_in_memory().unwrap();
+    migrations::run(&mut conn).unwrap();
+    let db = Mutex::new(conn);
+
+    let crashed = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+        db.with_writer(|conn| -> Result<(), JuliError> {
+            conn.execute_batch("BEGIN")?;
+            NoteStore::new(conn).save("Call John", TODAY)?;
+            panic!("bug in the middle of a write");
+        })
+    }));
+    assert!(crashed.is_err());
+    assert!(db.is_poisoned());
+
+    assert_eq!(db.with_reader(note_count).unwrap(), 0);
+    assert!(!db.is_poisoned());
+    db.with_writer(|conn| NoteStore::new(conn).save("Call John", TODAY)).unwrap();
+}
+
+#[tokio::test]
+async fn a_panicking_task_becomes_an_error() {
+    let mut conn = Connection::open_in_memory().unwrap();
+    migrations::run(&mut conn).unwrap();
+    let pool = DbPool::from_connection(conn);
+
+    let result: Result<(), JuliError> = pool.write(|_| panic!("bug in a command")).await;
+    assert!(matches!(result, Err(JuliError::Database { .. })));
+
+    // The next command runs as usual
+    pool.write(|conn| NoteStore::new(conn).save("Call John", TODAY)).await.unwrap();
+    assert_eq!(pool.read(note_count).await.unwrap(), 1);
+}
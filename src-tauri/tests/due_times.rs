@@ -0,0 +1,179 @@
+// Due times: a reminder due at a time of day is due at that moment in the timezone it was set in
+
+use std::sync::Mutex;
+
+use chrono::{NaiveDate, NaiveTime};
+use chrono_tz::Tz;
+use juli_lib::ai::MockProvider;
+use juli_lib::core::{due_at, format_due_at, load_config, Analyzer, NoteStore, ReminderDraft, ReminderPatch, ReminderStore};
+use juli_lib::error::JuliError;
+use juli_lib::migrations;
+use rusqlite::Connection;
+
+const TODAY: &str = "Friday, October 16, 2026";
+
+const CREATE_DENTIST_AT_3PM: &str = include_str!("fixtures/create_dentist_at_3pm.json");
+
+const NEW_YORK: Tz = chrono_tz::America::New_York;
+
+fn test_db() -> Connection {
+    let mut conn = Connection::open_in_memory().unwrap();
+    migrations::run(&mut conn).unwrap();
+    conn
+}
+
+fn draft(text: &str, due_date: Option<&str>, due_time: Option<&str>) -> ReminderDraft {
+    ReminderDraft {
+        text: text.to_string(),
+        due_date: due_date.map(str::to_string),
+        due_time: due_time.map(str::to_string),
+        ..Default::default()
+    }
+}
+
+fn due(date: &str, time: &str, tz: Tz) -> String {
+    let date = NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap();
+    let time = NaiveTime::parse_from_str(time, "%H:%M").unwrap();
+    format_due_at(due_at(date, time, tz))
+}
+
+#[test]
+fn due_times_carry_the_offset_of_their_timezone() {
+    assert_eq!(due("2026-10-16", "15:00", NEW_YORK), "2026-10-16T15:00:00-04:00");
+    assert_eq!(due("2026-12-16", "15:00", NEW_YORK), "2026-12-16T15:00:00-05:00");
+    assert_eq!(due("2026-10-16", "15:00", chrono_tz::Asia::Kolkata), "2026-10-16T15:00:00+05:30");
+}
+
+#[test]
+fn daylight_saving_changes_have_a_sensible_answer() {
+    // 2:30 doesn't exist on March 8, 2026 in New York: the clocks jump from 2:00 to 3:00
+    assert_eq!(due("2026-03-08", "02:30", NEW_YORK), "2026-03-08T03:00:00-04:00");
+    // 1:30 happens twice on November 1: the first one counts
+    assert_eq!(due("2026-11-01", "01:30", NEW_YORK), "2026-11-01T01:30:00-04:00");
+}
+
+#[test]
+fn reminders_with_a_time_are_due_at_that_moment() {
+    let conn = test_db();
+    let reminders = ReminderStore::new(&conn).with_timezone(NEW_YORK);
+
+    let id = reminders.create_manual(draft("Dentist", Some("2026-10-20"), Some("9:30"))).unwrap();
+
+    let reminder = reminders.get(id).unwrap();
+    assert_eq!(reminder.due_date.as_deref(), Some("2026-10-20"));
+    assert_eq!(reminder.due_time.as_deref(), Some("09:30"));
+    assert_eq!(reminder.due_at.as_deref(), Some("2026-10-20T09:30:00-04:00"));
+    assert_eq!(reminder.timezone.as_deref(), Some("America/New_York"));
+    assert!(reminder.created_at.unwrap().ends_with('Z'));
+
+    // Without a time there's no moment, only a day
+    let cleared = reminders.edit(id, ReminderPatch { due_time: Some(None), ..Default::default() }).unwrap();
+    assert_eq!(cleared.due_time, None);
+    assert_eq!(cleared.due_at, None);
+    assert_eq!(cleared.timezone, None);
+}
+
+#[test]
+fn bad_due_times_are_refused() {
+    let conn = test_db();
+    let reminders = ReminderStore::new(&conn).with_timezone(NEW_YORK);
+
+    let pm = reminders.create_manual(draft("Dentist", Some("2026-10-20"), Some("3pm")));
+    let no_date = reminders.create_manual(draft("Dentist", None, Some("15:00")));
+    let no_zone = reminders.create_manual(ReminderDraft {
+        timezone: Some("Mars/Olympus_Mons".to_string()),
+        ..draft("Dentist", Some("2026-10-20"), Some("15:00"))
+    });
+
+    for result in [pm, no_date, no_zone] {
+        assert!(matches!(result, Err(JuliError::Validation { .. })));
+    }
+    assert!(reminders.all().unwrap().is_empty());
+}
+
+#[test]
+fn a_reminder_keeps_its_timezone_until_it_is_moved() {
+    let conn = test_db();
+    let paris = ReminderStore::new(&conn).with_timezone(chrono_tz::Europe::Paris);
+    let id = paris.create_manual(draft("Call the Paris office", Some("2026-10-20"), Some("10:00"))).unwrap();
+
+    // Editing from New York leaves the time where it was...
+    let reminders = ReminderStore::new(&conn).with_timezone(NEW_YORK);
+    let renamed = ReminderPatch { text: Some("Call the Paris team".to_string()), ..Default::default() };
+    let reminder = reminders.edit(id, renamed).unwrap();
+    assert_eq!(reminder.due_at.as_deref(), Some("2026-10-20T10:00:00+02:00"));
+    assert_eq!(reminder.timezone.as_deref(), Some("Europe/Paris"));
+
+    // ...until the time itself changes, which is then New York time
+    let moved = ReminderPatch { due_time: Some(Some("08:00".to_string())), ..Default::default() };
+    let reminder = reminders.edit(id, moved).unwrap();
+    assert_eq!(reminder.due_at.as_deref(), Some("2026-10-20T08:00:00-04:00"));
+    assert_eq!(reminder.timezone.as_deref(), Some("America/New_York"));
+}
+
+#[test]
+fn the_next_occurrence_keeps_the_time_of_day() {
+    let conn = test_db();
+    let reminders = ReminderStore::new(&conn).with_timezone(NEW_YORK);
+    let standup = ReminderDraft {
+        recurrence: Some("FREQ=DAILY".to_string()),
+        ..draft("Standup", Some("2026-10-31"), Some("09:00"))
+    };
+    let id = reminders.create_manual(standup).unwrap();
+
+    let today = NaiveDate::from_ymd_opt(2026, 10, 31).unwrap();
+    let next_id = reminders.resolve_on(id, today).unwrap().unwrap();
+
+    // Still 9:00 in New York, after the clocks went back
+    let next = reminders.get(next_id).unwrap();
+    assert_eq!(next.due_at.as_deref(), Some("2026-11-01T09:00:00-05:00"));
+    assert_eq!(next.timezone.as_deref(), Some("America/New_York"));
+}
+
+#[test]
+fn reminders_on_the_same_day_are_listed_by_time() {
+    let conn = test_db();
+    let reminders = ReminderStore::new(&conn).with_timezone(NEW_YORK);
+    reminders.create_manual(draft("Sometime that day", Some("2026-10-20"), None)).unwrap();
+    reminders.create_manual(draft("Afternoon", Some("2026-10-20"), Some("15:00"))).unwrap();
+    // 9:00 in Paris is 3:00 in New York
+    let paris = ReminderDraft { timezone: Some("Europe/Paris".to_string()), ..draft("Paris", Some("2026-10-20"), Some("09:00")) };
+    reminders.create_manual(paris).unwrap();
+    reminders.create_manual(draft("Morning", Some("2026-10-20"), Some("08:00"))).unwrap();
+
+    let texts: Vec<String> = reminders.unresolved().unwrap().into_iter().map(|r| r.text).collect();
+
+    assert_eq!(texts, ["Paris", "Morning", "Afternoon", "Sometime that day"]);
+}
+
+#[tokio::test]
+async fn the_ai_gets_the_users_timezone_and_its_times_are_in_it() {
+    let conn = test_db();
+    let note_id = NoteStore::new(&conn).save("Dentist on Tuesday at 3pm", TODAY).unwrap();
+    let db = Mutex::new(conn);
+    let provider = MockProvider::new().respond_otherwise(CREATE_DENTIST_AT_3PM);
+
+    Analyzer::new(&provider)
+        .with_timezone(chrono_tz::Asia::Tokyo)
+        .analyze(&db, note_id, "Dentist on Tuesday at 3pm", TODAY)
+        .await
+        .unwrap();
+
+    let prompt = &provider.prompts()[0];
+    assert!(prompt.contains("The user's timezone is Asia/Tokyo and their local time is 20"));
+    assert!(prompt.contains("(UTC+09:00)"));
+    let conn = db.lock().unwrap();
+    let reminder = ReminderStore::new(&conn).unresolved().unwrap().remove(0);
+    assert_eq!(reminder.due_at.as_deref(), Some("2026-10-20T15:00:00+09:00"));
+}
+
+#[test]
+fn an_unknown_timezone_in_the_config_is_a_config_error() {
+    let path = std::env::temp_dir().join(format!("juli-due-times-config-{}.json", std::process::id()));
+    std::fs::write(&path, r#"{ "timezone": "Europe/Atlantis" }"#).unwrap();
+
+    let result = load_config(&path);
+
+    std::fs::remove_file(&path).ok();
+    assert!(matches!(result, Err(JuliError::Config { .. })));
+}
@@ -0,0 +1,123 @@
+// Reminders the user adds and edits by hand, without a note or the AI
+
+use std::sync::Mutex;
+
+use juli_lib::ai::MockProvider;
+use juli_lib::core::{Analyzer, NoteStore, ReminderDraft, ReminderPatch, ReminderStore};
+use juli_lib::error::JuliError;
+use juli_lib::migrations;
+use rusqlite::Connection;
+
+const TODAY: &str = "Friday, October 16, 2026";
+
+const NO_REMINDERS: &str = include_str!("fixtures/no_reminders.json");
+
+fn test_db() -> Connection {
+    let mut conn = Connection::open_in_memory().unwrap();
+    migrations::run(&mut conn).unwrap();
+    conn
+}
+
+fn draft(text: &str, tags: Option<&str>, due_date: Option<&str>) -> ReminderDraft {
+    ReminderDraft {
+        text: text.to_string(),
+        tags: tags.map(str::to_string),
+        due_date: due_date.map(str::to_string),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn manual_reminders_are_cleaned_up_and_marked() {
+    let conn = test_db();
+    let reminders = ReminderStore::new(&conn);
+
+    let id = reminders.create_manual(draft("  Renew passport ", Some("Admin, #travel"), Some("2026-11-01"))).unwrap();
+
+    let reminder = reminders.get(id).unwrap();
+    assert_eq!(reminder.text, "Renew passport");
+    assert_eq!(reminder.tags.as_deref(), Some("admin,travel"));
+    assert_eq!(reminder.due_date.as_deref(), Some("2026-11-01"));
+    assert_eq!(reminder.source, "manual");
+    assert_eq!(reminder.created_from_note_id, None);
+    assert!(reminder.created_at.is_some());
+}
+
+#[test]
+fn invalid_manual_reminders_are_refused() {
+    let conn = test_db();
+    let reminders = ReminderStore::new(&conn);
+
+    let empty = reminders.create_manual(draft("   ", None, None));
+    let bad_date = reminders.create_manual(draft("Renew passport", None, Some("next friday")));
+
+    assert!(matches!(empty, Err(JuliError::Validation { .. })));
+    assert!(matches!(bad_date, Err(JuliError::Validation { .. })));
+    assert!(reminders.all().unwrap().is_empty());
+}
+
+#[test]
+fn edits_only_change_the_given_fields() {
+    let conn = test_db();
+    let reminders = ReminderStore::new(&conn);
+    let id = reminders.create_manual(draft("Call John", Some("work"), Some("2026-10-20"))).unwrap();
+
+    let moved = ReminderPatch { due_date: Some(Some("2026-10-23".to_string())), ..Default::default() };
+    let reminder = reminders.edit(id, moved).unwrap();
+    assert_eq!(reminder.text, "Call John");
+    assert_eq!(reminder.tags.as_deref(), Some("work"));
+    assert_eq!(reminder.due_date.as_deref(), Some("2026-10-23"));
+
+    let cleared = ReminderPatch { tags: Some(None), due_date: Some(None), ..Default::default() };
+    let reminder = reminders.edit(id, cleared).unwrap();
+    assert_eq!(reminder.tags, None);
+    assert_eq!(reminder.due_date, None);
+}
+
+#[test]
+fn a_bad_edit_changes_nothing() {
+    let conn = test_db();
+    let reminders = ReminderStore::new(&conn);
+    let id = reminders.create_manual(draft("Call John", None, Some("2026-10-20"))).unwrap();
+
+    let patch = ReminderPatch {
+        text: Some("Call John about the budget".to_string()),
+        due_date: Some(Some("2026-02-30".to_string())),
+        ..Default::default()
+    };
+
+    assert!(matches!(reminders.edit(id, patch), Err(JuliError::Validation { .. })));
+    assert!(matches!(reminders.edit(99, ReminderPatch::default()), Err(JuliError::NotFound { .. })));
+    let reminder = reminders.get(id).unwrap();
+    assert_eq!(reminder.text, "Call John");
+    assert_eq!(reminder.due_date.as_deref(), Some("2026-10-20"));
+}
+
+#[test]
+fn missing_and_null_fields_mean_different_things() {
+    let keep: ReminderPatch = serde_json::from_str(r#"{ "text": "Call John" }"#).unwrap();
+    let clear: ReminderPatch = serde_json::from_str(r#"{ "tags": null, "due_date": "2026-10-23" }"#).unwrap();
+
+    assert_eq!(keep, ReminderPatch { text: Some("Call John".to_string()), ..Default::default() });
+    assert_eq!(
+        clear,
+        ReminderPatch { tags: Some(None), due_date: Some(Some("2026-10-23".to_string())), ..Default::default() }
+    );
+}
+
+#[tokio::test]
+async fn the_ai_sees_manual_reminders() {
+    let conn = test_db();
+    ReminderStore::new(&conn).create_manual(draft("Renew passport", None, Some("2026-11-01"))).unwrap();
+    let note_id = NoteStore::new(&conn).save("Renew my passport before the trip", TODAY).unwrap();
+    let db = Mutex::new(conn);
+    let provider = MockProvider::new().respond_otherwise(NO_REMINDERS);
+
+    Analyzer::new(&provider)
+        .analyze(&db, note_id, "Renew my passport before the trip", TODAY)
+        .await
+        .unwrap();
+
+    let prompt = &provider.prompts()[0];
+    assert!(prompt.contains("1: Renew passport (tags: none, due: 2026-11-01, added by the user)"));
+}
@@ -0,0 +1,175 @@
+// Schema upgrades must give the same result no matter which version a database starts at
+
+use juli_lib::migrations;
+use rusqlite::Connection;
+
+fn columns(conn: &Connection, table: &str) -> Vec<String> {
+    let mut stmt = conn.prepare("SELECT name FROM pragma_table_info(?1) ORDER BY cid").unwrap();
+    stmt.query_map([table], |row| row.get(0))
+        .unwrap()
+        .collect::<Result<Vec<String>, _>>()
+        .unwrap()
+}
+
+fn fresh_schema() -> Vec<(String, Vec<String>)> {
+    let mut conn = Connection::open_in_memory().unwrap();
+    migrations::run(&mut conn).unwrap();
+    schema(&conn)
+}
+
+fn schema(conn: &Connection) -> Vec<(String, Vec<String>)> {
+    let mut stmt = conn
+        .prepare("SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name")
+        .unwrap();
+    let tables = stmt
+        .query_map([], |row| row.get(0))
+        .unwrap()
+        .collect::<Result<Vec<String>, _>>()
+        .unwrap();
+    tables
+        .into_iter()
+        .map(|table| {
+            let mut cols = columns(conn, &table);
+            // Legacy databases added columns in whatever order the old code ran,
+            // so compare the set of columns rather than their positions
+            cols.sort();
+            (table, cols)
+        })
+        .collect()
+}
+
+#[test]
+fn new_database_reaches_latest_version() {
+    let mut conn = Connection::open_in_memory().unwrap();
+
+    let version = migrations::run(&mut conn).unwrap();
+
+    assert_eq!(version, migrations::latest_version());
+    assert_eq!(migrations::current_version(&conn).unwrap(), migrations::latest_version());
+}
+
+#[test]
+fn running_twice_is_a_no_op() {
+    let mut conn = Connection::open_in_memory().unwrap();
+    migrations::run(&mut conn).unwrap();
+    let before = schema(&conn);
+
+    migrations::run(&mut conn).unwrap();
+
+    assert_eq!(schema(&conn), before);
+}
+
+#[test]
+fn every_intermediate_version_upgrades_to_the_same_schema() {
+    let expected = fresh_schema();
+
+    for start in 0..=migrations::latest_version() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        migrations::migrate_to(&mut conn, start).unwrap();
+        assert_eq!(migrations::current_version(&conn).unwrap(), start);
+
+        migrations::run(&mut conn).unwrap();
+
+        assert_eq!(schema(&conn), expected, "upgrading from version {}", start);
+    }
+}
+
+#[test]
+fn unversioned_legacy_database_is_adopted() {
+    // What the app created before migrations existed: user_version 0,
+    // tables present, and some of the ALTER TABLE columns already added
+    let mut conn = Connection::open_in_memory().unwrap();
+    conn.execute_batch(
+        r#"
+        CREATE TABLE notes (id INTEGER PRIMARY KEY AUTOINCREMENT, text TEXT NOT NULL, for_date TEXT NOT NULL UNIQUE);
+        CREATE TABLE reminders (id INTEGER PRIMARY KEY AUTOINCREMENT, created_from_note_id INTEGER NOT NULL, text TEXT NOT NULL, resolved BOOLEAN NOT NULL DEFAULT FALSE, tags TEXT);
+        ALTER TABLE reminders ADD COLUMN resolved_at TEXT;
+        INSERT INTO notes (text, for_date) VALUES ('old note', 'Monday, January 5, 2026');
+        INSERT INTO reminders (created_from_note_id, text) VALUES (1, 'old reminder');
+        "#,
+    )
+    .unwrap();
+
+    migrations::run(&mut conn).unwrap();
+
+    assert_eq!(schema(&conn), fresh_schema());
+    let text: String = conn.query_row("SELECT text FROM reminders WHERE id = 1", [], |row| row.get(0)).unwrap();
+    assert_eq!(text, "old reminder");
+}
+
+#[test]
+fn database_from_a_newer_build_is_rejected() {
+    let mut conn = Connection::open_in_memory().unwrap();
+    conn.execute_batch(&format!("PRAGMA user_version = {}", migrations::latest_version() + 1)).unwrap();
+
+    let err = migrations::run(&mut conn).unwrap_err();
+
+    assert_eq!(err.version, migrations::latest_version() + 1);
+}
+
+#[test]
+fn rebuilding_reminders_keeps_ids_and_triggers() {
+    let mut conn = Connection::open_in_memory().unwrap();
+    migrations::migrate_to(&mut conn, 14).unwrap();
+    conn.execute_batch(
+        r#"
+        INSERT INTO notes (text, for_date) VALUES ('Renew passport', 'Friday, October 16, 2026');
+        INSERT INTO reminders (created_from_note_id, text) VALUES (1, 'Renew passport');
+        INSERT INTO reminders (created_from_note_id, text) VALUES (1, 'Call John');
+        DELETE FROM reminders WHERE id = 2;
+        "#,
+    )
+    .unwrap();
+
+    migrations::run(&mut conn).unwrap();
+
+    let source: String = conn.query_row("SELECT source FROM reminders WHERE id = 1", [], |row| row.get(0)).unwrap();
+    assert_eq!(source, "ai");
+    // A reminder without a note gets a new id, not the deleted reminder's
+    conn.execute("INSERT INTO reminders (text, source) VALUES ('Buy milk', 'manual')", []).unwrap();
+    assert_eq!(conn.last_insert_rowid(), 3);
+    // The search index and change feed triggers came through the rebuild
+    let found: i64 = conn
+        .query_row("SELECT COUNT(*) FROM reminders_fts WHERE reminders_fts MATCH 'milk OR passport'", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(found, 2);
+    let created: i64 = conn
+        .query_row("SELECT COUNT(*) FROM change_feed WHERE event = 'reminder:created' AND entity_id = 3", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(created, 1);
+}
+
+#[test]
+fn timestamps_become_utc_rfc3339() {
+    let mut conn = Connection::open_in_memory().unwrap();
+    migrations::migrate_to(&mut conn, 17).unwrap();
+    conn.execute_batch(
+        r#"
+        INSERT INTO notes (text, for_date) VALUES ('Renew passport', 'Friday, October 16, 2026');
+        INSERT INTO reminders (created_from_note_id, text, resolved, created_at, resolved_at)
+        VALUES (1, 'Renew passport', 1, '2026-10-16 18:00:00', '2026-10-16 19:04:05');
+        INSERT INTO ai_interaction_logs (note_id, prompt, response, success) VALUES (1, '', '', 1);
+        "#,
+    )
+    .unwrap();
+    let indexes = |conn: &Connection| -> Vec<String> {
+        let mut stmt = conn.prepare("SELECT name FROM sqlite_master WHERE type = 'index' ORDER BY name").unwrap();
+        stmt.query_map([], |row| row.get(0)).unwrap().map(Result::unwrap).collect()
+    };
+    let before = indexes(&conn);
+
+    migrations::migrate_to(&mut conn, 18).unwrap();
+
+    let (created_at, resolved_at): (String, String) = conn
+        .query_row("SELECT created_at, resolved_at FROM reminders WHERE id = 1", [], |row| Ok((row.get(0)?, row.get(1)?)))
+        .unwrap();
+    assert_eq!(created_at, "2026-10-16T18:00:00Z");
+    assert_eq!(resolved_at, "2026-10-16T19:04:05Z");
+    // The row from before and a new one that gets the column's default, which is the new format too
+    conn.execute("INSERT INTO ai_interaction_logs (note_id, prompt, response, success) VALUES (1, '', '', 1)", []).unwrap();
+    let mut stmt = conn.prepare("SELECT created_at FROM ai_interaction_logs").unwrap();
+    for logged_at in stmt.query_map([], |row| row.get::<_, String>(0)).unwrap().map(Result::unwrap) {
+        assert!(logged_at.contains('T') && logged_at.ends_with('Z'), "{}", logged_at);
+    }
+    assert_eq!(indexes(&conn), before);
+}
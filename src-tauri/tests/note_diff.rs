@@ -0,0 +1,54 @@
+// Which parts of a note count as changed since it was last analyzed
+
+use juli_lib::core::{NoteChanges, NoteSection};
+
+fn section(start_line: usize, end_line: usize, text: &str, changed: bool) -> NoteSection {
+    NoteSection { start_line, end_line, text: text.to_string(), changed }
+}
+
+#[test]
+fn a_note_without_history_is_new_in_full() {
+    let changes = NoteChanges::between(None, "Call John\nBuy milk");
+
+    assert!(changes.whole_note);
+    assert_eq!(changes.sections, vec![section(1, 2, "Call John\nBuy milk", true)]);
+}
+
+#[test]
+fn changed_paragraphs_come_with_their_neighbours() {
+    let before = "one\n\ntwo\n\nthree\n\nfour";
+    let after = "one\n\ntwo\n\nthree, edited\n\nfour";
+
+    let changes = NoteChanges::between(Some(before), after);
+
+    assert!(!changes.whole_note);
+    assert_eq!(
+        changes.sections,
+        vec![
+            section(3, 3, "two", false),
+            section(5, 5, "three, edited", true),
+            section(7, 7, "four", false),
+        ]
+    );
+}
+
+#[test]
+fn a_new_line_marks_its_whole_paragraph_changed() {
+    let changes = NoteChanges::between(Some("Call John\nabout the budget"), "Call John\nabout the budget\nby Monday");
+
+    assert_eq!(changes.sections, vec![section(1, 3, "Call John\nabout the budget\nby Monday", true)]);
+}
+
+#[test]
+fn a_trailing_newline_is_not_a_change() {
+    let changes = NoteChanges::between(Some("first\n\nsecond"), "first\n\nsecond\n\nthird\n");
+
+    assert_eq!(changes.changed_sections().cloned().collect::<Vec<_>>(), vec![section(5, 5, "third", true)]);
+}
+
+#[test]
+fn deletions_and_blank_notes_leave_nothing_to_analyze() {
+    assert!(NoteChanges::between(Some("Call John\nBuy milk"), "Call John").is_empty());
+    assert!(NoteChanges::between(Some("Call John"), "Call John").is_empty());
+    assert!(NoteChanges::between(None, "  \n").is_empty());
+}
@@ -0,0 +1,225 @@
+// Desktop notifications: which ones are due when, and that each one is only shown once
+
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use juli_lib::core::{
+    load_config, prune_delivered_notifications, Notification, NotificationSettings, Notifier, ReminderDraft, ReminderPatch,
+    ReminderStore,
+};
+use juli_lib::error::JuliError;
+use juli_lib::migrations;
+use rusqlite::Connection;
+
+const NEW_YORK: Tz = chrono_tz::America::New_York;
+
+fn test_db() -> Connection {
+    let mut conn = Connection::open_in_memory().unwrap();
+    migrations::run(&mut conn).unwrap();
+    conn
+}
+
+fn settings(lead_minutes: &[i64], digest_time: Option<&str>) -> NotificationSettings {
+    NotificationSettings {
+        enabled: true,
+        lead_minutes: lead_minutes.to_vec(),
+        digest_time: digest_time.map(str::to_string),
+    }
+}
+
+// A reminder added by hand in New York
+fn add(conn: &Connection, text: &str, due_date: &str, due_time: Option<&str>) -> i64 {
+    let draft = ReminderDraft {
+        text: text.to_string(),
+        due_date: Some(due_date.to_string()),
+        due_time: due_time.map(str::to_string),
+        ..Default::default()
+    };
+    ReminderStore::new(conn).with_timezone(NEW_YORK).create_manual(draft).unwrap()
+}
+
+// "2026-10-20T14:45:00-04:00" -> the moment in UTC
+fn at(time: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(time).unwrap().with_timezone(&Utc)
+}
+
+// What's due at `now`, recorded as shown like the scheduler does
+fn deliver(conn: &Connection, settings: &NotificationSettings, now: &str) -> Vec<Notification> {
+    let notifier = Notifier::new(conn, settings, NEW_YORK);
+    let due = notifier.due(at(now)).unwrap();
+    for notification in &due {
+        notifier.mark_delivered(notification).unwrap();
+    }
+    due
+}
+
+#[test]
+fn reminders_are_announced_at_each_lead_time_once() {
+    let conn = test_db();
+    let id = add(&conn, "Dentist", "2026-10-20", Some("15:00"));
+    let settings = settings(&[15, 0], None);
+
+    assert!(deliver(&conn, &settings, "2026-10-20T14:44:00-04:00").is_empty());
+
+    let soon = deliver(&conn, &settings, "2026-10-20T14:45:00-04:00");
+    assert_eq!(soon.len(), 1);
+    assert_eq!(soon[0].reminder_id, Some(id));
+    assert_eq!(soon[0].title, "Dentist");
+    assert_eq!(soon[0].body, "Due at 15:00, in 15 minutes");
+    // Already shown
+    assert!(deliver(&conn, &settings, "2026-10-20T14:50:00-04:00").is_empty());
+
+    let now = deliver(&conn, &settings, "2026-10-20T15:00:00-04:00");
+    assert_eq!(now[0].body, "Due now (15:00)");
+    assert!(deliver(&conn, &settings, "2026-10-20T15:01:00-04:00").is_empty());
+}
+
+#[test]
+fn a_restart_shows_only_the_latest_lead_time() {
+    let conn = test_db();
+    add(&conn, "Dentist", "2026-10-20", Some("15:00"));
+    let settings = settings(&[60, 15], None);
+
+    // The app was closed for the 60 minute one and starts 5 minutes before the reminder
+    let due = deliver(&conn, &settings, "2026-10-20T14:55:00-04:00");
+
+    assert_eq!(due.len(), 1);
+    assert_eq!(due[0].body, "Due at 15:00, in 5 minutes");
+    // A new Notifier (as after another restart) knows it was shown
+    assert!(deliver(&conn, &settings, "2026-10-20T14:56:00-04:00").is_empty());
+}
+
+#[test]
+fn notifications_that_are_long_overdue_are_dropped() {
+    let conn = test_db();
+    add(&conn, "Dentist", "2026-10-20", Some("15:00"));
+
+    assert!(deliver(&conn, &settings(&[15], None), "2026-10-20T17:00:00-04:00").is_empty());
+}
+
+#[test]
+fn a_moved_reminder_is_announced_again() {
+    let conn = test_db();
+    let id = add(&conn, "Dentist", "2026-10-20", Some("15:00"));
+    let settings = settings(&[15], None);
+    assert_eq!(deliver(&conn, &settings, "2026-10-20T14:45:00-04:00").len(), 1);
+
+    let later = ReminderPatch { due_time: Some(Some("16:00".to_string())), ..Default::default() };
+    ReminderStore::new(&conn).with_timezone(NEW_YORK).edit(id, later).unwrap();
+
+    assert!(deliver(&conn, &settings, "2026-10-20T15:00:00-04:00").is_empty());
+    assert_eq!(deliver(&conn, &settings, "2026-10-20T15:45:00-04:00").len(), 1);
+}
+
+#[test]
+fn the_morning_digest_lists_what_is_due_today() {
+    let conn = test_db();
+    add(&conn, "Dentist", "2026-10-20", Some("15:00"));
+    add(&conn, "Send the report", "2026-10-20", None);
+    add(&conn, "Renew passport", "2026-10-19", None);
+    add(&conn, "Book flights", "2026-10-21", None);
+    let done = add(&conn, "Buy milk", "2026-10-20", None);
+    ReminderStore::new(&conn).resolve(done).unwrap();
+    let settings = settings(&[], Some("08:00"));
+
+    assert!(deliver(&conn, &settings, "2026-10-20T07:59:00-04:00").is_empty());
+
+    let digest = deliver(&conn, &settings, "2026-10-20T08:00:00-04:00");
+    assert_eq!(digest.len(), 1);
+    assert_eq!(digest[0].reminder_id, None);
+    assert_eq!(digest[0].title, "Today: 3 reminders");
+    assert_eq!(digest[0].body, "• Renew passport (overdue)\n• Dentist at 15:00\n• Send the report");
+
+    // Once a day
+    assert!(deliver(&conn, &settings, "2026-10-20T09:00:00-04:00").is_empty());
+}
+
+#[test]
+fn a_late_start_still_gets_the_digest_but_not_in_the_evening() {
+    let conn = test_db();
+    add(&conn, "Send the report", "2026-10-20", None);
+    let settings = settings(&[], Some("08:00"));
+
+    assert!(deliver(&conn, &settings, "2026-10-20T19:00:00-04:00").is_empty());
+    assert_eq!(deliver(&conn, &settings, "2026-10-21T10:30:00-04:00")[0].body, "• Send the report (overdue)");
+}
+
+#[test]
+fn nothing_is_shown_when_notifications_are_off() {
+    let conn = test_db();
+    add(&conn, "Dentist", "2026-10-20", Some("15:00"));
+    let off = NotificationSettings { enabled: false, ..settings(&[15], Some("08:00")) };
+
+    assert!(deliver(&conn, &off, "2026-10-20T14:45:00-04:00").is_empty());
+    assert_eq!(Notifier::new(&conn, &off, NEW_YORK).seconds_until_next(at("2026-10-20T14:00:00-04:00")).unwrap(), None);
+}
+
+#[test]
+fn the_scheduler_knows_when_to_look_again() {
+    let conn = test_db();
+    add(&conn, "Dentist", "2026-10-20", Some("15:00"));
+    let notifier_settings = settings(&[15], Some("08:00"));
+    let notifier = Notifier::new(&conn, &notifier_settings, NEW_YORK);
+
+    // 14:00: the 15 minute notice at 14:45
+    assert_eq!(notifier.seconds_until_next(at("2026-10-20T14:00:00-04:00")).unwrap(), Some(45 * 60));
+    // 16:00: tomorrow's digest
+    assert_eq!(notifier.seconds_until_next(at("2026-10-20T16:00:00-04:00")).unwrap(), Some(16 * 60 * 60));
+}
+
+#[test]
+fn lead_times_too_long_for_a_date_are_skipped() {
+    let conn = test_db();
+    add(&conn, "Dentist", "2026-10-20", Some("15:00"));
+    let settings = settings(&[i64::MAX, 15], None);
+    let notifier = Notifier::new(&conn, &settings, NEW_YORK);
+
+    assert_eq!(notifier.seconds_until_next(at("2026-10-20T14:00:00-04:00")).unwrap(), Some(45 * 60));
+    assert_eq!(notifier.due(at("2026-10-20T14:45:00-04:00")).unwrap()[0].body, "Due at 15:00, in 15 minutes");
+}
+
+#[test]
+fn old_and_resolved_notifications_are_forgotten() {
+    let conn = test_db();
+    let dentist = add(&conn, "Dentist", "2026-10-20", Some("15:00"));
+    add(&conn, "Send the report", "2026-10-20", Some("17:00"));
+    assert_eq!(deliver(&conn, &settings(&[15], Some("08:00")), "2026-10-20T08:00:00-04:00").len(), 1);
+    assert_eq!(deliver(&conn, &settings(&[15], None), "2026-10-20T14:45:00-04:00").len(), 1);
+    assert_eq!(deliver(&conn, &settings(&[15], None), "2026-10-20T16:45:00-04:00").len(), 1);
+    conn.execute("UPDATE delivered_notifications SET delivered_at = '2026-10-01T12:00:00Z' WHERE key LIKE 'digest:%'", []).unwrap();
+    ReminderStore::new(&conn).resolve(dentist).unwrap();
+
+    assert_eq!(prune_delivered_notifications(&conn, at("2026-10-20T17:00:00-04:00")).unwrap(), 2);
+
+    let left: Vec<String> = conn
+        .prepare("SELECT key FROM delivered_notifications")
+        .unwrap()
+        .query_map([], |row| row.get(0))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(left, ["reminder:2:2026-10-20T17:00:00-04:00:15"]);
+}
+
+#[test]
+fn notification_settings_are_checked() {
+    let write = |name: &str, contents: &str| {
+        let path = std::env::temp_dir().join(format!("juli-notifications-{}-{}.json", name, std::process::id()));
+        std::fs::write(&path, contents).unwrap();
+        let config = load_config(&path);
+        std::fs::remove_file(&path).ok();
+        config
+    };
+
+    let config = write("ok", r#"{ "notifications": { "lead_minutes": [60, 5], "digest_time": "7:30" } }"#).unwrap();
+    assert_eq!(config.notifications.lead_minutes, [60, 5]);
+    assert_eq!(config.notifications.digest_time.as_deref(), Some("07:30"));
+    assert!(config.notifications.enabled);
+
+    let negative = write("negative", r#"{ "notifications": { "lead_minutes": [-5] } }"#);
+    let am = write("am", r#"{ "notifications": { "digest_time": "8am" } }"#);
+    let huge = write("huge", r#"{ "notifications": { "lead_minutes": [10081] } }"#);
+    assert!(matches!(negative, Err(JuliError::Config { .. })));
+    assert!(matches!(huge, Err(JuliError::Config { .. })));
+    assert!(write("week", r#"{ "notifications": { "lead_minutes": [10080] } }"#).is_ok());
+    assert!(matches!(am, Err(JuliError::Config { .. })));
+}
@@ -0,0 +1,171 @@
+// Reminder priorities: reading them, what the AI may change, and sorting by them
+
+use std::sync::Mutex;
+
+use chrono::NaiveDate;
+use juli_lib::ai::MockProvider;
+use juli_lib::core::{parse_priority, Analyzer, NoteStore, ReminderDraft, ReminderPatch, ReminderSort, ReminderStore};
+use juli_lib::error::JuliError;
+use juli_lib::migrations;
+use rusqlite::Connection;
+
+const TODAY: &str = "Friday, October 16, 2026";
+
+const CREATE_URGENT_BANK_CALL: &str = include_str!("fixtures/create_urgent_bank_call.json");
+const UPDATE_BANK_CALL_LOW: &str = include_str!("fixtures/update_bank_call_low.json");
+const UPDATE_CALL_JOHN: &str = include_str!("fixtures/update_call_john.json");
+
+fn test_db() -> Mutex<Connection> {
+    let mut conn = Connection::open_in_memory().unwrap();
+    migrations::run(&mut conn).unwrap();
+    Mutex::new(conn)
+}
+
+async fn add_note(db: &Mutex<Connection>, provider: &MockProvider, text: &str) {
+    let note_id = {
+        let conn = db.lock().unwrap();
+        NoteStore::new(&conn).save(text, TODAY).unwrap()
+    };
+    Analyzer::new(provider).analyze(db, note_id, text, TODAY).await.unwrap();
+}
+
+fn draft(text: &str, due_date: Option<&str>, priority: Option<&str>) -> ReminderDraft {
+    ReminderDraft {
+        text: text.to_string(),
+        due_date: due_date.map(str::to_string),
+        priority: priority.map(str::to_string),
+        ..Default::default()
+    }
+}
+
+fn texts(reminders: &ReminderStore) -> Vec<String> {
+    reminders.unresolved().unwrap().into_iter().map(|r| r.text).collect()
+}
+
+#[test]
+fn priorities_are_read_in_one_form() {
+    assert_eq!(parse_priority(Some(" High ")).unwrap().as_deref(), Some("high"));
+    assert_eq!(parse_priority(Some("p1")).unwrap().as_deref(), Some("urgent"));
+    assert_eq!(parse_priority(Some("P4")).unwrap().as_deref(), Some("low"));
+    assert_eq!(parse_priority(Some("medium")).unwrap().as_deref(), Some("normal"));
+    assert_eq!(parse_priority(Some("null")).unwrap(), None);
+    assert_eq!(parse_priority(None).unwrap(), None);
+    assert!(parse_priority(Some("p5")).is_err());
+    assert!(parse_priority(Some("very")).is_err());
+}
+
+#[tokio::test]
+async fn the_ai_infers_the_priority_from_the_note() {
+    let db = test_db();
+    let provider = MockProvider::new().respond_otherwise(CREATE_URGENT_BANK_CALL);
+
+    add_note(&db, &provider, "Call the bank about the card asap!!").await;
+
+    let conn = db.lock().unwrap();
+    let reminder = ReminderStore::new(&conn).get(1).unwrap();
+    assert_eq!(reminder.priority, "urgent");
+    assert!(!reminder.priority_set_by_user);
+    assert!(provider.prompts()[0].contains("For priority:"));
+}
+
+#[tokio::test]
+async fn an_update_without_a_priority_keeps_the_reminders() {
+    let db = test_db();
+    {
+        let conn = db.lock().unwrap();
+        ReminderStore::new(&conn).create(1, &draft("Call John about the project", None, Some("high"))).unwrap();
+    }
+
+    add_note(&db, &MockProvider::new().respond_otherwise(UPDATE_CALL_JOHN), "Call John about the budget by Monday").await;
+
+    let conn = db.lock().unwrap();
+    let reminder = ReminderStore::new(&conn).get(1).unwrap();
+    assert_eq!(reminder.text, "Call John about the project budget");
+    assert_eq!(reminder.priority, "high");
+}
+
+#[tokio::test]
+async fn the_ai_leaves_a_priority_the_user_set_alone() {
+    let db = test_db();
+    add_note(&db, &MockProvider::new().respond_otherwise(CREATE_URGENT_BANK_CALL), "Call the bank about the card asap!!").await;
+    {
+        let conn = db.lock().unwrap();
+        let reminder = ReminderStore::new(&conn).set_priority(1, "high").unwrap();
+        assert!(reminder.priority_set_by_user);
+    }
+
+    let provider = MockProvider::new().respond_otherwise(UPDATE_BANK_CALL_LOW);
+    add_note(&db, &provider, "The bank can wait until Monday").await;
+
+    let conn = db.lock().unwrap();
+    let reminder = ReminderStore::new(&conn).get(1).unwrap();
+    assert_eq!(reminder.due_date.as_deref(), Some("2026-10-20"));
+    assert_eq!(reminder.priority, "high");
+    // And the AI is told so
+    assert!(provider.prompts()[0].contains("priority: high (set by the user)"));
+}
+
+#[test]
+fn setting_a_priority_by_hand() {
+    let mut conn = Connection::open_in_memory().unwrap();
+    migrations::run(&mut conn).unwrap();
+    let reminders = ReminderStore::new(&conn);
+    let id = reminders.create_manual(draft("Renew passport", None, None)).unwrap();
+    assert_eq!(reminders.get(id).unwrap().priority, "normal");
+    assert!(!reminders.get(id).unwrap().priority_set_by_user);
+
+    let patch = ReminderPatch { priority: Some("p2".to_string()), ..Default::default() };
+    let edited = reminders.edit(id, patch).unwrap();
+    assert_eq!(edited.priority, "high");
+    assert!(edited.priority_set_by_user);
+
+    let bad = ReminderPatch { text: Some("Renew the passport".to_string()), priority: Some("soonish".to_string()), ..Default::default() };
+    assert!(matches!(reminders.edit(id, bad), Err(JuliError::Validation { .. })));
+    assert_eq!(reminders.get(id).unwrap().text, "Renew passport");
+    assert!(matches!(reminders.set_priority(id, ""), Err(JuliError::Validation { .. })));
+    assert!(matches!(reminders.set_priority(99, "low"), Err(JuliError::NotFound { .. })));
+}
+
+#[test]
+fn the_next_occurrence_keeps_the_priority() {
+    let mut conn = Connection::open_in_memory().unwrap();
+    migrations::run(&mut conn).unwrap();
+    let reminders = ReminderStore::new(&conn);
+    let id = reminders
+        .create_manual(ReminderDraft { recurrence: Some("FREQ=WEEKLY".to_string()), ..draft("Pay invoices", Some("2026-10-16"), Some("urgent")) })
+        .unwrap();
+
+    let next = reminders.resolve_on(id, NaiveDate::from_ymd_opt(2026, 10, 16).unwrap()).unwrap().unwrap();
+
+    let next = reminders.get(next).unwrap();
+    assert_eq!(next.priority, "urgent");
+    assert!(next.priority_set_by_user);
+}
+
+#[test]
+fn lists_sort_by_due_date_priority_or_creation() {
+    let mut conn = Connection::open_in_memory().unwrap();
+    migrations::run(&mut conn).unwrap();
+    let reminders = ReminderStore::new(&conn);
+    for (text, due_date, priority) in [
+        ("Water the plants", Some("2026-10-20"), Some("low")),
+        ("Send the report", Some("2026-10-20"), Some("urgent")),
+        ("Book flights", None, Some("high")),
+        ("Call John", Some("2026-10-18"), None),
+    ] {
+        reminders.create_manual(draft(text, due_date, priority)).unwrap();
+    }
+
+    // The same day goes by priority
+    assert_eq!(texts(&reminders), ["Call John", "Send the report", "Water the plants", "Book flights"]);
+    assert_eq!(
+        texts(&ReminderStore::new(&conn).with_sort(ReminderSort::Priority)),
+        ["Send the report", "Book flights", "Call John", "Water the plants"]
+    );
+    assert_eq!(
+        texts(&ReminderStore::new(&conn).with_sort(ReminderSort::Created)),
+        ["Call John", "Book flights", "Send the report", "Water the plants"]
+    );
+    assert_eq!("Priority".parse::<ReminderSort>(), Ok(ReminderSort::Priority));
+    assert!("importance".parse::<ReminderSort>().is_err());
+}
@@ -0,0 +1,209 @@
+// Review mode: the AI's changes are stored as proposals and only reach the
+// reminders table once the user accepts them
+
+use std::sync::Mutex;
+
+use juli_lib::ai::MockProvider;
+use juli_lib::core::{
+    AnalysisOutcome, Analyzer, NoteStore, ProposalRow, ProposalStore, ReminderDraft, ReminderSourceStore, ReminderStore,
+};
+use juli_lib::error::JuliError;
+use juli_lib::migrations;
+use rusqlite::Connection;
+
+const TODAY: &str = "Friday, October 16, 2026";
+
+const CREATE_CALL_JOHN: &str = include_str!("fixtures/create_call_john.json");
+const UPDATE_CALL_JOHN: &str = include_str!("fixtures/update_call_john.json");
+
+fn test_db() -> Mutex<Connection> {
+    let mut conn = Connection::open_in_memory().unwrap();
+    migrations::run(&mut conn).unwrap();
+    Mutex::new(conn)
+}
+
+async fn add_note(db: &Mutex<Connection>, provider: &MockProvider, text: &str, review: bool) -> AnalysisOutcome {
+    let note_id = {
+        let conn = db.lock().unwrap();
+        NoteStore::new(&conn).save(text, TODAY).unwrap()
+    };
+    Analyzer::new(provider)
+        .with_review(review)
+        .analyze(db, note_id, text, TODAY)
+        .await
+        .unwrap()
+}
+
+fn draft(text: &str, tags: Option<&str>, due_date: Option<&str>) -> ReminderDraft {
+    ReminderDraft {
+        text: text.to_string(),
+        tags: tags.map(str::to_string),
+        due_date: due_date.map(str::to_string),
+        ..Default::default()
+    }
+}
+
+// The accepted draft with another priority, as the review form sends it
+fn with_priority(proposal: &ProposalRow, priority: &str) -> ReminderDraft {
+    ReminderDraft { priority: Some(priority.to_string()), ..proposal.proposed.clone() }
+}
+
+#[tokio::test]
+async fn review_mode_stores_proposals_instead_of_reminders() {
+    let db = test_db();
+    let provider = MockProvider::new().respond_otherwise(CREATE_CALL_JOHN);
+
+    let outcome = add_note(&db, &provider, "Call John about the project tomorrow --[work]", true).await;
+
+    assert_eq!(outcome, AnalysisOutcome::Proposed { proposals: vec![1] });
+    let conn = db.lock().unwrap();
+    assert!(ReminderStore::new(&conn).all().unwrap().is_empty());
+    let pending = ProposalStore::new(&conn).pending().unwrap();
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].action, "CREATE");
+    assert_eq!(pending[0].proposed, draft("Call John about the project", Some("work"), Some("2026-10-17")));
+    assert_eq!(pending[0].before, None);
+    assert_eq!(pending[0].sources[0].excerpt, "Call John about the project tomorrow --[work]");
+}
+
+#[tokio::test]
+async fn update_proposals_show_the_reminder_before_and_after() {
+    let db = test_db();
+    add_note(&db, &MockProvider::new().respond_otherwise(CREATE_CALL_JOHN), "Call John tomorrow --[work]", false).await;
+
+    let provider = MockProvider::new().respond_otherwise(UPDATE_CALL_JOHN);
+    add_note(&db, &provider, "Call John tomorrow --[work]\n\nIt's about the budget, by Monday", true).await;
+
+    let conn = db.lock().unwrap();
+    let proposal = &ProposalStore::new(&conn).pending().unwrap()[0];
+    assert_eq!(proposal.reminder_id, Some(1));
+    // An UPDATE without a priority keeps the reminder's
+    let normal = |draft: ReminderDraft| ReminderDraft { priority: Some("normal".to_string()), ..draft };
+    assert_eq!(proposal.before, Some(normal(draft("Call John about the project", Some("work"), Some("2026-10-17")))));
+    assert_eq!(proposal.proposed, normal(draft("Call John about the project budget", Some("work,finance"), Some("2026-10-20"))));
+    // Still the original until it's accepted
+    assert_eq!(ReminderStore::new(&conn).get(1).unwrap().text, "Call John about the project");
+}
+
+#[tokio::test]
+async fn accepting_applies_the_change_and_records_its_source() {
+    let db = test_db();
+    add_note(&db, &MockProvider::new().respond_otherwise(CREATE_CALL_JOHN), "Call John tomorrow --[work]", true).await;
+    let conn = db.lock().unwrap();
+    let proposals = ProposalStore::new(&conn);
+
+    let reminder_id = proposals.accept(1, None).unwrap();
+
+    let reminder = ReminderStore::new(&conn).get(reminder_id).unwrap();
+    assert_eq!(reminder.text, "Call John about the project");
+    assert_eq!(reminder.due_date.as_deref(), Some("2026-10-17"));
+    let sources = ReminderSourceStore::new(&conn).for_reminder(reminder_id).unwrap();
+    assert_eq!(sources[0].excerpt, "Call John tomorrow --[work]");
+    assert_eq!(sources[0].ai_log_id, Some(1));
+    assert!(proposals.pending().unwrap().is_empty());
+    assert_eq!(proposals.get(1).unwrap().reminder_id, Some(reminder_id));
+
+    // A proposal can only be decided once
+    assert!(matches!(proposals.accept(1, None), Err(JuliError::Validation { .. })));
+}
+
+#[tokio::test]
+async fn accepting_with_edits_writes_the_edited_version() {
+    let db = test_db();
+    add_note(&db, &MockProvider::new().respond_otherwise(CREATE_CALL_JOHN), "Call John tomorrow --[work]", true).await;
+    let conn = db.lock().unwrap();
+    let proposals = ProposalStore::new(&conn);
+
+    assert!(matches!(proposals.accept(1, Some(draft("  ", None, None))), Err(JuliError::Validation { .. })));
+    let reminder_id = proposals.accept(1, Some(draft("Call John and Mary", Some("work"), None))).unwrap();
+
+    let reminder = ReminderStore::new(&conn).get(reminder_id).unwrap();
+    assert_eq!(reminder.text, "Call John and Mary");
+    assert_eq!(reminder.due_date, None);
+    let accepted = proposals.get(1).unwrap();
+    assert_eq!(accepted.status, "accepted");
+    assert_eq!(accepted.proposed, draft("Call John and Mary", Some("work"), None));
+}
+
+#[tokio::test]
+async fn accepting_an_update_keeps_a_priority_set_while_it_waited() {
+    let db = test_db();
+    add_note(&db, &MockProvider::new().respond_otherwise(CREATE_CALL_JOHN), "Call John tomorrow --[work]", false).await;
+    let provider = MockProvider::new().respond_otherwise(UPDATE_CALL_JOHN);
+    add_note(&db, &provider, "Call John tomorrow --[work]\n\nIt's about the budget, by Monday", true).await;
+    let conn = db.lock().unwrap();
+    ReminderStore::new(&conn).set_priority(1, "urgent").unwrap();
+    let proposals = ProposalStore::new(&conn);
+
+    proposals.accept(1, None).unwrap();
+
+    let reminder = ReminderStore::new(&conn).get(1).unwrap();
+    assert_eq!(reminder.text, "Call John about the project budget");
+    assert_eq!(reminder.priority, "urgent");
+    assert!(reminder.priority_set_by_user);
+    assert_eq!(proposals.get(1).unwrap().proposed.priority.as_deref(), Some("urgent"));
+}
+
+#[tokio::test]
+async fn a_priority_picked_while_accepting_a_new_reminder_is_the_users() {
+    let db = test_db();
+    add_note(&db, &MockProvider::new().respond_otherwise(CREATE_CALL_JOHN), "Call John tomorrow --[work]", true).await;
+    let conn = db.lock().unwrap();
+    let proposals = ProposalStore::new(&conn);
+
+    let reminder_id = proposals.accept(1, Some(with_priority(&proposals.get(1).unwrap(), "high"))).unwrap();
+
+    let reminder = ReminderStore::new(&conn).get(reminder_id).unwrap();
+    assert_eq!(reminder.priority, "high");
+    assert!(reminder.priority_set_by_user);
+}
+
+#[tokio::test]
+async fn a_priority_picked_while_accepting_an_update_wins() {
+    let db = test_db();
+    add_note(&db, &MockProvider::new().respond_otherwise(CREATE_CALL_JOHN), "Call John tomorrow --[work]", false).await;
+    let provider = MockProvider::new().respond_otherwise(UPDATE_CALL_JOHN);
+    add_note(&db, &provider, "Call John tomorrow --[work]\n\nIt's about the budget, by Monday", true).await;
+    let conn = db.lock().unwrap();
+    let reminders = ReminderStore::new(&conn);
+    reminders.set_priority(1, "urgent").unwrap();
+    let proposals = ProposalStore::new(&conn);
+
+    proposals.accept(1, Some(with_priority(&proposals.get(1).unwrap(), "low"))).unwrap();
+
+    let reminder = reminders.get(1).unwrap();
+    assert_eq!(reminder.text, "Call John about the project budget");
+    assert_eq!(reminder.priority, "low");
+    assert!(reminder.priority_set_by_user);
+}
+
+#[tokio::test]
+async fn rejecting_leaves_reminders_alone() {
+    let db = test_db();
+    add_note(&db, &MockProvider::new().respond_otherwise(CREATE_CALL_JOHN), "Call John tomorrow --[work]", false).await;
+    let provider = MockProvider::new().respond_otherwise(UPDATE_CALL_JOHN);
+    add_note(&db, &provider, "Call John tomorrow --[work]\n\nIt's about the budget", true).await;
+    let conn = db.lock().unwrap();
+    let proposals = ProposalStore::new(&conn);
+
+    proposals.reject(1).unwrap();
+
+    assert_eq!(proposals.get(1).unwrap().status, "rejected");
+    assert!(proposals.pending().unwrap().is_empty());
+    assert_eq!(ReminderStore::new(&conn).get(1).unwrap().text, "Call John about the project");
+}
+
+#[tokio::test]
+async fn accepting_an_update_of_a_deleted_reminder_fails_and_stays_pending() {
+    let db = test_db();
+    add_note(&db, &MockProvider::new().respond_otherwise(CREATE_CALL_JOHN), "Call John tomorrow --[work]", false).await;
+    let provider = MockProvider::new().respond_otherwise(UPDATE_CALL_JOHN);
+    add_note(&db, &provider, "Call John tomorrow --[work]\n\nIt's about the budget", true).await;
+    let conn = db.lock().unwrap();
+    ReminderStore::new(&conn).delete(1).unwrap();
+    let proposals = ProposalStore::new(&conn);
+
+    assert!(matches!(proposals.accept(1, None), Err(JuliError::NotFound { .. })));
+    assert_eq!(proposals.get(1).unwrap().status, "pending");
+    assert!(ReminderSourceStore::new(&conn).for_reminder(1).unwrap().is_empty());
+}
@@ -0,0 +1,232 @@
+// Recurring reminders: reading RRULEs, working out the next date, and resolving a
+// recurring reminder into its next occurrence
+
+use chrono::NaiveDate;
+use juli_lib::core::{NoteStore, Recurrence, ReminderDraft, ReminderPatch, ReminderStore};
+use juli_lib::error::JuliError;
+use juli_lib::migrations;
+use rusqlite::Connection;
+
+const TODAY: &str = "Friday, October 16, 2026";
+
+fn test_db() -> Connection {
+    let mut conn = Connection::open_in_memory().unwrap();
+    migrations::run(&mut conn).unwrap();
+    conn
+}
+
+fn draft(text: &str, due_date: Option<&str>, recurrence: Option<&str>) -> ReminderDraft {
+    ReminderDraft {
+        text: text.to_string(),
+        due_date: due_date.map(str::to_string),
+        recurrence: recurrence.map(str::to_string),
+        ..Default::default()
+    }
+}
+
+fn date(s: &str) -> NaiveDate {
+    NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+}
+
+// The next `n` dates after `start` (occurrence 1), as if each one were resolved on its due date
+fn dates(rule: &str, start: &str, n: usize) -> Vec<String> {
+    let rule = Recurrence::parse(rule).unwrap();
+    let (mut current, mut occurrence) = (date(start), 1);
+    let mut dates = Vec::new();
+    while dates.len() < n {
+        let Some((next, next_occurrence)) = rule.next_after(current, occurrence, current) else {
+            break;
+        };
+        dates.push(next.to_string());
+        (current, occurrence) = (next, next_occurrence);
+    }
+    dates
+}
+
+#[test]
+fn rules_are_read_and_written_back_in_one_form() {
+    let rule = Recurrence::parse("rrule:freq=weekly; byday=mo,fr ;interval=2").unwrap();
+    assert_eq!(rule.to_string(), "FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,FR");
+    assert_eq!(rule.describe(), "every 2 weeks on Mon, Fri");
+
+    let rule = Recurrence::parse("FREQ=MONTHLY;BYDAY=-1FR;UNTIL=20270630T000000Z").unwrap();
+    assert_eq!(rule.to_string(), "FREQ=MONTHLY;BYDAY=-1FR;UNTIL=20270630");
+    assert_eq!(rule.describe(), "every month on the last Fri until 2027-06-30");
+
+    assert_eq!(Recurrence::parse("FREQ=WEEKLY;BYDAY=MO,TU,WE,TH,FR").unwrap().describe(), "every weekday");
+    assert_eq!(Recurrence::parse("FREQ=MONTHLY;BYMONTHDAY=1").unwrap().describe(), "every month on day 1");
+    assert_eq!(Recurrence::parse("FREQ=DAILY;COUNT=3").unwrap().describe(), "every day, 3 times");
+}
+
+#[test]
+fn rules_outside_the_supported_subset_are_refused() {
+    for rule in [
+        "",
+        "BYDAY=MO",
+        "FREQ=HOURLY",
+        "FREQ=DAILY;INTERVAL=0",
+        "FREQ=YEARLY;INTERVAL=1001",
+        "FREQ=YEARLY;INTERVAL=4000000000",
+        "FREQ=WEEKLY;BYDAY=XX",
+        "FREQ=WEEKLY;BYDAY=1MO",
+        "FREQ=WEEKLY;BYMONTHDAY=1",
+        "FREQ=MONTHLY;BYMONTHDAY=32",
+        "FREQ=YEARLY;BYDAY=MO",
+        "FREQ=DAILY;COUNT=3;UNTIL=20261231",
+        "FREQ=DAILY;FREQ=WEEKLY",
+        "FREQ=DAILY;BYHOUR=9",
+        "FREQ=DAILY;UNTIL=soon",
+        // Not ASCII: refused, not a panic
+        "FREQ=WEEKLY;BYDAY=MÖ",
+        "FREQ=MONTHLY;BYDAY=1MÉ",
+        "FREQ=WEEKLY;BYDAY=É",
+        "FREQ=WEEKLY;BYDAY=M€",
+        "FREQ=MONTHLY;BYDAY=1🙂",
+    ] {
+        assert!(Recurrence::parse(rule).is_err(), "{} should be refused", rule);
+    }
+}
+
+#[test]
+fn next_dates_follow_the_rule() {
+    // 2026-10-16 is a Friday
+    assert_eq!(dates("FREQ=DAILY;INTERVAL=3", "2026-10-16", 2), ["2026-10-19", "2026-10-22"]);
+    assert_eq!(
+        dates("FREQ=WEEKLY;BYDAY=MO,TU,WE,TH,FR", "2026-10-16", 3),
+        ["2026-10-19", "2026-10-20", "2026-10-21"]
+    );
+    assert_eq!(dates("FREQ=WEEKLY;INTERVAL=2;BYDAY=FR", "2026-10-16", 2), ["2026-10-30", "2026-11-13"]);
+    assert_eq!(dates("FREQ=MONTHLY;BYMONTHDAY=1", "2026-10-01", 3), ["2026-11-01", "2026-12-01", "2027-01-01"]);
+    assert_eq!(dates("FREQ=MONTHLY;BYMONTHDAY=-1", "2026-10-31", 2), ["2026-11-30", "2026-12-31"]);
+    assert_eq!(dates("FREQ=MONTHLY;BYDAY=1MO", "2026-10-05", 2), ["2026-11-02", "2026-12-07"]);
+    assert_eq!(dates("FREQ=YEARLY", "2026-10-16", 1), ["2027-10-16"]);
+}
+
+#[test]
+fn dates_that_dont_exist_are_skipped() {
+    assert_eq!(dates("FREQ=MONTHLY", "2026-10-31", 3), ["2026-12-31", "2027-01-31", "2027-03-31"]);
+    assert_eq!(dates("FREQ=YEARLY", "2028-02-29", 1), ["2032-02-29"]);
+}
+
+#[test]
+fn count_and_until_end_the_series() {
+    assert_eq!(dates("FREQ=DAILY;COUNT=3", "2026-10-16", 5), ["2026-10-17", "2026-10-18"]);
+    assert_eq!(dates("FREQ=WEEKLY;UNTIL=20261030", "2026-10-16", 5), ["2026-10-23", "2026-10-30"]);
+}
+
+#[test]
+fn resolving_late_skips_the_missed_occurrences() {
+    let rule = Recurrence::parse("FREQ=DAILY;COUNT=10").unwrap();
+
+    // Due on the 12th, resolved on the 16th: the next one is today's, the 5th of the series
+    assert_eq!(rule.next_after(date("2026-10-12"), 1, date("2026-10-16")), Some((date("2026-10-16"), 5)));
+}
+
+#[test]
+fn resolving_a_recurring_reminder_creates_the_next_occurrence() {
+    let conn = test_db();
+    let note_id = NoteStore::new(&conn).save("Pay rent on the 1st every month --[home]", TODAY).unwrap();
+    let reminders = ReminderStore::new(&conn);
+    let rent = draft("Pay rent", Some("2026-11-01"), Some("FREQ=MONTHLY;BYMONTHDAY=1"));
+    let id = reminders.create(note_id, &ReminderDraft { tags: Some("home".to_string()), ..rent }).unwrap();
+
+    let next_id = reminders.resolve_on(id, date("2026-10-30")).unwrap().unwrap();
+
+    let done = reminders.get(id).unwrap();
+    assert!(done.resolved);
+    let next = reminders.get(next_id).unwrap();
+    assert!(!next.resolved);
+    assert_eq!(next.text, "Pay rent");
+    assert_eq!(next.tags.as_deref(), Some("home"));
+    assert_eq!(next.due_date.as_deref(), Some("2026-12-01"));
+    assert_eq!(next.recurrence.as_deref(), Some("FREQ=MONTHLY;BYMONTHDAY=1"));
+    assert_eq!(next.repeats.as_deref(), Some("every month on day 1"));
+    assert_eq!(next.occurrence, 2);
+    assert_eq!(next.recurs_from_id, Some(id));
+    assert_eq!(next.created_from_note_id, Some(note_id));
+
+    // Resolving it again doesn't add another one
+    assert_eq!(reminders.resolve_on(id, date("2026-10-30")).unwrap(), None);
+    assert_eq!(reminders.unresolved().unwrap().len(), 1);
+}
+
+#[test]
+fn resolving_twice_changes_nothing_the_second_time() {
+    let conn = test_db();
+    let reminders = ReminderStore::new(&conn);
+    let id = reminders.create_manual(draft("Water the plants", Some("2026-10-16"), Some("FREQ=WEEKLY"))).unwrap();
+    reminders.resolve_on(id, date("2026-10-16")).unwrap().unwrap();
+    conn.execute("UPDATE reminders SET resolved_at = '2026-10-16T08:00:00Z' WHERE id = ?1", [id]).unwrap();
+    let changes = |conn: &Connection| -> i64 { conn.query_row("SELECT COUNT(*) FROM change_feed", [], |row| row.get(0)).unwrap() };
+    let before = changes(&conn);
+
+    assert_eq!(reminders.resolve_on(id, date("2026-10-17")).unwrap(), None);
+
+    let resolved_at: String = conn.query_row("SELECT resolved_at FROM reminders WHERE id = ?1", [id], |row| row.get(0)).unwrap();
+    assert_eq!(resolved_at, "2026-10-16T08:00:00Z");
+    assert_eq!(changes(&conn), before);
+    assert_eq!(reminders.all().unwrap().len(), 2);
+}
+
+#[test]
+fn unresolving_takes_the_next_occurrence_back() {
+    let conn = test_db();
+    let reminders = ReminderStore::new(&conn);
+    let id = reminders
+        .create_manual(draft("Standup notes", Some("2026-10-16"), Some("freq=weekly;byday=mo,tu,we,th,fr")))
+        .unwrap();
+    assert_eq!(reminders.get(id).unwrap().recurrence.as_deref(), Some("FREQ=WEEKLY;BYDAY=MO,TU,WE,TH,FR"));
+
+    let next_id = reminders.resolve_on(id, date("2026-10-16")).unwrap().unwrap();
+    assert_eq!(reminders.get(next_id).unwrap().due_date.as_deref(), Some("2026-10-19"));
+    assert_eq!(reminders.get(next_id).unwrap().source, "manual");
+
+    reminders.unresolve(id).unwrap();
+    assert!(matches!(reminders.get(next_id), Err(JuliError::NotFound { .. })));
+    assert_eq!(reminders.unresolved().unwrap().len(), 1);
+}
+
+#[test]
+fn reminders_that_dont_repeat_just_resolve() {
+    let conn = test_db();
+    let reminders = ReminderStore::new(&conn);
+    let once = reminders.create(1, &draft("Call John", Some("2026-10-16"), None)).unwrap();
+    let last = reminders.create(1, &draft("Take the pills", Some("2026-10-16"), Some("FREQ=DAILY;UNTIL=20261016"))).unwrap();
+
+    assert_eq!(reminders.resolve(once).unwrap(), None);
+    assert_eq!(reminders.resolve_on(last, date("2026-10-16")).unwrap(), None);
+    assert!(reminders.unresolved().unwrap().is_empty());
+}
+
+#[test]
+fn extreme_intervals_have_no_next_occurrence() {
+    // Refused by parse, but a Recurrence can be built by hand
+    for frequency in ["DAILY", "WEEKLY", "MONTHLY", "YEARLY"] {
+        let rule = Recurrence { interval: u32::MAX, ..Recurrence::parse(&format!("FREQ={}", frequency)).unwrap() };
+        assert_eq!(rule.next_after(date("2026-10-16"), 1, date("2026-10-16")), None, "{}", frequency);
+    }
+    let rule = Recurrence::parse("FREQ=YEARLY;INTERVAL=1000").unwrap();
+    assert_eq!(rule.next_after(date("2026-10-16"), 1, date("2026-10-16")), Some((date("3026-10-16"), 2)));
+
+    // A rule saved before INTERVAL had a limit just resolves
+    let conn = test_db();
+    let reminders = ReminderStore::new(&conn);
+    let id = reminders.create(1, &draft("Renew the lease", Some("2026-10-16"), Some("FREQ=YEARLY"))).unwrap();
+    conn.execute("UPDATE reminders SET recurrence = 'FREQ=YEARLY;INTERVAL=4000000000' WHERE id = ?1", [id]).unwrap();
+
+    assert_eq!(reminders.resolve_on(id, date("2026-10-16")).unwrap(), None);
+    assert!(reminders.get(id).unwrap().resolved);
+    assert!(reminders.unresolved().unwrap().is_empty());
+}
+
+#[test]
+fn bad_rules_are_a_validation_error() {
+    let conn = test_db();
+    let reminders = ReminderStore::new(&conn);
+    let id = reminders.create(1, &draft("Call John", None, None)).unwrap();
+
+    let patch = ReminderPatch { recurrence: Some(Some("every tuesday".to_string())), ..Default::default() };
+
+    assert!(matches!(reminders.edit(id, patch), Err(JuliError::Validation { .. })));
+    assert_eq!(reminders.get(id).unwrap().recurrence, None);
+}
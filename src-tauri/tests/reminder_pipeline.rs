@@ -0,0 +1,426 @@
+// Integration tests for the note -> AI -> reminders pipeline
+// Everything runs against an in-memory SQLite database and a scripted MockProvider,
+// so no API key or network access is needed
+
+use std::sync::Mutex;
+
+use juli_lib::ai::MockProvider;
+use juli_lib::core::{AiLogStore, AnalysisOutcome, Analyzer, NoteStore, ReminderSourceStore, ReminderStore};
+use juli_lib::error::JuliError;
+use juli_lib::migrations;
+use rusqlite::Connection;
+
+const TODAY: &str = "Thursday, October 16, 2026";
+
+const CREATE_CALL_JOHN: &str = include_str!("fixtures/create_call_john.json");
+const UPDATE_CALL_JOHN: &str = include_str!("fixtures/update_call_john.json");
+const UPDATE_UNKNOWN_ID: &str = include_str!("fixtures/update_unknown_id.json");
+const NO_REMINDERS: &str = include_str!("fixtures/no_reminders.json");
+const CREATE_TWO_REMINDERS: &str = include_str!("fixtures/create_two_reminders.json");
+const MIXED_VALIDITY: &str = include_str!("fixtures/mixed_validity.json");
+const NOT_JSON: &str = include_str!("fixtures/not_json.txt");
+
+#[derive(Debug, PartialEq)]
+struct Reminder {
+    id: i64,
+    text: String,
+    tags: Option<String>,
+    due_date: Option<String>,
+}
+
+#[derive(Debug)]
+struct Log {
+    response: String,
+    success: bool,
+    reminders_count: i64,
+}
+
+fn test_db() -> Mutex<Connection> {
+    let mut conn = Connection::open_in_memory().unwrap();
+    migrations::run(&mut conn).unwrap();
+    Mutex::new(conn)
+}
+
+// Same steps as the add_note command: save the note, then analyze it
+async fn add_note(db: &Mutex<Connection>, provider: &MockProvider, text: &str) -> Result<i64, JuliError> {
+    let note_id = {
+        let conn = db.lock().unwrap();
+        NoteStore::new(&conn).save(text, TODAY)?
+    };
+    Analyzer::new(provider).analyze(db, note_id, text, TODAY).await?;
+    Ok(note_id)
+}
+
+fn reminders(db: &Mutex<Connection>) -> Vec<Reminder> {
+    let conn = db.lock().unwrap();
+    let mut reminders: Vec<Reminder> = ReminderStore::new(&conn)
+        .all()
+        .unwrap()
+        .into_iter()
+        .map(|r| Reminder {
+            id: r.id,
+            text: r.text,
+            tags: r.tags,
+            due_date: r.due_date,
+        })
+        .collect();
+    reminders.sort_by_key(|r| r.id);
+    reminders
+}
+
+fn logs(db: &Mutex<Connection>) -> Vec<Log> {
+    let conn = db.lock().unwrap();
+    let mut stmt = conn
+        .prepare("SELECT response, success, reminders_count FROM ai_interaction_logs ORDER BY id")
+        .unwrap();
+    stmt.query_map([], |row| {
+        Ok(Log {
+            response: row.get(0)?,
+            success: row.get(1)?,
+            reminders_count: row.get(2)?,
+        })
+    })
+    .unwrap()
+    .collect::<Result<Vec<_>, _>>()
+    .unwrap()
+}
+
+#[tokio::test]
+async fn create_action_inserts_reminder_and_logs_success() {
+    let db = test_db();
+    let provider = MockProvider::new().respond_when("Call John", CREATE_CALL_JOHN);
+
+    let note_id = add_note(&db, &provider, "Call John about the project tomorrow --[work]").await.unwrap();
+
+    assert_eq!(
+        reminders(&db),
+        vec![Reminder {
+            id: 1,
+            text: "Call John about the project".to_string(),
+            tags: Some("work".to_string()),
+            due_date: Some("2026-10-17".to_string()),
+        }]
+    );
+    let conn = db.lock().unwrap();
+    assert_eq!(ReminderStore::new(&conn).get(1).unwrap().created_from_note_id, Some(note_id));
+    drop(conn);
+
+    let logs = logs(&db);
+    assert_eq!(logs.len(), 1);
+    assert!(logs[0].success);
+    assert_eq!(logs[0].reminders_count, 1);
+}
+
+#[tokio::test]
+async fn update_action_rewrites_existing_reminder() {
+    let db = test_db();
+    let first = MockProvider::new().respond_otherwise(CREATE_CALL_JOHN);
+    add_note(&db, &first, "Call John about the project tomorrow --[work]").await.unwrap();
+
+    // The second response is wrapped in ```json fences, which must be tolerated
+    let second = MockProvider::new().respond_otherwise(UPDATE_CALL_JOHN);
+    add_note(&db, &second, "Call John about the project tomorrow --[work]\nActually it's about the budget, by Monday")
+        .await
+        .unwrap();
+
+    // The existing reminder is offered to the model so it can pick UPDATE
+    assert!(second.prompts()[0].contains("1: Call John about the project (tags: work, due: 2026-10-17)"));
+    assert_eq!(
+        reminders(&db),
+        vec![Reminder {
+            id: 1,
+            text: "Call John about the project budget".to_string(),
+            tags: Some("work,finance".to_string()),
+            due_date: Some("2026-10-20".to_string()),
+        }]
+    );
+}
+
+#[tokio::test]
+async fn unchanged_note_is_not_analyzed_twice() {
+    let db = test_db();
+    let provider = MockProvider::new()
+        .respond_when("Call John", CREATE_CALL_JOHN)
+        .respond_otherwise(NO_REMINDERS);
+
+    add_note(&db, &provider, "Call John about the project tomorrow").await.unwrap();
+    add_note(&db, &provider, "Call John about the project tomorrow").await.unwrap();
+
+    // The analysis cache matched, so the second save never reached the provider
+    assert_eq!(provider.call_count(), 1);
+    assert_eq!(reminders(&db).len(), 1);
+    assert_eq!(logs(&db).len(), 1);
+
+    // Any edit makes the note eligible again
+    add_note(&db, &provider, "Buy milk").await.unwrap();
+    assert_eq!(provider.call_count(), 2);
+}
+
+#[tokio::test]
+async fn unparseable_response_is_logged_as_failure() {
+    let db = test_db();
+    let provider = MockProvider::new().respond_otherwise(NOT_JSON);
+
+    let err = add_note(&db, &provider, "Call John tomorrow").await.unwrap_err();
+
+    assert_eq!(err.kind(), "AiParse");
+    assert!(reminders(&db).is_empty());
+
+    let logs = logs(&db);
+    assert_eq!(logs.len(), 1);
+    assert!(!logs[0].success);
+    assert!(logs[0].response.starts_with("Failed to parse AI response as JSON"));
+
+    // The failed note isn't cached, so the next save tries again
+    let _ = add_note(&db, &provider, "Call John tomorrow").await;
+    assert_eq!(provider.call_count(), 2);
+}
+
+#[tokio::test]
+async fn provider_failure_is_logged_and_returned() {
+    let db = test_db();
+    // No rules and no fallback: every call fails like a network error
+    let provider = MockProvider::new();
+
+    let err = add_note(&db, &provider, "Call John tomorrow").await.unwrap_err();
+
+    assert_eq!(err.kind(), "AiTransport");
+    let logs = logs(&db);
+    assert_eq!(logs.len(), 1);
+    assert!(!logs[0].success);
+    assert!(logs[0].response.starts_with("AI API call failed"));
+}
+
+#[tokio::test]
+async fn update_with_unknown_id_leaves_reminders_untouched() {
+    let db = test_db();
+    let first = MockProvider::new().respond_otherwise(CREATE_CALL_JOHN);
+    add_note(&db, &first, "Call John about the project tomorrow --[work]").await.unwrap();
+    let before = reminders(&db);
+
+    let second = MockProvider::new().respond_otherwise(UPDATE_UNKNOWN_ID);
+    add_note(&db, &second, "Water the plants").await.unwrap();
+
+    assert_eq!(reminders(&db), before);
+    let conn = db.lock().unwrap();
+    let log = &AiLogStore::new(&conn).all().unwrap()[0];
+    assert_eq!(log.reminders_count, 0);
+    assert_eq!(log.rejections[0].reason, "UPDATE of reminder 9999, which doesn't exist");
+}
+
+#[tokio::test]
+async fn invalid_reminders_are_rejected_with_a_reason() {
+    let db = test_db();
+    let provider = MockProvider::new().respond_otherwise(MIXED_VALIDITY);
+
+    add_note(&db, &provider, "Water the plants on Sunday --[home, garden]").await.unwrap();
+
+    // Only the valid one is written, trimmed and with its tags cleaned up
+    assert_eq!(
+        reminders(&db),
+        vec![Reminder {
+            id: 1,
+            text: "Water the plants".to_string(),
+            tags: Some("home,garden".to_string()),
+            due_date: Some("2026-10-18".to_string()),
+        }]
+    );
+
+    let conn = db.lock().unwrap();
+    let log = &AiLogStore::new(&conn).all().unwrap()[0];
+    assert!(log.success);
+    assert_eq!(log.reminders_count, 1);
+    let reasons: Vec<&str> = log.rejections.iter().map(|r| r.reason.as_str()).collect();
+    assert_eq!(
+        reasons,
+        vec![
+            "due_date \"2026-02-30\" is not a YYYY-MM-DD date",
+            "empty reminder text",
+            "UPDATE without an update_id",
+            "unknown action \"DELETE\" (expected CREATE or UPDATE)",
+        ]
+    );
+    assert!(log.rejections[0].item.contains("Book the dentist"));
+}
+
+#[tokio::test]
+async fn saving_again_for_the_same_date_keeps_one_note() {
+    let db = test_db();
+    let provider = MockProvider::new().respond_otherwise(NO_REMINDERS);
+
+    let first_id = add_note(&db, &provider, "first draft").await.unwrap();
+    let second_id = add_note(&db, &provider, "second draft").await.unwrap();
+
+    assert_eq!(first_id, second_id);
+    let conn = db.lock().unwrap();
+    assert_eq!(NoteStore::new(&conn).for_date(TODAY).unwrap().text, "second draft");
+    assert_eq!(NoteStore::new(&conn).all().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn analyzer_reports_what_it_did() {
+    let db = test_db();
+    let provider = MockProvider::new().respond_otherwise(CREATE_CALL_JOHN);
+    let analyzer = Analyzer::new(&provider);
+    let note_id = {
+        let conn = db.lock().unwrap();
+        NoteStore::new(&conn).save("Call John tomorrow", TODAY).unwrap()
+    };
+
+    let first = analyzer.analyze(&db, note_id, "Call John tomorrow", TODAY).await.unwrap();
+    let second = analyzer.analyze(&db, note_id, "Call John tomorrow", TODAY).await.unwrap();
+
+    assert_eq!(first, AnalysisOutcome::Applied { created: vec![1], updated: vec![] });
+    assert_eq!(second, AnalysisOutcome::Skipped);
+}
+
+#[tokio::test]
+async fn edits_only_send_the_changed_paragraphs() {
+    let db = test_db();
+    let provider = MockProvider::new().respond_otherwise(NO_REMINDERS);
+    add_note(&db, &provider, "Call John tomorrow\n\nBuy milk\n\nWater the plants").await.unwrap();
+
+    add_note(&db, &provider, "Call John tomorrow\n\nBuy milk\n\nWater the plants\n\nBook the dentist").await.unwrap();
+
+    let prompt = &provider.prompts()[1];
+    assert!(prompt.contains("[CONTEXT, lines 5-5]\nWater the plants"));
+    assert!(prompt.contains("[CHANGED 1, lines 7-7]\nBook the dentist"));
+    // Paragraphs far from the edit aren't sent again
+    assert!(!prompt.contains("Call John tomorrow"));
+}
+
+#[tokio::test]
+async fn deleting_lines_does_not_reach_the_provider() {
+    let db = test_db();
+    let provider = MockProvider::new().respond_otherwise(NO_REMINDERS);
+    add_note(&db, &provider, "Call John tomorrow\nBuy milk").await.unwrap();
+
+    add_note(&db, &provider, "Call John tomorrow").await.unwrap();
+
+    assert_eq!(provider.call_count(), 1);
+}
+
+#[tokio::test]
+async fn reminders_record_the_lines_they_came_from() {
+    let db = test_db();
+    let first = MockProvider::new().respond_otherwise(NO_REMINDERS);
+    add_note(&db, &first, "Groceries for the weekend").await.unwrap();
+
+    let second = MockProvider::new().respond_otherwise(CREATE_CALL_JOHN);
+    add_note(&db, &second, "Groceries for the weekend\n\nCall John about the project tomorrow --[work]")
+        .await
+        .unwrap();
+
+    let conn = db.lock().unwrap();
+    let sources = ReminderSourceStore::new(&conn).for_reminder(1).unwrap();
+    assert_eq!(sources.len(), 1);
+    assert_eq!(sources[0].action, "CREATE");
+    assert_eq!((sources[0].start_line, sources[0].end_line), (3, 3));
+    assert_eq!(sources[0].excerpt, "Call John about the project tomorrow --[work]");
+    assert_eq!(sources[0].ai_log_id, Some(2));
+}
+
+#[tokio::test]
+async fn each_note_keeps_its_own_cache_entry() {
+    let db = test_db();
+    let provider = MockProvider::new().respond_otherwise(NO_REMINDERS);
+    let analyzer = Analyzer::new(&provider);
+    let save = |text: &str, date: &str| {
+        let conn = db.lock().unwrap();
+        NoteStore::new(&conn).save(text, date).unwrap()
+    };
+
+    let yesterday = save("Buy milk", "Thursday, October 15, 2026");
+    analyzer.analyze(&db, yesterday, "Buy milk", TODAY).await.unwrap();
+    let today = save("Call John", TODAY);
+    analyzer.analyze(&db, today, "Call John", TODAY).await.unwrap();
+
+    // Going back to yesterday's unchanged note doesn't reach the provider
+    let outcome = analyzer.analyze(&db, yesterday, "Buy milk", TODAY).await.unwrap();
+
+    assert_eq!(outcome, AnalysisOutcome::Skipped);
+    assert_eq!(provider.call_count(), 2);
+}
+
+#[tokio::test]
+async fn changing_the_model_analyzes_the_whole_note_again() {
+    let db = test_db();
+    let text = "Call John tomorrow\n\nBuy milk";
+    let first = MockProvider::new().respond_otherwise(NO_REMINDERS);
+    add_note(&db, &first, text).await.unwrap();
+
+    let other_model = MockProvider::new().with_model("mock-large").respond_otherwise(NO_REMINDERS);
+    add_note(&db, &other_model, text).await.unwrap();
+    add_note(&db, &other_model, text).await.unwrap();
+
+    assert_eq!(other_model.call_count(), 1);
+    assert!(other_model.prompts()[0].contains("Note to analyze:\nCall John tomorrow\n\nBuy milk"));
+
+    // Switching back finds the first model's entry still valid
+    add_note(&db, &first, text).await.unwrap();
+    assert_eq!(first.call_count(), 1);
+}
+
+#[tokio::test]
+async fn switching_back_to_a_model_shows_it_what_another_model_analyzed() {
+    let db = test_db();
+    let first = MockProvider::new().respond_otherwise(NO_REMINDERS);
+    add_note(&db, &first, "Call John tomorrow").await.unwrap();
+    let other_model = MockProvider::new().with_model("mock-large").respond_otherwise(NO_REMINDERS);
+    add_note(&db, &other_model, "Call John tomorrow\n\nBuy milk").await.unwrap();
+
+    add_note(&db, &first, "Call John tomorrow\n\nBuy milk\n\nWater the plants").await.unwrap();
+
+    // The first model never saw "Buy milk", so it gets the whole note rather than a diff
+    // against what the other model analyzed
+    assert_eq!(first.call_count(), 2);
+    assert!(first.prompts()[1].contains("Note to analyze:\nCall John tomorrow\n\nBuy milk\n\nWater the plants"));
+}
+
+#[tokio::test]
+async fn a_failed_write_keeps_nothing_and_is_logged() {
+    let db = test_db();
+    // Make the second reminder insert fail, like a full disk would
+    db.lock()
+        .unwrap()
+        .execute_batch(
+            "CREATE TRIGGER fail_second_reminder BEFORE INSERT ON reminders
+             WHEN (SELECT COUNT(*) FROM reminders) >= 1
+             BEGIN SELECT RAISE(ABORT, 'disk full'); END;",
+        )
+        .unwrap();
+    let provider = MockProvider::new().respond_otherwise(CREATE_TWO_REMINDERS);
+    let text = "Call John about the project tomorrow --[work]\nWater the plants";
+
+    let result = add_note(&db, &provider, text).await;
+
+    assert!(matches!(result, Err(JuliError::Database { .. })));
+    assert!(reminders(&db).is_empty());
+    let logs = logs(&db);
+    assert_eq!(logs.len(), 1);
+    assert!(!logs[0].success);
+    assert!(logs[0].response.contains("disk full"));
+    assert!(ReminderSourceStore::new(&db.lock().unwrap()).for_reminder(1).unwrap().is_empty());
+
+    // Nothing was marked as analyzed, so the next save tries again - and creates each reminder once
+    db.lock().unwrap().execute_batch("DROP TRIGGER fail_second_reminder").unwrap();
+    add_note(&db, &provider, text).await.unwrap();
+
+    assert_eq!(provider.call_count(), 2);
+    assert_eq!(reminders(&db).len(), 2);
+}
+
+#[tokio::test]
+async fn json_surrounded_by_prose_is_still_applied() {
+    let db = test_db();
+    let answer = format!(
+        "Sure! Here is what I found:\n```json\n{}\n```\nThe `reasoning` explains why.",
+        CREATE_CALL_JOHN.replace("tomorrow.", "tomorrow (see `notes`).")
+    );
+    let provider = MockProvider::new().respond_otherwise(answer);
+
+    add_note(&db, &provider, "Call John about the project tomorrow --[work]").await.unwrap();
+
+    assert_eq!(reminders(&db).len(), 1);
+    assert!(logs(&db)[0].success);
+}
@@ -0,0 +1,139 @@
+// Note history: saves are kept as revisions (coalesced while typing), can be diffed,
+// and any of them can be restored
+
+use juli_lib::core::{NoteStore, RevisionPolicy, RevisionStore};
+use juli_lib::migrations;
+use rusqlite::Connection;
+
+const DATE: &str = "Friday, October 16, 2026";
+
+fn test_db() -> Connection {
+    let mut conn = Connection::open_in_memory().unwrap();
+    migrations::run(&mut conn).unwrap();
+    conn
+}
+
+fn every_save() -> RevisionPolicy {
+    RevisionPolicy { coalesce_seconds: 0, ..Default::default() }
+}
+
+fn texts(conn: &Connection, note_id: i64) -> Vec<String> {
+    RevisionStore::new(conn)
+        .for_note(note_id)
+        .unwrap()
+        .into_iter()
+        .map(|r| r.text)
+        .collect()
+}
+
+#[test]
+fn saves_within_the_window_extend_the_latest_revision() {
+    let conn = test_db();
+    let notes = NoteStore::new(&conn);
+
+    notes.save("Call", DATE).unwrap();
+    notes.save("Call John", DATE).unwrap();
+    let note_id = notes.save("Call John tomorrow", DATE).unwrap();
+
+    let revisions = RevisionStore::new(&conn).for_note(note_id).unwrap();
+    assert_eq!(revisions.len(), 1);
+    assert_eq!(revisions[0].text, "Call John tomorrow");
+    assert_eq!(revisions[0].size, 18);
+}
+
+#[test]
+fn large_deletions_always_start_a_new_revision() {
+    let conn = test_db();
+    let notes = NoteStore::new(&conn);
+
+    notes.save("Call John tomorrow about the budget", DATE).unwrap();
+    // The accidental select-all-delete autosaves right away, well inside the window
+    let note_id = notes.save("", DATE).unwrap();
+
+    assert_eq!(texts(&conn, note_id), vec!["", "Call John tomorrow about the budget"]);
+}
+
+#[test]
+fn unchanged_saves_add_nothing() {
+    let conn = test_db();
+    let notes = NoteStore::new(&conn).with_revision_policy(every_save());
+
+    notes.save("Buy milk", DATE).unwrap();
+    let note_id = notes.save("Buy milk", DATE).unwrap();
+
+    assert_eq!(texts(&conn, note_id).len(), 1);
+}
+
+#[test]
+fn diff_lists_changed_lines() {
+    let conn = test_db();
+    let notes = NoteStore::new(&conn).with_revision_policy(every_save());
+    let note_id = notes.save("Buy milk\nCall John", DATE).unwrap();
+    notes.save("Buy milk\nCall John about the budget\nWater plants", DATE).unwrap();
+    let revisions = RevisionStore::new(&conn).for_note(note_id).unwrap();
+    let (newer, older) = (revisions[0].id, revisions[1].id);
+
+    let diff = RevisionStore::new(&conn).diff(older, newer).unwrap();
+
+    let changes: Vec<(&str, &str)> = diff.lines.iter().map(|l| (l.kind, l.text.as_str())).collect();
+    assert_eq!(
+        changes,
+        vec![
+            ("equal", "Buy milk"),
+            ("delete", "Call John"),
+            ("insert", "Call John about the budget"),
+            ("insert", "Water plants"),
+        ]
+    );
+    assert!(diff.unified.contains("+Water plants"));
+}
+
+#[test]
+fn restore_brings_back_old_text_and_keeps_the_current_one() {
+    let conn = test_db();
+    let notes = NoteStore::new(&conn);
+    let note_id = notes.save("Call John tomorrow about the budget", DATE).unwrap();
+    notes.save("", DATE).unwrap();
+    let lost = RevisionStore::new(&conn).for_note(note_id).unwrap()[1].id;
+
+    let restored = notes.restore_revision(lost).unwrap();
+
+    assert_eq!(restored.text, "Call John tomorrow about the budget");
+    assert_eq!(notes.for_date(DATE).unwrap().text, "Call John tomorrow about the budget");
+    assert_eq!(
+        texts(&conn, note_id),
+        vec!["Call John tomorrow about the budget", "", "Call John tomorrow about the budget"]
+    );
+}
+
+#[test]
+fn retention_drops_revisions_by_count_and_age() {
+    let conn = test_db();
+    let policy = RevisionPolicy { coalesce_seconds: 0, max_per_note: 3, max_age_days: Some(30) };
+    let notes = NoteStore::new(&conn).with_revision_policy(policy);
+
+    let note_id = notes.save("v1", DATE).unwrap();
+    conn.execute("UPDATE note_revisions SET created_at = '2020-01-01 00:00:00', updated_at = '2020-01-01 00:00:00'", [])
+        .unwrap();
+    for text in ["v2", "v3", "v4", "v5"] {
+        notes.save(text, DATE).unwrap();
+    }
+    assert_eq!(texts(&conn, note_id), vec!["v5", "v4", "v3"]);
+
+    // Past max_age_days everything goes, except the revision holding the current text
+    conn.execute("UPDATE note_revisions SET updated_at = '2020-01-01 00:00:00'", []).unwrap();
+    notes.save("v6", DATE).unwrap();
+    assert_eq!(texts(&conn, note_id), vec!["v6"]);
+}
+
+#[test]
+fn existing_notes_start_with_one_revision() {
+    let mut conn = Connection::open_in_memory().unwrap();
+    migrations::migrate_to(&mut conn, 6).unwrap();
+    conn.execute("INSERT INTO notes (text, for_date) VALUES ('written before history existed', ?1)", [DATE])
+        .unwrap();
+
+    migrations::run(&mut conn).unwrap();
+
+    assert_eq!(texts(&conn, 1), vec!["written before history existed"]);
+}
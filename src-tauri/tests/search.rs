@@ -0,0 +1,132 @@
+// Full-text search: the FTS5 indexes must follow every write to notes and reminders,
+// and the filters must narrow the ranked hits
+
+use juli_lib::core::{NoteStore, ReminderDraft, ReminderStore, SearchHitKind, SearchIndex, SearchQuery, SearchScope};
+use juli_lib::migrations;
+use rusqlite::Connection;
+
+fn test_db() -> Connection {
+    let mut conn = Connection::open_in_memory().unwrap();
+    migrations::run(&mut conn).unwrap();
+    conn
+}
+
+fn draft(text: &str, tags: Option<&str>) -> ReminderDraft {
+    ReminderDraft { text: text.to_string(), tags: tags.map(str::to_string), ..Default::default() }
+}
+
+fn search(conn: &Connection, query: SearchQuery) -> Vec<(SearchHitKind, i64)> {
+    SearchIndex::new(conn)
+        .search(&query)
+        .unwrap()
+        .into_iter()
+        .map(|hit| (hit.kind, hit.id))
+        .collect()
+}
+
+fn words(query: &str) -> SearchQuery {
+    SearchQuery { query: query.to_string(), ..Default::default() }
+}
+
+#[test]
+fn index_follows_inserts_updates_and_deletes() {
+    let conn = test_db();
+    let notes = NoteStore::new(&conn);
+    let reminders = ReminderStore::new(&conn);
+
+    let note_id = notes.save("Lunch with Priya", "Friday, October 16, 2026").unwrap();
+    let reminder_id = reminders.create(note_id, &draft("Book a table for lunch", Some("social"))).unwrap();
+    assert_eq!(
+        search(&conn, words("lunch")).len(),
+        2,
+        "both the note and the reminder are indexed on insert"
+    );
+
+    // Saving the same date again goes through ON CONFLICT DO UPDATE
+    notes.save("Dinner with Priya", "Friday, October 16, 2026").unwrap();
+    reminders.update(reminder_id, &draft("Book a table for dinner", Some("social"))).unwrap();
+    assert!(search(&conn, words("lunch")).is_empty());
+    assert_eq!(search(&conn, words("dinner")).len(), 2);
+
+    reminders.delete(reminder_id).unwrap();
+    assert_eq!(search(&conn, words("dinner")), vec![(SearchHitKind::Note, note_id)]);
+}
+
+#[test]
+fn rows_written_before_the_index_existed_are_searchable() {
+    let mut conn = Connection::open_in_memory().unwrap();
+    migrations::migrate_to(&mut conn, 4).unwrap();
+    conn.execute_batch(
+        "INSERT INTO notes (text, for_date) VALUES ('Renew the passport', 'Friday, October 16, 2026');
+         INSERT INTO reminders (created_from_note_id, text, tags) VALUES (1, 'Renew passport', 'admin');",
+    )
+    .unwrap();
+
+    migrations::run(&mut conn).unwrap();
+
+    assert_eq!(search(&conn, words("passport")).len(), 2);
+    // The backfilled day makes old notes reachable by date range too
+    let in_range = SearchQuery { from_date: Some("2026-10-16".into()), to_date: Some("2026-10-16".into()), ..words("passport") };
+    assert_eq!(search(&conn, in_range).len(), 2);
+}
+
+#[test]
+fn hits_carry_snippets_with_highlight_offsets() {
+    let conn = test_db();
+    NoteStore::new(&conn)
+        .save("Called the bank. Still need to call John about the budget", "Friday, October 16, 2026")
+        .unwrap();
+
+    let hits = SearchIndex::new(&conn).search(&words("john budg")).unwrap();
+
+    assert_eq!(hits.len(), 1);
+    let hit = &hits[0];
+    let highlighted: Vec<&str> = hit.highlights.iter().map(|&(start, end)| &hit.snippet[start..end]).collect();
+    // "budg" is the word being typed, so it matches as a prefix
+    assert_eq!(highlighted, vec!["John", "budget"]);
+    assert!(!hit.snippet.contains('\u{2}'));
+}
+
+#[test]
+fn filters_narrow_the_results() {
+    let conn = test_db();
+    let notes = NoteStore::new(&conn);
+    let reminders = ReminderStore::new(&conn);
+    let october = notes.save("Report for the board", "Friday, October 16, 2026").unwrap();
+    let november = notes.save("Report for the team", "Monday, November 16, 2026").unwrap();
+    let board = reminders.create(october, &draft("Send the board report", Some("work, finance"))).unwrap();
+    let team = reminders.create(november, &draft("Send the team report", Some("work"))).unwrap();
+    let homework = reminders.create(november, &draft("Mark the report cards", Some("homework"))).unwrap();
+    reminders.resolve(board).unwrap();
+
+    let only_reminders = SearchQuery { scope: SearchScope::Reminders, ..words("report") };
+    assert_eq!(search(&conn, only_reminders).len(), 3);
+
+    let open = SearchQuery { scope: SearchScope::Reminders, resolved: Some(false), ..words("report") };
+    let mut ids: Vec<i64> = search(&conn, open).into_iter().map(|(_, id)| id).collect();
+    ids.sort();
+    assert_eq!(ids, vec![team, homework]);
+
+    // "work" must not match "homework"
+    let work = SearchQuery { tags: vec!["Work".into()], scope: SearchScope::Reminders, ..words("report") };
+    let mut ids: Vec<i64> = search(&conn, work).into_iter().map(|(_, id)| id).collect();
+    ids.sort();
+    assert_eq!(ids, vec![board, team]);
+
+    let october_only = SearchQuery { to_date: Some("2026-10-31".into()), ..words("report") };
+    let mut hits = search(&conn, october_only);
+    hits.sort_by_key(|&(kind, id)| (kind == SearchHitKind::Reminder, id));
+    assert_eq!(hits, vec![(SearchHitKind::Note, october), (SearchHitKind::Reminder, board)]);
+}
+
+#[test]
+fn user_input_is_never_parsed_as_fts_syntax() {
+    let conn = test_db();
+    NoteStore::new(&conn).save("follow-up: \"quarterly\" numbers", "Friday, October 16, 2026").unwrap();
+
+    assert_eq!(search(&conn, words("follow-up: \"quarterly")).len(), 1);
+    assert!(search(&conn, words("   ")).is_empty());
+
+    let bad_date = SearchQuery { from_date: Some("last week".into()), ..words("numbers") };
+    assert_eq!(SearchIndex::new(&conn).search(&bad_date).unwrap_err().kind(), "Validation");
+}
@@ -0,0 +1,184 @@
+// Snoozing reminders: reading "when", moving the reminder, and keeping notifications quiet until then
+
+use chrono::{DateTime, NaiveDate, Utc};
+use chrono_tz::Tz;
+use juli_lib::core::{snooze_date, NotificationSettings, Notifier, ReminderDraft, ReminderPatch, ReminderStore};
+use juli_lib::error::JuliError;
+use juli_lib::migrations;
+use rusqlite::Connection;
+
+const NEW_YORK: Tz = chrono_tz::America::New_York;
+
+fn test_db() -> Connection {
+    let mut conn = Connection::open_in_memory().unwrap();
+    migrations::run(&mut conn).unwrap();
+    conn
+}
+
+fn date(s: &str) -> NaiveDate {
+    NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+}
+
+// Snoozing on Friday 2026-10-16
+fn until(when: &str, due_date: Option<&str>) -> Result<String, String> {
+    snooze_date(when, date("2026-10-16"), due_date.map(date)).map(|d| d.to_string())
+}
+
+fn add(conn: &Connection, text: &str, due_date: &str, due_time: Option<&str>, recurrence: Option<&str>) -> i64 {
+    let draft = ReminderDraft {
+        text: text.to_string(),
+        due_date: Some(due_date.to_string()),
+        due_time: due_time.map(str::to_string),
+        recurrence: recurrence.map(str::to_string),
+        ..Default::default()
+    };
+    ReminderStore::new(conn).with_timezone(NEW_YORK).create_manual(draft).unwrap()
+}
+
+fn at(time: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(time).unwrap().with_timezone(&Utc)
+}
+
+#[test]
+fn when_is_a_date_or_relative_to_the_due_date() {
+    // Undated or overdue: counted from today
+    for due in [None, Some("2026-10-10")] {
+        assert_eq!(until("2026-10-30", due).unwrap(), "2026-10-30");
+        assert_eq!(until("1d", due).unwrap(), "2026-10-17");
+        assert_eq!(until("2w", due).unwrap(), "2026-10-30");
+        assert_eq!(until("tomorrow", due).unwrap(), "2026-10-17");
+        assert_eq!(until("next monday", due).unwrap(), "2026-10-19");
+        assert_eq!(until(" Mon ", due).unwrap(), "2026-10-19");
+        assert_eq!(until("next week", due).unwrap(), "2026-10-19");
+        // Today is a Friday, so the end of the week is the next one
+        assert_eq!(until("eow", due).unwrap(), "2026-10-23");
+        assert_eq!(until("friday", due).unwrap(), "2026-10-23");
+    }
+
+    // Due next Tuesday: counted from then
+    assert_eq!(until("1d", Some("2026-10-20")).unwrap(), "2026-10-21");
+    assert_eq!(until("eow", Some("2026-10-20")).unwrap(), "2026-10-23");
+    assert_eq!(until("next monday", Some("2026-10-20")).unwrap(), "2026-10-26");
+}
+
+#[test]
+fn when_has_to_be_later() {
+    for when in ["", "soon", "0d", "-1d", "1y", "mo", "2026-02-30", "1é", "été", "1€", "2🙂"] {
+        assert!(until(when, None).is_err(), "{:?} should be refused", when);
+    }
+    assert!(until("2026-10-15", None).unwrap_err().contains("in the past"));
+    assert!(until("2026-10-19", Some("2026-10-20")).is_err());
+    assert!(until("tomorrow", Some("2026-10-20")).is_err());
+}
+
+#[test]
+fn snoozing_moves_the_reminder_and_counts() {
+    let conn = test_db();
+    let id = add(&conn, "Dentist", "2026-10-16", Some("15:00"), None);
+    let reminders = ReminderStore::new(&conn).with_timezone(NEW_YORK);
+
+    let snoozed = reminders.snooze_on(id, "1d", date("2026-10-16")).unwrap();
+    assert_eq!(snoozed.due_date.as_deref(), Some("2026-10-17"));
+    assert_eq!(snoozed.due_time.as_deref(), Some("15:00"));
+    assert_eq!(snoozed.due_at.as_deref(), Some("2026-10-17T15:00:00-04:00"));
+    assert_eq!(snoozed.snooze_count, 1);
+    assert_eq!(snoozed.original_due_date.as_deref(), Some("2026-10-16"));
+
+    // original_due_date stays the one from before the first snooze
+    let again = reminders.snooze_on(id, "next monday", date("2026-10-17")).unwrap();
+    assert_eq!(again.due_date.as_deref(), Some("2026-10-19"));
+    assert_eq!(again.snooze_count, 2);
+    assert_eq!(again.original_due_date.as_deref(), Some("2026-10-16"));
+}
+
+#[test]
+fn bad_snoozes_leave_the_reminder_alone() {
+    let conn = test_db();
+    let id = add(&conn, "Dentist", "2026-10-16", None, None);
+    let reminders = ReminderStore::new(&conn);
+
+    assert!(matches!(reminders.snooze_on(id, "later", date("2026-10-16")), Err(JuliError::Validation { .. })));
+    assert!(matches!(reminders.snooze_on(99, "1d", date("2026-10-16")), Err(JuliError::NotFound { .. })));
+    reminders.resolve(id).unwrap();
+    assert!(matches!(reminders.snooze_on(id, "1d", date("2026-10-16")), Err(JuliError::Validation { .. })));
+
+    let reminder = reminders.get(id).unwrap();
+    assert_eq!(reminder.due_date.as_deref(), Some("2026-10-16"));
+    assert_eq!(reminder.snooze_count, 0);
+}
+
+#[test]
+fn a_snoozed_occurrence_doesnt_shift_its_series() {
+    let conn = test_db();
+    let id = add(&conn, "Pay rent", "2026-11-01", None, Some("FREQ=MONTHLY"));
+    let reminders = ReminderStore::new(&conn);
+    reminders.snooze_on(id, "2d", date("2026-11-01")).unwrap();
+
+    let next_id = reminders.resolve_on(id, date("2026-11-03")).unwrap().unwrap();
+
+    let next = reminders.get(next_id).unwrap();
+    assert_eq!(next.due_date.as_deref(), Some("2026-12-01"));
+    assert_eq!(next.snooze_count, 0);
+    assert_eq!(next.original_due_date, None);
+}
+
+#[test]
+fn editing_the_due_date_ends_the_snooze() {
+    let conn = test_db();
+    let id = add(&conn, "Pay rent", "2026-11-01", None, Some("FREQ=MONTHLY"));
+    let reminders = ReminderStore::new(&conn);
+    reminders.snooze_on(id, "2d", date("2026-11-01")).unwrap();
+
+    let moved = ReminderPatch { due_date: Some(Some("2026-11-05".to_string())), ..Default::default() };
+    let edited = reminders.edit(id, moved).unwrap();
+    assert_eq!(edited.snooze_count, 0);
+    assert_eq!(edited.original_due_date, None);
+
+    // The series now follows the date set by hand, not the one from before the snooze
+    let next_id = reminders.resolve_on(id, date("2026-11-05")).unwrap().unwrap();
+    assert_eq!(reminders.get(next_id).unwrap().due_date.as_deref(), Some("2026-12-05"));
+}
+
+#[test]
+fn snoozed_reminders_stay_quiet_until_their_new_date() {
+    let conn = test_db();
+    let dentist = add(&conn, "Dentist", "2026-10-16", Some("15:00"), None);
+    let report = add(&conn, "Send the report", "2026-10-15", None, None);
+    let reminders = ReminderStore::new(&conn).with_timezone(NEW_YORK);
+    reminders.snooze_on(dentist, "1d", date("2026-10-16")).unwrap();
+    reminders.snooze_on(report, "next monday", date("2026-10-16")).unwrap();
+
+    // A day's notice would start at 15:00 today, but it's snoozed until tomorrow
+    let settings = NotificationSettings { enabled: true, lead_minutes: vec![24 * 60], digest_time: Some("08:00".to_string()) };
+    let notifier = Notifier::new(&conn, &settings, NEW_YORK);
+    assert!(notifier.due(at("2026-10-16T16:00:00-04:00")).unwrap().is_empty());
+    assert_eq!(notifier.seconds_until_next(at("2026-10-16T16:00:00-04:00")).unwrap(), Some(8 * 60 * 60));
+
+    let tomorrow = notifier.due(at("2026-10-17T00:00:00-04:00")).unwrap();
+    assert_eq!(tomorrow.len(), 1);
+    assert_eq!(tomorrow[0].title, "Dentist");
+
+    // The report isn't overdue any more, so the digest only has the dentist
+    let digest = notifier.due(at("2026-10-17T08:00:00-04:00")).unwrap();
+    assert!(digest.iter().all(|n| !n.body.contains("Send the report")));
+}
+
+#[test]
+fn a_snooze_ends_at_midnight_where_the_reminder_is() {
+    let conn = test_db();
+    let draft = ReminderDraft {
+        text: "Call the Tokyo office".to_string(),
+        due_date: Some("2026-10-16".to_string()),
+        due_time: Some("08:00".to_string()),
+        ..Default::default()
+    };
+    let reminders = ReminderStore::new(&conn).with_timezone(chrono_tz::Asia::Tokyo);
+    let id = reminders.create_manual(draft).unwrap();
+    reminders.snooze_on(id, "1d", date("2026-10-16")).unwrap();
+
+    // 08:00 in Tokyo on the 17th is still the evening of the 16th in New York
+    let settings = NotificationSettings { enabled: true, lead_minutes: vec![0], digest_time: None };
+    let due = Notifier::new(&conn, &settings, NEW_YORK).due(at("2026-10-16T19:00:00-04:00")).unwrap();
+    assert_eq!(due.len(), 1);
+    assert_eq!(due[0].reminder_id, Some(id));
+}
@@ -0,0 +1,36 @@
+// The checks every reminder from the AI goes through before it's written
+
+use juli_lib::core::{normalize_tags, parse_due_date, parse_due_time};
+
+#[test]
+fn due_dates_must_be_real_calendar_dates() {
+    assert_eq!(parse_due_date(Some("2026-10-20")), Ok(Some("2026-10-20".to_string())));
+    assert_eq!(parse_due_date(Some(" 2026-10-20 ")), Ok(Some("2026-10-20".to_string())));
+    assert_eq!(parse_due_date(None), Ok(None));
+    assert_eq!(parse_due_date(Some("")), Ok(None));
+    assert_eq!(parse_due_date(Some("null")), Ok(None));
+
+    assert!(parse_due_date(Some("2026-02-30")).is_err());
+    assert!(parse_due_date(Some("next friday")).is_err());
+    assert!(parse_due_date(Some("10/20/2026")).is_err());
+}
+
+#[test]
+fn due_times_are_24_hour_hh_mm() {
+    assert_eq!(parse_due_time(Some("15:00")), Ok(Some("15:00".to_string())));
+    assert_eq!(parse_due_time(Some(" 9:30 ")), Ok(Some("09:30".to_string())));
+    assert_eq!(parse_due_time(Some("15:00:59")), Ok(Some("15:00".to_string())));
+    assert_eq!(parse_due_time(Some("null")), Ok(None));
+    assert_eq!(parse_due_time(None), Ok(None));
+
+    assert!(parse_due_time(Some("3pm")).is_err());
+    assert!(parse_due_time(Some("24:00")).is_err());
+}
+
+#[test]
+fn tags_are_trimmed_lowercased_and_deduplicated() {
+    assert_eq!(normalize_tags(Some("work,urgent")), Some("work,urgent".to_string()));
+    assert_eq!(normalize_tags(Some(" Work, #urgent,,work ")), Some("work,urgent".to_string()));
+    assert_eq!(normalize_tags(Some(" , ")), None);
+    assert_eq!(normalize_tags(None), None);
+}
@@ -1,7 +1,7 @@
 import { useState, useEffect, useRef, useCallback } from "react";
 import "./App.css";
 
-import type { View, DayNote } from "./lib/types";
+import type { View, DayNote, ReminderSort } from "./lib/types";
 import * as api from "./services/api";
 import { useNotes, useReminders, useAiLogs, useKeyboardNavigation } from "./hooks";
 import {
@@ -17,11 +17,11 @@ function App() {
   const [currentView, setCurrentView] = useState<View>('today');
   const [pastDays, setPastDays] = useState<DayNote[]>([]);
   const [selectedReminderIndex, setSelectedReminderIndex] = useState<number | null>(null);
+  const [reminderSort, setReminderSort] = useState<ReminderSort>('due');
   const textareaRef = useRef<HTMLTextAreaElement>(null);
 
   const {
     reminders,
-    setReminders,
     searchQuery,
     setSearchQuery,
     showResolvedReminders,
@@ -33,7 +33,7 @@ function App() {
     filteredUnresolvedReminders,
     filteredResolvedReminders,
     resetResolvedView,
-  } = useReminders();
+  } = useReminders(reminderSort);
 
   const {
     notes,
@@ -46,13 +46,23 @@ function App() {
     loadTodayNote,
     saveNote,
     clearDebounceTimer,
-  } = useNotes({
-    onRemindersUpdate: setReminders,
-    remindersCount: reminders.length,
-  });
+  } = useNotes();
 
   const { aiLogs, loadAiLogs, deleteAiLog, deleteAllAiLogs } = useAiLogs();
 
+  // Keep the history view current when any note is saved
+  useEffect(() => {
+    const unlisten = api.onNoteSaved((note) => {
+      setPastDays(days => {
+        const known = days.some(day => String(day.id) === String(note.id));
+        return known ? days.map(day => (String(day.id) === String(note.id) ? note : day)) : [...days, note];
+      });
+    });
+    return () => {
+      void unlisten.then((stop) => stop());
+    };
+  }, []);
+
   // Handle resolving reminder with selection management
   const handleResolveReminder = useCallback(async (reminderId: string) => {
     const updatedReminders = await resolveReminder(reminderId);
@@ -80,8 +90,7 @@ function App() {
       const note = await api.getNotesForDate(formattedDate);
       setNotes(note);
 
-      const remindersData = await api.getUnresolvedReminders();
-      setReminders(remindersData);
+      await loadUnresolvedReminders();
 
       if (showResolvedReminders) {
         await toggleShowResolved();
@@ -98,7 +107,7 @@ function App() {
     } catch (error) {
       console.error('Failed to reload:', error);
     }
-  }, [currentView, showResolvedReminders, setCurrentDate, setNotes, setReminders, toggleShowResolved, loadAiLogs]);
+  }, [currentView, showResolvedReminders, setCurrentDate, setNotes, loadUnresolvedReminders, toggleShowResolved, loadAiLogs]);
 
   const switchView = useCallback((view: View) => {
     // Save current notes before switching views
@@ -245,6 +254,8 @@ function App() {
           <RemindersView
             searchQuery={searchQuery}
             onSearchChange={setSearchQuery}
+            sort={reminderSort}
+            onSortChange={setReminderSort}
             showResolvedReminders={showResolvedReminders}
             onToggleShowResolved={toggleShowResolved}
             filteredUnresolvedReminders={filteredUnresolvedReminders}
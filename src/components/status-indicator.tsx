@@ -15,12 +15,24 @@ export function StatusIndicator({ status }: StatusIndicatorProps) {
           <span className="status-text">Saving...</span>
         </>
       )}
+      {status.type === 'ai-queued' && (
+        <>
+          <span className="status-dot"></span>
+          <span className="status-text">Saved · waiting for AI...</span>
+        </>
+      )}
       {status.type === 'ai-running' && (
         <>
           <span className="status-dot"></span>
           <span className="status-text">AI analyzing...</span>
         </>
       )}
+      {status.type === 'ai-retrying' && (
+        <>
+          <span className="status-icon">↻</span>
+          <span className="status-text" title={status.message}>Saved · AI will run when back online</span>
+        </>
+      )}
       {status.type === 'ai-success' && (
         <>
           <span className="status-icon">✓</span>
@@ -37,6 +49,18 @@ export function StatusIndicator({ status }: StatusIndicatorProps) {
           <span className="status-text">Saved</span>
         </>
       )}
+      {status.type === 'setup-required' && (
+        <>
+          <span className="status-icon">!</span>
+          <span className="status-text" title={status.message}>Set up your API key to enable AI reminders</span>
+        </>
+      )}
+      {status.type === 'error' && (
+        <>
+          <span className="status-icon">!</span>
+          <span className="status-text">{status.message}</span>
+        </>
+      )}
     </div>
   );
 }
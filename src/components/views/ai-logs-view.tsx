@@ -61,6 +61,24 @@ export function AiLogsView({
                   <h4>Response</h4>
                   <pre className="detail-content">{log.response}</pre>
                 </div>
+                {log.attempts.length > 1 && (
+                  <div className="detail-section">
+                    <h4>Attempts</h4>
+                    <pre className="detail-content">
+                      {log.attempts
+                        .map((a) => `#${a.attempt} ${a.status ?? 'no response'} ${a.duration_ms}ms${a.error ? ` - ${a.error}` : ''}${a.retry_in_ms !== null ? ` (retried after ${a.retry_in_ms}ms)` : ''}`)
+                        .join('\n')}
+                    </pre>
+                  </div>
+                )}
+                {log.rejections.length > 0 && (
+                  <div className="detail-section">
+                    <h4>Rejected</h4>
+                    {log.rejections.map((rejection, i) => (
+                      <pre key={i} className="detail-content">{rejection.reason}{'\n'}{rejection.item}</pre>
+                    ))}
+                  </div>
+                )}
               </div>
             </details>
           ))
@@ -1,8 +1,10 @@
-import type { Reminder } from "../../lib/types";
+import type { Reminder, ReminderSort } from "../../lib/types";
 
 interface RemindersViewProps {
   searchQuery: string;
   onSearchChange: (query: string) => void;
+  sort: ReminderSort;
+  onSortChange: (sort: ReminderSort) => void;
   showResolvedReminders: boolean;
   onToggleShowResolved: () => void;
   filteredUnresolvedReminders: Reminder[];
@@ -12,10 +14,14 @@ interface RemindersViewProps {
   onDeleteReminder: (reminderId: string, isResolved?: boolean) => void;
 }
 
+// A date as YYYY-MM-DD in the local timezone (toISOString() would give the UTC date)
+function localDate(date: Date): string {
+  return date.toLocaleDateString('en-CA');
+}
+
 // Helper to get today's date in YYYY-MM-DD format
 function getTodayDate(): string {
-  const now = new Date();
-  return now.toISOString().split('T')[0];
+  return localDate(new Date());
 }
 
 // Helper to check if a date is today
@@ -41,15 +47,26 @@ function formatDueDate(dateStr: string | undefined): string {
   }
   const tomorrow = new Date();
   tomorrow.setDate(tomorrow.getDate() + 1);
-  if (dateStr === tomorrow.toISOString().split('T')[0]) return 'Tomorrow';
+  if (dateStr === localDate(tomorrow)) return 'Tomorrow';
   // Format as "Dec 20, 2025"
   const date = new Date(dateStr + 'T00:00:00');
   return date.toLocaleDateString('en-US', { month: 'short', day: 'numeric', year: 'numeric' });
 }
 
+// "3:00 PM", with the timezone when it isn't this computer's ("9:00 AM Europe/Paris")
+function formatDueTime(reminder: Reminder): string {
+  if (!reminder.due_time) return '';
+  const [hours, minutes] = reminder.due_time.split(':').map(Number);
+  const time = new Date(2000, 0, 1, hours, minutes).toLocaleTimeString('en-US', { hour: 'numeric', minute: '2-digit' });
+  const localZone = Intl.DateTimeFormat().resolvedOptions().timeZone;
+  return reminder.timezone && reminder.timezone !== localZone ? `${time} ${reminder.timezone}` : time;
+}
+
 export function RemindersView({
   searchQuery,
   onSearchChange,
+  sort,
+  onSortChange,
   showResolvedReminders,
   onToggleShowResolved,
   filteredUnresolvedReminders,
@@ -68,6 +85,16 @@ export function RemindersView({
           value={searchQuery}
           onChange={(e) => onSearchChange(e.target.value)}
         />
+        <select
+          className="sort-select"
+          aria-label="Sort reminders"
+          value={sort}
+          onChange={(e) => onSortChange(e.target.value as ReminderSort)}
+        >
+          <option value="due">by due date</option>
+          <option value="priority">by priority</option>
+          <option value="created">newest first</option>
+        </select>
         <button
           className={`toggle-resolved-btn ${showResolvedReminders ? 'active' : ''}`}
           onClick={onToggleShowResolved}
@@ -97,8 +124,12 @@ export function RemindersView({
                       {reminder.due_date && (
                         <span className={`reminder-due-date ${dueToday ? 'today' : ''} ${overdue ? 'overdue' : ''}`}>
                           Due: {formatDueDate(reminder.due_date)}
+                          {reminder.due_time && ` at ${formatDueTime(reminder)}`}
                         </span>
                       )}
+                      {reminder.repeats && (
+                        <span className="reminder-repeats">↻ {reminder.repeats}</span>
+                      )}
                       {reminder.tags && (
                         <div className="reminder-tags">
                           {reminder.tags.split(',').map((tag, idx) => (
@@ -144,6 +175,7 @@ export function RemindersView({
                           {reminder.due_date && (
                             <span className="reminder-due-date">
                               Due: {formatDueDate(reminder.due_date)}
+                              {reminder.due_time && ` at ${formatDueTime(reminder)}`}
                             </span>
                           )}
                           {reminder.tags && (
@@ -14,10 +14,14 @@ interface TodayViewProps {
   onDeselectReminders: () => void;
 }
 
+// A date as YYYY-MM-DD in the local timezone (toISOString() would give the UTC date)
+function localDate(date: Date): string {
+  return date.toLocaleDateString('en-CA');
+}
+
 // Helper to get today's date in YYYY-MM-DD format
 function getTodayDate(): string {
-  const now = new Date();
-  return now.toISOString().split('T')[0];
+  return localDate(new Date());
 }
 
 // Helper to check if a date is today
@@ -43,7 +47,7 @@ function formatDueDate(dateStr: string | undefined): string {
   }
   const tomorrow = new Date();
   tomorrow.setDate(tomorrow.getDate() + 1);
-  if (dateStr === tomorrow.toISOString().split('T')[0]) return 'tomorrow';
+  if (dateStr === localDate(tomorrow)) return 'tomorrow';
   // Format as "Dec 20"
   const date = new Date(dateStr + 'T00:00:00');
   return date.toLocaleDateString('en-US', { month: 'short', day: 'numeric' });
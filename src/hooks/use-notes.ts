@@ -1,13 +1,9 @@
-import { useState, useRef, useCallback } from "react";
+import { useState, useRef, useCallback, useEffect } from "react";
 import type { DayNote, StatusState } from "../lib/types";
+import { isJuliError } from "../lib/types";
 import * as api from "../services/api";
 
-interface UseNotesOptions {
-  onRemindersUpdate: (reminders: Awaited<ReturnType<typeof api.getUnresolvedReminders>>) => void;
-  remindersCount: number;
-}
-
-export function useNotes({ onRemindersUpdate, remindersCount }: UseNotesOptions) {
+export function useNotes() {
   const [notes, setNotes] = useState<DayNote | null>(null);
   const [currentDate, setCurrentDate] = useState("");
   const [status, setStatus] = useState<StatusState>({ type: null });
@@ -21,43 +17,87 @@ export function useNotes({ onRemindersUpdate, remindersCount }: UseNotesOptions)
     }
   }, []);
 
-  const saveNote = useCallback(async (text: string, forDate: string) => {
-    try {
-      setStatus({ type: 'saving' });
+  const showStatus = useCallback((next: StatusState, hideAfterMs?: number) => {
+    if (statusTimeoutRef.current !== null) {
+      clearTimeout(statusTimeoutRef.current);
+      statusTimeoutRef.current = null;
+    }
+    setStatus(next);
+    if (hideAfterMs !== undefined) {
+      statusTimeoutRef.current = window.setTimeout(() => {
+        setStatus({ type: null });
+      }, hideAfterMs);
+    }
+  }, []);
 
-      if (statusTimeoutRef.current !== null) {
-        clearTimeout(statusTimeoutRef.current);
+  // The analysis runs in the background after the note is saved; the status follows its events
+  // (queued -> started -> completed or failed), including retries of notes saved while offline
+  useEffect(() => {
+    const unlistenQueued = api.onAnalysisQueued(() => {
+      showStatus({ type: 'ai-queued' });
+    });
+
+    const unlistenStarted = api.onAnalysisStarted(() => {
+      showStatus({ type: 'ai-running' });
+    });
+
+    // The reminders themselves arrive as reminder:* events (see use-reminders.ts);
+    // this only reports how many the analysis created
+    const unlistenCompleted = api.onAnalysisCompleted((event) => {
+      if (event.created.length > 0) {
+        showStatus({ type: 'ai-success', remindersCount: event.created.length }, 3000);
+      } else {
+        showStatus({ type: 'ai-no-action' }, 3000);
       }
-
-      const currentRemindersCount = remindersCount;
-
-      setTimeout(() => {
-        setStatus({ type: 'ai-running' });
-      }, 300);
-
-      const result = await api.addNote(text, forDate);
-      console.log('Note saved:', result);
-
-      const updatedReminders = await api.getUnresolvedReminders();
-      onRemindersUpdate(updatedReminders);
-
-      const newRemindersCount = updatedReminders.length - currentRemindersCount;
-
-      if (newRemindersCount > 0) {
-        setStatus({ type: 'ai-success', remindersCount: newRemindersCount });
+    });
+
+    const unlistenFailed = api.onAnalysisFailed((event) => {
+      console.error(`Analysis job ${event.job_id} failed:`, event.error);
+      if (event.kind === 'Config') {
+        showStatus({ type: 'setup-required', message: event.error.message });
+      } else if (event.will_retry) {
+        showStatus({ type: 'ai-retrying', message: event.error.message }, 5000);
       } else {
-        setStatus({ type: 'ai-no-action' });
+        showStatus({ type: 'error', message: event.error.message }, 5000);
       }
+    });
 
-      statusTimeoutRef.current = window.setTimeout(() => {
-        setStatus({ type: null });
-      }, 3000);
+    return () => {
+      for (const unlisten of [unlistenQueued, unlistenStarted, unlistenCompleted, unlistenFailed]) {
+        void unlisten.then((stop) => stop());
+      }
+    };
+  }, [showStatus]);
+
+  // A note saved somewhere else (juli-cli, a restored revision, ...) replaces the one
+  // in the editor, unless there are unsaved edits waiting for the debounce timer
+  useEffect(() => {
+    const unlisten = api.onNoteSaved((note) => {
+      if (note.for_date === currentDate && debounceTimerRef.current === null) {
+        setNotes(current => (current?.text === note.text ? current : note));
+      }
+    });
+    return () => {
+      void unlisten.then((stop) => stop());
+    };
+  }, [currentDate]);
 
+  const saveNote = useCallback(async (text: string, forDate: string) => {
+    try {
+      showStatus({ type: 'saving' });
+
+      // Returns as soon as the note is written; the analysis:* events take it from here
+      const saved = await api.addNote(text, forDate);
+      console.log('Note saved:', saved.note_id, 'analysis job:', saved.job_id);
     } catch (error) {
       console.error('Failed to save note:', error);
-      setStatus({ type: null });
+      if (isJuliError(error)) {
+        showStatus({ type: 'error', message: error.message }, 5000);
+      } else {
+        showStatus({ type: null });
+      }
     }
-  }, [onRemindersUpdate, remindersCount]);
+  }, [showStatus]);
 
   const handleNotesChange = useCallback((text: string) => {
     if (!currentDate) return;
@@ -67,6 +107,7 @@ export function useNotes({ onRemindersUpdate, remindersCount }: UseNotesOptions)
     clearDebounceTimer();
 
     debounceTimerRef.current = window.setTimeout(() => {
+      debounceTimerRef.current = null;
       saveNote(text, currentDate);
     }, 30000);
   }, [currentDate, clearDebounceTimer, saveNote]);
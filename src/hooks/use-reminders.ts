@@ -1,16 +1,53 @@
-import { useState, useCallback } from "react";
-import type { Reminder } from "../lib/types";
+import { useState, useCallback, useEffect, useRef } from "react";
+import type { Priority, Reminder, ReminderDraft, ReminderPatch, ReminderSort } from "../lib/types";
 import * as api from "../services/api";
 
-export function useReminders() {
+// Most pressing first
+const PRIORITY_RANK: Record<Priority, number> = { urgent: 0, high: 1, normal: 2, low: 3 };
+
+// Ascending, with missing values last
+function byField(x?: string | null, y?: string | null): number {
+  if (x === y) return 0;
+  if (!x) return 1;
+  if (!y) return -1;
+  return x < y ? -1 : 1;
+}
+
+// By due date (none last), then due time (untimed last)
+// due_at carries an offset, so compare the moments rather than the strings
+function byDue(a: Reminder, b: Reminder): number {
+  const byMoment = (x: string | null, y: string | null) =>
+    x && y ? Date.parse(x) - Date.parse(y) : byField(x, y);
+  return byField(a.due_date, b.due_date) || byMoment(a.due_at, b.due_at);
+}
+
+const byPriority = (a: Reminder, b: Reminder) => PRIORITY_RANK[a.priority] - PRIORITY_RANK[b.priority];
+
+// Same orders as the backend's ReminderSort, so a reminder that changes lands where a refetch would put it
+const COMPARE_REMINDERS: Record<ReminderSort, (a: Reminder, b: Reminder) => number> = {
+  // Soonest due first, the same day by priority, then oldest first
+  due: (a, b) => byDue(a, b) || byPriority(a, b) || byField(a.created_at, b.created_at) || Number(a.id) - Number(b.id),
+  // Most pressing first, then by due date, then oldest first
+  priority: (a, b) => byPriority(a, b) || byDue(a, b) || byField(a.created_at, b.created_at) || Number(a.id) - Number(b.id),
+  // Newest first
+  created: (a, b) =>
+    Number(!a.created_at) - Number(!b.created_at) || byField(b.created_at, a.created_at) || Number(b.id) - Number(a.id),
+};
+
+// `sort` is the order the lists are fetched and kept in (see the sort control in the reminders view)
+export function useReminders(sort: ReminderSort = 'due') {
   const [reminders, setReminders] = useState<Reminder[]>([]);
   const [resolvedReminders, setResolvedReminders] = useState<Reminder[]>([]);
   const [showResolvedReminders, setShowResolvedReminders] = useState(false);
   const [searchQuery, setSearchQuery] = useState("");
+  // Reminder ids matching searchQuery, best match first (null when not searching)
+  const [searchRanking, setSearchRanking] = useState<Map<string, number> | null>(null);
+  // The sort the lists were last loaded with; a ref so the loaders below don't change with it
+  const sortRef = useRef(sort);
 
   const loadUnresolvedReminders = useCallback(async () => {
     try {
-      const data = await api.getUnresolvedReminders();
+      const data = await api.getUnresolvedReminders(sortRef.current);
       setReminders(data);
       return data;
     } catch (error) {
@@ -21,7 +58,7 @@ export function useReminders() {
 
   const loadResolvedReminders = useCallback(async () => {
     try {
-      const data = await api.getResolvedReminders();
+      const data = await api.getResolvedReminders(sortRef.current);
       setResolvedReminders(data);
       return data;
     } catch (error) {
@@ -40,11 +77,11 @@ export function useReminders() {
   const resolveReminder = useCallback(async (reminderId: string) => {
     try {
       await api.resolveReminder(parseInt(reminderId));
-      const updatedReminders = await api.getUnresolvedReminders();
+      const updatedReminders = await api.getUnresolvedReminders(sortRef.current);
       setReminders(updatedReminders);
 
       if (showResolvedReminders) {
-        const updatedResolved = await api.getResolvedReminders();
+        const updatedResolved = await api.getResolvedReminders(sortRef.current);
         setResolvedReminders(updatedResolved);
       }
 
@@ -58,11 +95,11 @@ export function useReminders() {
   const unresolveReminder = useCallback(async (reminderId: string) => {
     try {
       await api.unresolveReminder(parseInt(reminderId));
-      const updatedReminders = await api.getUnresolvedReminders();
+      const updatedReminders = await api.getUnresolvedReminders(sortRef.current);
       setReminders(updatedReminders);
 
       if (showResolvedReminders) {
-        const updatedResolved = await api.getResolvedReminders();
+        const updatedResolved = await api.getResolvedReminders(sortRef.current);
         setResolvedReminders(updatedResolved);
       }
     } catch (error) {
@@ -74,10 +111,10 @@ export function useReminders() {
     try {
       await api.deleteReminder(parseInt(reminderId));
       if (isResolved && showResolvedReminders) {
-        const updatedResolved = await api.getResolvedReminders();
+        const updatedResolved = await api.getResolvedReminders(sortRef.current);
         setResolvedReminders(updatedResolved);
       } else {
-        const updatedReminders = await api.getUnresolvedReminders();
+        const updatedReminders = await api.getUnresolvedReminders(sortRef.current);
         setReminders(updatedReminders);
       }
     } catch (error) {
@@ -85,13 +122,97 @@ export function useReminders() {
     }
   }, [showResolvedReminders]);
 
-  const filteredUnresolvedReminders = reminders.filter(reminder =>
-    reminder.text.toLowerCase().includes(searchQuery.toLowerCase())
-  );
+  // The reminder:created / reminder:updated events below put the result in the lists
+  // Validation errors are passed on, so a form can show what's wrong
+  const createReminder = useCallback(async (reminder: ReminderDraft) => {
+    return api.createReminder(reminder);
+  }, []);
+
+  const updateReminder = useCallback(async (reminderId: string, patch: ReminderPatch) => {
+    return api.updateReminder(parseInt(reminderId), patch);
+  }, []);
+
+  const snoozeReminder = useCallback(async (reminderId: string, until: string) => {
+    return api.snoozeReminder(parseInt(reminderId), until);
+  }, []);
+
+  const setReminderPriority = useCallback(async (reminderId: string, priority: Priority) => {
+    return api.setReminderPriority(parseInt(reminderId), priority);
+  }, []);
+
+  // Keep both lists in step with the backend's reminder:* events, so changes made by
+  // the AI, another window or juli-cli show up without refetching
+  useEffect(() => {
+    const sameId = (a: Reminder, id: number | string) => String(a.id) === String(id);
+    const withReminder = (list: Reminder[], reminder: Reminder, belongs: boolean) => {
+      const rest = list.filter(r => !sameId(r, reminder.id));
+      return belongs ? [...rest, reminder].sort(COMPARE_REMINDERS[sortRef.current]) : rest;
+    };
+    const place = (reminder: Reminder) => {
+      setReminders(list => withReminder(list, reminder, !reminder.resolved));
+      // The resolved list is only loaded while it's shown; toggling it on fetches it fresh
+      setResolvedReminders(list => withReminder(list, reminder, reminder.resolved));
+    };
+
+    const unlisteners = [
+      api.onReminderChanged('reminder:created', place),
+      api.onReminderChanged('reminder:updated', place),
+      api.onReminderChanged('reminder:resolved', place),
+      api.onReminderDeleted(({ id }) => {
+        setReminders(list => list.filter(r => !sameId(r, id)));
+        setResolvedReminders(list => list.filter(r => !sameId(r, id)));
+      }),
+    ];
+
+    return () => {
+      for (const unlisten of unlisteners) {
+        void unlisten.then((stop) => stop());
+      }
+    };
+  }, []);
+
+  // Another sort picked: fetch the lists again in that order
+  useEffect(() => {
+    if (sortRef.current === sort) return;
+    sortRef.current = sort;
+    void loadUnresolvedReminders();
+    if (showResolvedReminders) {
+      void loadResolvedReminders();
+    }
+  }, [sort, showResolvedReminders, loadUnresolvedReminders, loadResolvedReminders]);
+
+  // Searching happens in the backend's full-text index, so it also finds
+  // word variants ("calls" for "call") and matches in tags
+  useEffect(() => {
+    if (!searchQuery.trim()) {
+      setSearchRanking(null);
+      return;
+    }
+
+    let cancelled = false;
+    api.search({ query: searchQuery, scope: 'reminders', limit: 1000 })
+      .then(hits => {
+        if (!cancelled) {
+          setSearchRanking(new Map(hits.map((hit, index) => [String(hit.id), index])));
+        }
+      })
+      .catch(error => console.error('Failed to search reminders:', error));
+
+    return () => {
+      cancelled = true;
+    };
+  }, [searchQuery, reminders, resolvedReminders]);
+
+  const applySearch = (list: Reminder[]) => {
+    if (!searchRanking) return list;
+    return list
+      .filter(reminder => searchRanking.has(String(reminder.id)))
+      .sort((a, b) => searchRanking.get(String(a.id))! - searchRanking.get(String(b.id))!);
+  };
+
+  const filteredUnresolvedReminders = applySearch(reminders);
 
-  const filteredResolvedReminders = resolvedReminders.filter(reminder =>
-    reminder.text.toLowerCase().includes(searchQuery.toLowerCase())
-  );
+  const filteredResolvedReminders = applySearch(resolvedReminders);
 
   const resetResolvedView = useCallback(() => {
     setShowResolvedReminders(false);
@@ -109,6 +230,10 @@ export function useReminders() {
     resolveReminder,
     unresolveReminder,
     deleteReminder,
+    createReminder,
+    updateReminder,
+    snoozeReminder,
+    setReminderPriority,
     loadUnresolvedReminders,
     loadResolvedReminders,
     filteredUnresolvedReminders,
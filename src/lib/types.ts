@@ -1,6 +1,6 @@
 export type View = 'today' | 'history' | 'reminders' | 'ai-logs';
 
-export type StatusType = 'saving' | 'ai-running' | 'ai-success' | 'ai-no-action' | null;
+export type StatusType = 'saving' | 'ai-queued' | 'ai-running' | 'ai-retrying' | 'ai-success' | 'ai-no-action' | 'setup-required' | 'error' | null;
 
 export interface StatusState {
   type: StatusType;
@@ -14,14 +14,108 @@ export interface DayNote {
   for_date: string;
 }
 
+export interface NoteRevision {
+  id: number;
+  note_id: number;
+  text: string;
+  size: number; // characters
+  created_at: string; // UTC, when the revision was started
+  updated_at: string; // UTC, its last save
+}
+
+export interface RevisionDiffLine {
+  kind: 'equal' | 'insert' | 'delete';
+  old_line: number | null;
+  new_line: number | null;
+  text: string;
+}
+
+export interface RevisionDiff {
+  from_revision_id: number;
+  to_revision_id: number;
+  lines: RevisionDiffLine[];
+  unified: string;
+}
+
 export interface Reminder {
   id: string;
-  created_from_note_id: string;
+  created_from_note_id: number | null; // null for a reminder added by hand
   text: string;
   resolved: boolean;
   tags?: string;
-  created_at?: string;
+  created_at?: string; // UTC, RFC 3339 ("2026-10-16T19:04:05Z")
   due_date?: string;
+  due_time: string | null; // "15:00", in `timezone`
+  due_at: string | null; // the moment it's due, e.g. "2026-10-16T15:00:00-04:00"
+  timezone: string | null; // IANA name the due time is in, e.g. "America/New_York"
+  source: 'ai' | 'manual';
+  recurrence: string | null; // RRULE, e.g. "FREQ=MONTHLY;BYMONTHDAY=1"
+  repeats: string | null; // the recurrence in words, e.g. "every month on day 1"
+  occurrence: number; // 1 for the first of a recurring series
+  recurs_from_id: number | null; // the resolved occurrence this one follows
+  snooze_count: number; // how many times it was snoozed
+  original_due_date: string | null; // the due date before the first snooze
+  priority: Priority;
+  priority_set_by_user: boolean; // the AI doesn't change a priority the user picked
+}
+
+export type Priority = 'low' | 'normal' | 'high' | 'urgent';
+
+// How the reminder lists are ordered; 'created' is newest first
+export type ReminderSort = 'due' | 'priority' | 'created';
+
+// Sent by the backend whenever a reminder or note changes, whoever changed it
+export type ReminderChangeEvent = 'reminder:created' | 'reminder:updated' | 'reminder:resolved';
+
+export interface ReminderDeleted {
+  id: number;
+}
+
+export interface ReminderSource {
+  id: number;
+  reminder_id: number;
+  note_id: number;
+  ai_log_id: number | null;
+  action: 'CREATE' | 'UPDATE';
+  start_line: number; // 1-based, inclusive, in the note as it was analyzed
+  end_line: number;
+  excerpt: string;
+  created_at: string;
+}
+
+// A reminder's editable fields: a new manual reminder, or the user's version of a proposal
+export interface ReminderDraft {
+  text: string;
+  tags: string | null;
+  due_date: string | null;
+  due_time?: string | null; // "15:00"; needs a due_date
+  timezone?: string | null; // defaults to the user's timezone
+  recurrence?: string | null;
+  priority?: Priority | null; // null is 'normal'
+}
+
+// update_reminder: leave a field out to keep it, send null to clear tags, the due date, the time or the recurrence
+// A priority sent here counts as the user's choice, like set_reminder_priority
+export type ReminderPatch = Partial<ReminderDraft>;
+
+export interface ProposalSource {
+  start_line: number;
+  end_line: number;
+  excerpt: string;
+}
+
+export interface ReminderProposal {
+  id: number;
+  note_id: number;
+  ai_log_id: number | null;
+  action: 'CREATE' | 'UPDATE';
+  reminder_id: number | null; // UPDATE: the reminder it changes; CREATE: the new reminder once accepted
+  proposed: ReminderDraft;
+  before: ReminderDraft | null; // UPDATE only: the reminder as it was when proposed
+  status: 'pending' | 'accepted' | 'rejected';
+  created_at: string;
+  decided_at: string | null;
+  sources: ProposalSource[];
 }
 
 export interface AiLog {
@@ -31,6 +125,109 @@ export interface AiLog {
   response: string;
   success: boolean;
   reasoning: string;
-  reminders_count: number;
+  reminders_count: number; // reminders that passed validation
+  created_at: string;
+  rejections: AiLogRejection[];
+  attempts: RequestAttempt[]; // every HTTP request it took, retries included
+}
+
+export interface RequestAttempt {
+  attempt: number;
+  status: number | null; // null when no response came back (timeout, connection refused, ...)
+  error: string | null;
+  duration_ms: number;
+  retry_in_ms: number | null;
+}
+
+// What add_note returns; job_id matches the job_id of its analysis:* events
+export interface SavedNote {
+  note_id: number;
+  job_id: number;
+}
+
+// A note waiting for the background worker to analyze it
+export interface PendingAnalysis {
+  id: number;
+  note_id: number;
+  saved_on: string;
+  attempts: number; // failed tries so far
+  last_error: string | null;
+  last_error_kind: JuliErrorKind | null;
+  next_attempt_at: string;
   created_at: string;
 }
+
+// Payload of the "analysis:queued" event
+export interface AnalysisQueued {
+  job_id: number;
+  note_id: number;
+}
+
+// Payload of the "analysis:started" event
+export interface AnalysisStarted {
+  job_id: number;
+  note_id: number;
+  attempt: number; // 1 for the first try
+}
+
+// Payload of the "analysis:completed" event
+export interface AnalysisCompleted {
+  job_id: number;
+  note_id: number;
+  created: number[];
+  updated: number[];
+  proposals: number[]; // review mode only
+}
+
+// Payload of the "analysis:failed" event
+export interface AnalysisFailed {
+  job_id: number;
+  note_id: number;
+  kind: JuliErrorKind;
+  error: JuliError;
+  will_retry: boolean; // still queued, e.g. while offline
+  next_attempt_at: string | null;
+}
+
+// A reminder from the AI's answer that failed validation
+export interface AiLogRejection {
+  item: string; // the reminder as the AI sent it, as JSON
+  reason: string;
+}
+
+export type SearchScope = 'all' | 'notes' | 'reminders';
+
+export interface SearchQuery {
+  query: string;
+  scope?: SearchScope;
+  from_date?: string; // YYYY-MM-DD, inclusive
+  to_date?: string;
+  tags?: string[];
+  resolved?: boolean;
+  limit?: number;
+}
+
+export interface SearchHit {
+  kind: 'note' | 'reminder';
+  id: number;
+  text: string;
+  snippet: string;
+  highlights: [number, number][]; // [start, end) offsets into snippet
+  rank: number; // lower is better
+  for_date?: string | null;
+  tags?: string | null;
+  resolved?: boolean | null;
+  due_date?: string | null;
+}
+
+export type JuliErrorKind = 'Database' | 'NotFound' | 'Config' | 'AiTransport' | 'AiParse' | 'Validation';
+
+export interface JuliError {
+  kind: JuliErrorKind;
+  message: string;
+  details?: string | null;
+}
+
+export function isJuliError(error: unknown): error is JuliError {
+  return typeof error === 'object' && error !== null && 'kind' in error && 'message' in error;
+}
@@ -1,5 +1,6 @@
 import { invoke } from "@tauri-apps/api/core";
-import type { DayNote, Reminder, AiLog } from "../lib/types";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+import type { DayNote, Reminder, AiLog, SearchQuery, SearchHit, NoteRevision, RevisionDiff, ReminderSource, ReminderProposal, ReminderDraft, ReminderPatch, Priority, ReminderSort, SavedNote, ReminderChangeEvent, ReminderDeleted, PendingAnalysis, AnalysisQueued, AnalysisStarted, AnalysisCompleted, AnalysisFailed } from "../lib/types";
 
 // Database
 export async function initDb(): Promise<void> {
@@ -20,31 +21,128 @@ export async function getAllNotes(): Promise<DayNote[]> {
   return invoke<DayNote[]>('get_all_notes');
 }
 
-export async function addNote(text: string, forDate: string): Promise<number> {
-  return invoke<number>('add_note', { text, forDate });
+// Returns as soon as the note is saved; the analysis runs in the background
+export async function addNote(text: string, forDate: string): Promise<SavedNote> {
+  return invoke<SavedNote>('add_note', { text, forDate });
+}
+
+// Note history
+export async function getNoteRevisions(forDate: string): Promise<NoteRevision[]> {
+  return invoke<NoteRevision[]>('get_note_revisions', { forDate });
+}
+
+export async function diffNoteRevisions(fromRevisionId: number, toRevisionId: number): Promise<RevisionDiff> {
+  return invoke<RevisionDiff>('diff_note_revisions', { fromRevisionId, toRevisionId });
+}
+
+export async function restoreNoteRevision(revisionId: number): Promise<DayNote> {
+  return invoke<DayNote>('restore_note_revision', { revisionId });
 }
 
 // Reminders
-export async function getUnresolvedReminders(): Promise<Reminder[]> {
-  return invoke<Reminder[]>('get_unresolved_reminders');
+// Sorted by due date unless asked otherwise
+export async function getUnresolvedReminders(sort?: ReminderSort): Promise<Reminder[]> {
+  return invoke<Reminder[]>('get_unresolved_reminders', { sort });
 }
 
-export async function getResolvedReminders(): Promise<Reminder[]> {
-  return invoke<Reminder[]>('get_resolved_reminders');
+export async function getResolvedReminders(sort?: ReminderSort): Promise<Reminder[]> {
+  return invoke<Reminder[]>('get_resolved_reminders', { sort });
 }
 
-export async function resolveReminder(reminderId: number): Promise<void> {
-  return invoke('resolve_reminder', { reminderId });
+// Returns the id of the next occurrence when the reminder repeats
+export async function resolveReminder(reminderId: number): Promise<number | null> {
+  return invoke<number | null>('resolve_reminder', { reminderId });
 }
 
 export async function unresolveReminder(reminderId: number): Promise<void> {
   return invoke('unresolve_reminder', { reminderId });
 }
 
+// The user's own priority; the AI keeps it from then on
+export async function setReminderPriority(reminderId: number, priority: Priority): Promise<Reminder> {
+  return invoke<Reminder>('set_reminder_priority', { reminderId, priority });
+}
+
+// Push a reminder out: a date (YYYY-MM-DD), "1d", "2w", "tomorrow", "next monday", "next week" or "eow"
+export async function snoozeReminder(reminderId: number, until: string): Promise<Reminder> {
+  return invoke<Reminder>('snooze_reminder', { reminderId, until });
+}
+
 export async function deleteReminder(reminderId: number): Promise<void> {
   return invoke('delete_reminder', { reminderId });
 }
 
+// Add a reminder by hand; the backend rejects empty text and due dates that aren't YYYY-MM-DD
+export async function createReminder(reminder: ReminderDraft): Promise<Reminder> {
+  return invoke<Reminder>('create_reminder', { reminder });
+}
+
+// Only the fields in patch change; null clears tags or the due date
+export async function updateReminder(reminderId: number, patch: ReminderPatch): Promise<Reminder> {
+  return invoke<Reminder>('update_reminder', { reminderId, patch });
+}
+
+export async function getReminderSources(reminderId: number): Promise<ReminderSource[]> {
+  return invoke<ReminderSource[]>('get_reminder_sources', { reminderId });
+}
+
+// Proposals (review mode)
+export async function getPendingProposals(): Promise<ReminderProposal[]> {
+  return invoke<ReminderProposal[]>('get_pending_proposals');
+}
+
+// Resolves to the id of the created or updated reminder
+export async function acceptProposal(proposalId: number, edits?: ReminderDraft): Promise<number> {
+  if (edits) {
+    return invoke<number>('accept_proposal_with_edits', { proposalId, edits });
+  }
+  return invoke<number>('accept_proposal', { proposalId });
+}
+
+export async function rejectProposal(proposalId: number): Promise<void> {
+  return invoke('reject_proposal', { proposalId });
+}
+
+// Analysis queue
+export async function getPendingAnalyses(): Promise<PendingAnalysis[]> {
+  return invoke<PendingAnalysis[]>('get_pending_analyses');
+}
+
+export async function onAnalysisQueued(handler: (event: AnalysisQueued) => void): Promise<UnlistenFn> {
+  return listen<AnalysisQueued>('analysis:queued', (event) => handler(event.payload));
+}
+
+export async function onAnalysisStarted(handler: (event: AnalysisStarted) => void): Promise<UnlistenFn> {
+  return listen<AnalysisStarted>('analysis:started', (event) => handler(event.payload));
+}
+
+export async function onAnalysisCompleted(handler: (event: AnalysisCompleted) => void): Promise<UnlistenFn> {
+  return listen<AnalysisCompleted>('analysis:completed', (event) => handler(event.payload));
+}
+
+export async function onAnalysisFailed(handler: (event: AnalysisFailed) => void): Promise<UnlistenFn> {
+  return listen<AnalysisFailed>('analysis:failed', (event) => handler(event.payload));
+}
+
+// Change events: every write to reminders and notes, from this window, the AI, juli-cli, ...
+// payload: the reminder as it is now
+export async function onReminderChanged(eventName: ReminderChangeEvent, handler: (reminder: Reminder) => void): Promise<UnlistenFn> {
+  return listen<Reminder>(eventName, (event) => handler(event.payload));
+}
+
+export async function onReminderDeleted(handler: (event: ReminderDeleted) => void): Promise<UnlistenFn> {
+  return listen<ReminderDeleted>('reminder:deleted', (event) => handler(event.payload));
+}
+
+export async function onNoteSaved(handler: (note: DayNote) => void): Promise<UnlistenFn> {
+  return listen<DayNote>('note:saved', (event) => handler(event.payload));
+}
+
+// Search
+export async function search(query: SearchQuery): Promise<SearchHit[]> {
+  return invoke<SearchHit[]>('search', { query });
+}
+
 // AI Logs
 export async function getAllAiLogs(): Promise<AiLog[]> {
   return invoke<AiLog[]>('get_all_ai_logs');